@@ -0,0 +1,4 @@
+. ../.env
+export TENDERMINT_URL
+export DATABASE_URL
+cargo run -- "$@"
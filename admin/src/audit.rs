@@ -0,0 +1,562 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::identity;
+use std::future::Future;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use bigdecimal::BigDecimal;
+use chain::services::namada as namada_service;
+use chrono::Utc;
+use deadpool_diesel::postgres::Object;
+use diesel::PgConnection;
+use orm::audit::{AuditKindDb, AuditMismatchInsertDb};
+use orm::balances::BalancesInsertDb;
+use orm::bond::BondInsertDb;
+use orm::crawler_state::{CrawlerNameDb, CrawlerStateDb};
+use orm::pos_rewards::PosRewardInsertDb;
+use orm::unbond::UnbondInsertDb;
+use orm::validators::ValidatorDb;
+use shared::balance::Amount;
+use shared::block::{BlockHeight, Epoch};
+use shared::error::ContextDbInteractError;
+use shared::id::Id;
+use tendermint_rpc::HttpClient;
+use tokio::time::sleep;
+
+use crate::repository::audit::{
+    self as repository, BalanceSample, DelegationSample,
+};
+use crate::repository::crawler_state;
+
+/// Attempts at auditing an item, the crawlers moving while the node is
+/// queried make an attempt inconclusive
+const MAX_ATTEMPTS: usize = 5;
+const RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+type Mismatches = Vec<AuditMismatchInsertDb>;
+
+pub struct Sample {
+    pub balances: Vec<BalanceSample>,
+    pub delegations: Vec<DelegationSample>,
+    pub rewards: Vec<DelegationSample>,
+    pub validators: Vec<ValidatorDb>,
+}
+
+/// Picks `sample_size` random balances, delegations, rewards and validators
+/// to audit. Balances and rewards are picked among the pairs that should have
+/// one, so that missing rows are audited too.
+pub async fn sample(conn: &Object, sample_size: i64) -> anyhow::Result<Sample> {
+    conn.interact(move |conn| {
+        anyhow::Ok(Sample {
+            balances: repository::sample_balances(conn, sample_size)?,
+            delegations: repository::sample_delegations(conn, sample_size)?,
+            rewards: repository::sample_rewards(conn, sample_size)?,
+            validators: repository::sample_validators(conn, sample_size)?,
+        })
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+}
+
+/// Compares the sampled items with the node, records the mismatches and
+/// overwrites them with the node values when `repair` is set.
+///
+/// Each item is compared at a fixed point: balances at the block the chain
+/// crawler is at and voting powers at the epoch the PoS crawler is at, while
+/// holding that crawler state so it can't move until the comparison is
+/// recorded. The node only serves the latest bonds, unbonds and rewards, so
+/// those are compared when the chain crawler is at the node last block, and
+/// rewards only once the rewards crawler completed the current epoch.
+pub async fn audit(
+    client: &HttpClient,
+    conn: &Object,
+    sample: Sample,
+    repair: bool,
+) -> anyhow::Result<Mismatches> {
+    let mut mismatches = vec![];
+
+    for balance in sample.balances {
+        let found =
+            retry(|| audit_balance(client, conn, &balance, repair)).await?;
+        match found {
+            Some(found) => mismatches.extend(found),
+            None => tracing::warn!(
+                "Skipped balance of {} in {}, the chain crawler kept moving",
+                balance.owner,
+                balance.token
+            ),
+        }
+    }
+
+    for delegation in sample.delegations {
+        let found =
+            retry(|| audit_delegation(client, conn, &delegation, repair))
+                .await?;
+        match found {
+            Some(found) => mismatches.extend(found),
+            None => tracing::warn!(
+                "Skipped delegation of {} to {}, the chain crawler is not at \
+                 the node last block",
+                delegation.address,
+                delegation.validator
+            ),
+        }
+    }
+
+    for delegation in sample.rewards {
+        let found =
+            retry(|| audit_reward(client, conn, &delegation, repair)).await?;
+        match found {
+            Some(found) => mismatches.extend(found),
+            None => tracing::warn!(
+                "Skipped rewards of {} from {}, the chain crawler is not at \
+                 the node last block or the rewards crawler has not completed \
+                 the current epoch",
+                delegation.address,
+                delegation.validator
+            ),
+        }
+    }
+
+    for validator in sample.validators {
+        let found =
+            retry(|| audit_voting_power(client, conn, &validator, repair))
+                .await?;
+        match found {
+            Some(found) => mismatches.extend(found),
+            None => tracing::warn!(
+                "Skipped voting power of {}, the PoS crawler kept moving",
+                validator.namada_address
+            ),
+        }
+    }
+
+    Ok(mismatches)
+}
+
+async fn audit_balance(
+    client: &HttpClient,
+    conn: &Object,
+    balance: &BalanceSample,
+    repair: bool,
+) -> anyhow::Result<Option<Mismatches>> {
+    let block_height = get_crawler_state(conn, CrawlerNameDb::Chain)
+        .await?
+        .last_processed_block
+        .context("The chain crawler has not processed any block yet")?;
+
+    let expected = namada_service::query_balance_at_height(
+        client,
+        &Id::Account(balance.owner.clone()),
+        &Id::Account(balance.token.clone()),
+        block_height as BlockHeight,
+    )
+    .await?;
+
+    let owner = balance.owner.clone();
+    let token = balance.token.clone();
+    with_locked_crawler_state(conn, CrawlerNameDb::Chain, move |conn, state| {
+        if state.last_processed_block != Some(block_height) {
+            return anyhow::Ok(None);
+        }
+
+        let indexed =
+            repository::get_balance(conn, &owner, &token)?.map(Amount::from);
+        if indexed
+            .as_ref()
+            .map_or(expected.is_zero(), |a| *a == expected)
+        {
+            return anyhow::Ok(Some(vec![]));
+        }
+
+        let mismatches = vec![AuditMismatchInsertDb {
+            kind: AuditKindDb::Balance,
+            address: owner.clone(),
+            target: Some(token.clone()),
+            epoch: None,
+            indexed: indexed.map(|amount| amount.to_string()),
+            expected: Some(expected.to_string()),
+            block_height: Some(block_height),
+            repaired: repair,
+            timestamp: Utc::now().naive_utc(),
+        }];
+
+        if repair {
+            repository::repair_balance(
+                conn,
+                BalancesInsertDb {
+                    owner,
+                    token,
+                    raw_amount: to_raw_amount(&expected),
+                },
+            )?;
+        }
+        repository::insert_mismatches(conn, mismatches.clone())?;
+
+        anyhow::Ok(Some(mismatches))
+    })
+    .await
+}
+
+async fn audit_delegation(
+    client: &HttpClient,
+    conn: &Object,
+    delegation: &DelegationSample,
+    repair: bool,
+) -> anyhow::Result<Option<Mismatches>> {
+    let Some((block_height, (expected_bonds, expected_unbonds))) =
+        query_latest(client, async {
+            namada_service::query_all_bonds_and_unbonds(
+                client,
+                Some(Id::Account(delegation.address.clone())),
+                Some(Id::Account(delegation.validator.clone())),
+            )
+            .await
+        })
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let delegation = delegation.clone();
+    with_locked_crawler_state(conn, CrawlerNameDb::Chain, move |conn, state| {
+        if state.last_processed_block != Some(block_height) {
+            return anyhow::Ok(None);
+        }
+
+        let expected_bonds = expected_bonds
+            .into_iter()
+            .map(|bond| BondInsertDb::from_bond(bond, delegation.validator_id))
+            .collect::<Vec<_>>();
+        let expected_unbonds = expected_unbonds
+            .into_iter()
+            .map(|unbond| {
+                UnbondInsertDb::from_unbond(unbond, delegation.validator_id)
+            })
+            .collect::<Vec<_>>();
+
+        let (indexed_bonds, indexed_unbonds) = repository::get_delegation(
+            conn,
+            &delegation.address,
+            delegation.validator_id,
+        )?;
+
+        let mut mismatches = vec![];
+        for (kind, indexed, expected) in [
+            (
+                AuditKindDb::Bond,
+                indexed_bonds
+                    .iter()
+                    .map(|bond| (bond.start, bond.raw_amount.clone()))
+                    .collect::<BTreeMap<_, _>>(),
+                expected_bonds
+                    .iter()
+                    .map(|bond| (bond.start, bond.raw_amount.clone()))
+                    .collect::<BTreeMap<_, _>>(),
+            ),
+            (
+                AuditKindDb::Unbond,
+                indexed_unbonds
+                    .iter()
+                    .map(|unbond| {
+                        (unbond.withdraw_epoch, unbond.raw_amount.clone())
+                    })
+                    .collect(),
+                expected_unbonds
+                    .iter()
+                    .map(|unbond| {
+                        (unbond.withdraw_epoch, unbond.raw_amount.clone())
+                    })
+                    .collect(),
+            ),
+        ] {
+            let epochs = indexed.keys().chain(expected.keys()).copied();
+            for epoch in epochs.collect::<BTreeSet<_>>() {
+                let indexed = indexed.get(&epoch).cloned().map(Amount::from);
+                let expected = expected.get(&epoch).cloned().map(Amount::from);
+                if indexed == expected {
+                    continue;
+                }
+
+                mismatches.push(AuditMismatchInsertDb {
+                    kind,
+                    address: delegation.address.clone(),
+                    target: Some(delegation.validator.clone()),
+                    epoch: Some(epoch),
+                    indexed: indexed.map(|amount| amount.to_string()),
+                    expected: expected.map(|amount| amount.to_string()),
+                    block_height: Some(block_height),
+                    repaired: repair,
+                    timestamp: Utc::now().naive_utc(),
+                });
+            }
+        }
+
+        if mismatches.is_empty() {
+            return anyhow::Ok(Some(mismatches));
+        }
+
+        if repair {
+            repository::repair_delegation(
+                conn,
+                &delegation.address,
+                delegation.validator_id,
+                expected_bonds,
+                expected_unbonds,
+            )?;
+        }
+        repository::insert_mismatches(conn, mismatches.clone())?;
+
+        anyhow::Ok(Some(mismatches))
+    })
+    .await
+}
+
+async fn audit_reward(
+    client: &HttpClient,
+    conn: &Object,
+    delegation: &DelegationSample,
+    repair: bool,
+) -> anyhow::Result<Option<Mismatches>> {
+    let Some((block_height, (epoch, expected))) = query_latest(client, async {
+        let epoch = namada_service::get_current_epoch(client).await?;
+        let rewards = namada_service::query_rewards(
+            client,
+            &Id::Account(delegation.address.clone()),
+            &Id::Account(delegation.validator.clone()),
+        )
+        .await?;
+
+        anyhow::Ok((epoch, rewards))
+    })
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let delegation = delegation.clone();
+    with_locked_crawler_state(conn, CrawlerNameDb::Chain, move |conn, state| {
+        if state.last_processed_block != Some(block_height) {
+            return anyhow::Ok(None);
+        }
+
+        let rewards_state =
+            crawler_state::lock_crawler_state(conn, CrawlerNameDb::Rewards)?;
+        if !rewards_completed(rewards_state.as_ref(), epoch) {
+            return anyhow::Ok(None);
+        }
+
+        let indexed = repository::get_reward(
+            conn,
+            &delegation.address,
+            delegation.validator_id,
+        )?
+        .map(Amount::from);
+        if indexed
+            .as_ref()
+            .map_or(expected.is_zero(), |a| *a == expected)
+        {
+            return anyhow::Ok(Some(vec![]));
+        }
+
+        let mismatches = vec![AuditMismatchInsertDb {
+            kind: AuditKindDb::Reward,
+            address: delegation.address.clone(),
+            target: Some(delegation.validator),
+            epoch: Some(epoch as i32),
+            indexed: indexed.map(|amount| amount.to_string()),
+            expected: Some(expected.to_string()),
+            block_height: Some(block_height),
+            repaired: repair,
+            timestamp: Utc::now().naive_utc(),
+        }];
+
+        if repair {
+            repository::repair_reward(
+                conn,
+                PosRewardInsertDb {
+                    owner: delegation.address,
+                    validator_id: delegation.validator_id,
+                    raw_amount: to_raw_amount(&expected),
+                },
+            )?;
+        }
+        repository::insert_mismatches(conn, mismatches.clone())?;
+
+        anyhow::Ok(Some(mismatches))
+    })
+    .await
+}
+
+async fn audit_voting_power(
+    client: &HttpClient,
+    conn: &Object,
+    validator: &ValidatorDb,
+    repair: bool,
+) -> anyhow::Result<Option<Mismatches>> {
+    let epoch = get_crawler_state(conn, CrawlerNameDb::Pos)
+        .await?
+        .last_processed_epoch
+        .context("The PoS crawler has not processed any epoch yet")?;
+
+    let stake = namada_service::query_validator_stake(
+        client,
+        &Id::Account(validator.namada_address.clone()),
+        epoch as Epoch,
+    )
+    .await?;
+    // Same conversion as the PoS crawler
+    let expected = f32::from_str(&stake)
+        .with_context(|| format!("Invalid validator stake {}", stake))?
+        as i32;
+
+    let validator = validator.clone();
+    with_locked_crawler_state(conn, CrawlerNameDb::Pos, move |conn, state| {
+        if state.last_processed_epoch != Some(epoch) {
+            return anyhow::Ok(None);
+        }
+
+        let indexed = repository::get_voting_power(conn, validator.id)?;
+        if indexed == Some(expected) {
+            return anyhow::Ok(Some(vec![]));
+        }
+
+        let mismatches = vec![AuditMismatchInsertDb {
+            kind: AuditKindDb::VotingPower,
+            address: validator.namada_address.clone(),
+            target: None,
+            epoch: Some(epoch),
+            indexed: indexed.map(|voting_power| voting_power.to_string()),
+            expected: Some(expected.to_string()),
+            block_height: None,
+            repaired: repair,
+            timestamp: Utc::now().naive_utc(),
+        }];
+
+        if repair {
+            repository::repair_voting_power(conn, validator.id, expected)?;
+        }
+        repository::insert_mismatches(conn, mismatches.clone())?;
+
+        anyhow::Ok(Some(mismatches))
+    })
+    .await
+}
+
+/// Runs `query` against the node latest state, along with the block height
+/// of that state. `None` if a block was committed while querying.
+async fn query_latest<T>(
+    client: &HttpClient,
+    query: impl Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<Option<(i32, T)>> {
+    let before = namada_service::query_last_block_height(client).await?;
+    let result = query.await?;
+    let after = namada_service::query_last_block_height(client).await?;
+
+    Ok((before == after).then_some((before as i32, result)))
+}
+
+/// Runs `attempt` until it is conclusive, `None` if it never was
+async fn retry<F, Fut>(mut attempt: F) -> anyhow::Result<Option<Mismatches>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<Option<Mismatches>>>,
+{
+    for _ in 0..MAX_ATTEMPTS {
+        if let Some(mismatches) = attempt().await? {
+            return Ok(Some(mismatches));
+        }
+        sleep(RETRY_INTERVAL).await;
+    }
+
+    Ok(None)
+}
+
+async fn get_crawler_state(
+    conn: &Object,
+    crawler: CrawlerNameDb,
+) -> anyhow::Result<CrawlerStateDb> {
+    let name = crawler.to_string();
+
+    conn.interact(move |conn| crawler_state::get_crawler_state(conn, crawler))
+        .await
+        .context_db_interact_error()
+        .and_then(identity)?
+        .ok_or_else(|| anyhow!("The {} crawler has not started yet", name))
+}
+
+/// Runs `check` in a transaction holding the crawler state row, so the
+/// crawler can't commit meanwhile
+async fn with_locked_crawler_state<T, F>(
+    conn: &Object,
+    crawler: CrawlerNameDb,
+    check: F,
+) -> anyhow::Result<Option<T>>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut PgConnection, CrawlerStateDb) -> anyhow::Result<Option<T>>
+        + Send
+        + 'static,
+{
+    conn.interact(move |conn| {
+        conn.build_transaction()
+            .read_write()
+            .run(|transaction_conn| {
+                match crawler_state::lock_crawler_state(
+                    transaction_conn,
+                    crawler,
+                )? {
+                    Some(state) => check(transaction_conn, state),
+                    None => anyhow::Ok(None),
+                }
+            })
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+}
+
+/// Rewards are written by the rewards crawler, spread over the epoch, so they
+/// only match the node once it completed `epoch`
+fn rewards_completed(
+    rewards_state: Option<&CrawlerStateDb>,
+    epoch: Epoch,
+) -> bool {
+    rewards_state.and_then(|state| state.last_processed_epoch)
+        == Some(epoch as i32)
+}
+
+fn to_raw_amount(amount: &Amount) -> BigDecimal {
+    BigDecimal::from_str(&amount.to_string()).expect("Invalid amount")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rewards_state(last_processed_epoch: Option<i32>) -> CrawlerStateDb {
+        CrawlerStateDb {
+            name: CrawlerNameDb::Rewards,
+            last_processed_block: None,
+            last_processed_epoch,
+            first_block_in_epoch: None,
+            timestamp: chrono::DateTime::from_timestamp(0, 0)
+                .unwrap()
+                .naive_utc(),
+            first_processed_block: None,
+        }
+    }
+
+    /// Test that rewards are compared only once the rewards crawler completed
+    /// the epoch the node was queried at.
+    #[test]
+    fn test_rewards_completed() {
+        assert!(rewards_completed(Some(&rewards_state(Some(5))), 5));
+        assert!(!rewards_completed(Some(&rewards_state(Some(4))), 5));
+        assert!(!rewards_completed(Some(&rewards_state(Some(6))), 5));
+        assert!(!rewards_completed(Some(&rewards_state(None)), 5));
+        assert!(!rewards_completed(None, 5));
+    }
+}
@@ -0,0 +1,95 @@
+use core::fmt;
+use std::fmt::Display;
+
+use clap_verbosity_flag::{InfoLevel, Verbosity};
+use orm::crawler_state::CrawlerNameDb;
+
+#[derive(clap::ValueEnum, Clone, Debug, Copy)]
+pub enum Crawler {
+    Chain,
+    Governance,
+    Parameters,
+    Pos,
+    Rewards,
+    Transactions,
+}
+
+impl Display for Crawler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", CrawlerNameDb::from(*self))
+    }
+}
+
+impl From<Crawler> for CrawlerNameDb {
+    fn from(value: Crawler) -> Self {
+        match value {
+            Crawler::Chain => Self::Chain,
+            Crawler::Governance => Self::Governance,
+            Crawler::Parameters => Self::Parameters,
+            Crawler::Pos => Self::Pos,
+            Crawler::Rewards => Self::Rewards,
+            Crawler::Transactions => Self::Transactions,
+        }
+    }
+}
+
+#[derive(clap::Subcommand)]
+pub enum Command {
+    /// Show the state of every crawler and how far behind the chain it is
+    Status,
+    /// Move the chain or transactions crawler back, deleting the rows it
+    /// derived afterwards. The epoch crawlers always resume from the current
+    /// epoch so they can't be rewound.
+    Rewind {
+        #[clap(long, value_enum)]
+        crawler: Crawler,
+
+        /// Last block height to keep
+        #[clap(long)]
+        height: u32,
+
+        /// Confirms the crawler, and any transactions range instance, is
+        /// stopped. A running crawler would overwrite the rewound state with
+        /// its next block.
+        #[clap(long)]
+        crawler_stopped: bool,
+    },
+    /// Reindex the transactions of a block height range, the transactions
+    /// crawler should be stopped meanwhile
+    Reprocess {
+        #[clap(long)]
+        from_height: u32,
+
+        #[clap(long)]
+        to_height: u32,
+    },
+    /// Check that every migration has been applied to the database
+    VerifyMigrations,
+    /// Compare a random sample of balances, bonds, unbonds, rewards and
+    /// voting powers with the node, recording the mismatches found
+    Audit {
+        /// Number of items sampled of each kind
+        #[clap(long, default_value_t = 100)]
+        sample_size: i64,
+
+        /// Overwrite the mismatching rows with the node values
+        #[clap(long)]
+        repair: bool,
+    },
+}
+
+#[derive(clap::Parser)]
+pub struct AppConfig {
+    #[clap(long, env)]
+    pub database_url: String,
+
+    /// Needed to compute the crawlers lag, to reprocess blocks and to audit
+    #[clap(long, env)]
+    pub tendermint_url: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Command,
+
+    #[command(flatten)]
+    pub verbosity: Verbosity<InfoLevel>,
+}
@@ -0,0 +1,4 @@
+pub mod app_state;
+pub mod audit;
+pub mod config;
+pub mod repository;
@@ -0,0 +1,276 @@
+use std::convert::identity;
+use std::sync::Arc;
+
+use admin::app_state::AppState;
+use admin::audit;
+use admin::config::{AppConfig, Command, Crawler};
+use admin::repository::{crawler_state, rewind};
+use anyhow::{anyhow, bail, Context};
+use chain::services::namada as namada_service;
+use chrono::Utc;
+use clap::Parser;
+use clap_verbosity_flag::LevelFilter;
+use deadpool_diesel::postgres::{Object, Pool};
+use orm::migrations::pending_migrations;
+use shared::error::ContextDbInteractError;
+use tendermint_rpc::HttpClient;
+use tracing::Level;
+use tracing_subscriber::FmtSubscriber;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let config = AppConfig::parse();
+
+    let log_level = match config.verbosity.log_level_filter() {
+        LevelFilter::Off => None,
+        LevelFilter::Error => Some(Level::ERROR),
+        LevelFilter::Warn => Some(Level::WARN),
+        LevelFilter::Info => Some(Level::INFO),
+        LevelFilter::Debug => Some(Level::DEBUG),
+        LevelFilter::Trace => Some(Level::TRACE),
+    };
+    if let Some(log_level) = log_level {
+        let subscriber =
+            FmtSubscriber::builder().with_max_level(log_level).finish();
+        tracing::subscriber::set_global_default(subscriber).unwrap();
+    }
+
+    let client = config
+        .tendermint_url
+        .map(|url| Arc::new(HttpClient::new(url.as_str()).unwrap()));
+
+    let app_state = AppState::new(config.database_url)?;
+    let conn = Arc::new(app_state.get_db_connection().await?);
+
+    match config.command {
+        Command::Status => status(&conn, client.as_deref()).await,
+        Command::Rewind {
+            crawler,
+            height,
+            crawler_stopped,
+        } => rewind(&conn, crawler, height, crawler_stopped).await,
+        Command::Reprocess {
+            from_height,
+            to_height,
+        } => {
+            let client =
+                client.context("--tendermint-url is required to reprocess")?;
+            reprocess(conn, app_state.pool(), client, from_height, to_height)
+                .await
+        }
+        Command::VerifyMigrations => verify_migrations(&conn).await,
+        Command::Audit {
+            sample_size,
+            repair,
+        } => {
+            let client =
+                client.context("--tendermint-url is required to audit")?;
+            audit(&conn, &client, sample_size, repair).await
+        }
+    }
+}
+
+/// Lag is measured against the chain when an RPC url is given, and against
+/// the wall clock otherwise
+async fn status(
+    conn: &Object,
+    client: Option<&HttpClient>,
+) -> anyhow::Result<()> {
+    let (states, ranges) = conn
+        .interact(|conn| {
+            anyhow::Ok((
+                crawler_state::get_crawler_states(conn)?,
+                crawler_state::get_transactions_ranges(conn)?,
+            ))
+        })
+        .await
+        .context_db_interact_error()
+        .and_then(identity)?;
+
+    let (last_block, current_epoch) = match client {
+        Some(client) => (
+            Some(namada_service::query_last_block_height(client).await?),
+            Some(namada_service::get_current_epoch(client).await?),
+        ),
+        None => (None, None),
+    };
+
+    let now = Utc::now().naive_utc();
+    for state in states {
+        let mut line = format!("{}:", state.name);
+
+        if let Some(block) = state.last_processed_block {
+            line.push_str(&format!(" block {}", block));
+            if let Some(last_block) = last_block {
+                line.push_str(&format!(
+                    " ({} behind)",
+                    last_block as i64 - block as i64
+                ));
+            }
+        }
+        if let Some(epoch) = state.last_processed_epoch {
+            line.push_str(&format!(" epoch {}", epoch));
+            if let Some(current_epoch) = current_epoch {
+                line.push_str(&format!(
+                    " ({} behind)",
+                    current_epoch as i64 - epoch as i64
+                ));
+            }
+        }
+        line.push_str(&format!(
+            " updated {}s ago",
+            (now - state.timestamp).num_seconds()
+        ));
+
+        println!("{}", line);
+    }
+
+    for range in ranges {
+        println!(
+            "transactions {}-{}: block {} ({} left) updated {}s ago",
+            range.from_block_height,
+            range.to_block_height,
+            range.last_processed_block,
+            range.to_block_height - range.last_processed_block,
+            (now - range.timestamp).num_seconds()
+        );
+    }
+
+    Ok(())
+}
+
+async fn rewind(
+    conn: &Object,
+    crawler: Crawler,
+    height: u32,
+    crawler_stopped: bool,
+) -> anyhow::Result<()> {
+    if !crawler_stopped {
+        bail!(
+            "Stop the {} crawler first, then confirm with --crawler-stopped",
+            crawler
+        );
+    }
+
+    conn.interact(move |conn| {
+        conn.build_transaction()
+            .read_write()
+            .run(|transaction_conn| {
+                let now = Utc::now().naive_utc();
+                match crawler {
+                    Crawler::Chain => {
+                        rewind::rewind_chain(transaction_conn, height, now)
+                    }
+                    Crawler::Transactions => rewind::rewind_transactions(
+                        transaction_conn,
+                        height,
+                        now,
+                    ),
+                    Crawler::Pos | Crawler::Rewards => Err(anyhow!(
+                        "The {} crawler always resumes from the current epoch \
+                         and can't be rewound",
+                        crawler
+                    )),
+                    Crawler::Governance | Crawler::Parameters => Err(anyhow!(
+                        "The {} crawler has no progress to rewind",
+                        crawler
+                    )),
+                }
+            })
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)?;
+
+    println!("{} crawler rewound", crawler);
+
+    Ok(())
+}
+
+async fn reprocess(
+    conn: Arc<Object>,
+    pool: Pool,
+    client: Arc<HttpClient>,
+    from: u32,
+    to: u32,
+) -> anyhow::Result<()> {
+    if from > to {
+        bail!("--from-height must not be above --to-height");
+    }
+
+    conn.interact(move |conn| {
+        conn.build_transaction()
+            .read_write()
+            .run(|transaction_conn| {
+                rewind::delete_transactions_rows_between(
+                    transaction_conn,
+                    from as i32,
+                    to as i32,
+                )
+            })
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)?;
+
+    transactions::crawler::reprocess(client, pool, from, to).await?;
+
+    println!("Blocks {} to {} reprocessed", from, to);
+
+    Ok(())
+}
+
+async fn verify_migrations(conn: &Object) -> anyhow::Result<()> {
+    let pending = pending_migrations(conn)
+        .await
+        .context_db_interact_error()
+        .and_then(identity)?;
+
+    if !pending.is_empty() {
+        for migration in &pending {
+            println!("pending: {}", migration);
+        }
+        bail!("{} migrations are not applied", pending.len());
+    }
+
+    println!("All migrations are applied");
+
+    Ok(())
+}
+
+async fn audit(
+    conn: &Object,
+    client: &HttpClient,
+    sample_size: i64,
+    repair: bool,
+) -> anyhow::Result<()> {
+    let sample = audit::sample(conn, sample_size).await?;
+    let mismatches = audit::audit(client, conn, sample, repair).await?;
+
+    for mismatch in &mismatches {
+        println!(
+            "{:?} {}{}{}: indexed {}, node {}",
+            mismatch.kind,
+            mismatch.address,
+            mismatch
+                .target
+                .as_ref()
+                .map(|target| format!(" -> {}", target))
+                .unwrap_or_default(),
+            mismatch
+                .epoch
+                .map(|epoch| format!(" at epoch {}", epoch))
+                .unwrap_or_default(),
+            mismatch.indexed.as_deref().unwrap_or("none"),
+            mismatch.expected.as_deref().unwrap_or("none"),
+        );
+    }
+
+    if !mismatches.is_empty() && !repair {
+        bail!("{} mismatches found", mismatches.len());
+    }
+
+    println!("{} mismatches found and repaired", mismatches.len());
+
+    Ok(())
+}
@@ -0,0 +1,282 @@
+use anyhow::Context;
+use bigdecimal::BigDecimal;
+use diesel::sql_types::{BigInt, Integer, Varchar};
+use diesel::upsert::excluded;
+use diesel::{
+    sql_query, BoolExpressionMethods, ExpressionMethods, OptionalExtension,
+    PgConnection, QueryDsl, QueryableByName, RunQueryDsl, SelectableHelper,
+};
+use orm::audit::AuditMismatchInsertDb;
+use orm::balances::BalancesInsertDb;
+use orm::bond::BondInsertDb;
+use orm::pos_rewards::PosRewardInsertDb;
+use orm::schema::{
+    audit_mismatches, balances, bonds, pos_rewards, unbonds, validators,
+};
+use orm::unbond::UnbondInsertDb;
+use orm::validators::ValidatorDb;
+
+diesel::define_sql_function!(fn random() -> diesel::sql_types::Double);
+
+#[derive(QueryableByName, Clone, Debug)]
+pub struct BalanceSample {
+    #[diesel(sql_type = Varchar)]
+    pub owner: String,
+    #[diesel(sql_type = Varchar)]
+    pub token: String,
+}
+
+#[derive(QueryableByName, Clone, Debug)]
+pub struct DelegationSample {
+    #[diesel(sql_type = Varchar)]
+    pub address: String,
+    #[diesel(sql_type = Integer)]
+    pub validator_id: i32,
+    #[diesel(sql_type = Varchar)]
+    pub validator: String,
+}
+
+/// Samples (owner, token) pairs that have either a balance or a balance
+/// change, so that missing balances are sampled too
+pub fn sample_balances(
+    transaction_conn: &mut PgConnection,
+    sample_size: i64,
+) -> anyhow::Result<Vec<BalanceSample>> {
+    sql_query(
+        "SELECT pairs.owner, pairs.token
+            FROM (
+                SELECT owner, token FROM balances
+                UNION
+                SELECT owner, token FROM balance_changes
+            ) AS pairs
+            ORDER BY random()
+            LIMIT $1",
+    )
+    .bind::<BigInt, _>(sample_size)
+    .load(transaction_conn)
+    .context("Failed to sample balances from db")
+}
+
+/// Samples (delegator, validator) pairs that have either bonds or unbonds
+pub fn sample_delegations(
+    transaction_conn: &mut PgConnection,
+    sample_size: i64,
+) -> anyhow::Result<Vec<DelegationSample>> {
+    sql_query(
+        "SELECT pairs.address, pairs.validator_id, validators.namada_address \
+         AS validator
+            FROM (
+                SELECT address, validator_id FROM bonds
+                UNION
+                SELECT address, validator_id FROM unbonds
+            ) AS pairs
+            JOIN validators ON validators.id = pairs.validator_id
+            ORDER BY random()
+            LIMIT $1",
+    )
+    .bind::<BigInt, _>(sample_size)
+    .load(transaction_conn)
+    .context("Failed to sample delegations from db")
+}
+
+/// Samples (delegator, validator) pairs that have either rewards, bonds or
+/// unbonds, so that missing rewards are sampled too
+pub fn sample_rewards(
+    transaction_conn: &mut PgConnection,
+    sample_size: i64,
+) -> anyhow::Result<Vec<DelegationSample>> {
+    sql_query(
+        "SELECT pairs.address, pairs.validator_id, validators.namada_address \
+         AS validator
+            FROM (
+                SELECT owner AS address, validator_id FROM pos_rewards
+                UNION
+                SELECT address, validator_id FROM bonds
+                UNION
+                SELECT address, validator_id FROM unbonds
+            ) AS pairs
+            JOIN validators ON validators.id = pairs.validator_id
+            ORDER BY random()
+            LIMIT $1",
+    )
+    .bind::<BigInt, _>(sample_size)
+    .load(transaction_conn)
+    .context("Failed to sample rewards from db")
+}
+
+pub fn sample_validators(
+    transaction_conn: &mut PgConnection,
+    sample_size: i64,
+) -> anyhow::Result<Vec<ValidatorDb>> {
+    validators::table
+        .order(random())
+        .limit(sample_size)
+        .select(ValidatorDb::as_select())
+        .load(transaction_conn)
+        .context("Failed to sample validators from db")
+}
+
+pub fn get_balance(
+    transaction_conn: &mut PgConnection,
+    owner: &str,
+    token: &str,
+) -> anyhow::Result<Option<BigDecimal>> {
+    balances::table
+        .filter(balances::owner.eq(owner).and(balances::token.eq(token)))
+        .select(balances::raw_amount)
+        .first(transaction_conn)
+        .optional()
+        .context("Failed to read balance from db")
+}
+
+pub fn get_delegation(
+    transaction_conn: &mut PgConnection,
+    address: &str,
+    validator_id: i32,
+) -> anyhow::Result<(Vec<BondInsertDb>, Vec<UnbondInsertDb>)> {
+    let bonds = bonds::table
+        .filter(
+            bonds::address
+                .eq(address)
+                .and(bonds::validator_id.eq(validator_id)),
+        )
+        .select(BondInsertDb::as_select())
+        .load(transaction_conn)
+        .context("Failed to read bonds from db")?;
+
+    let unbonds = unbonds::table
+        .filter(
+            unbonds::address
+                .eq(address)
+                .and(unbonds::validator_id.eq(validator_id)),
+        )
+        .select(UnbondInsertDb::as_select())
+        .load(transaction_conn)
+        .context("Failed to read unbonds from db")?;
+
+    anyhow::Ok((bonds, unbonds))
+}
+
+pub fn get_reward(
+    transaction_conn: &mut PgConnection,
+    owner: &str,
+    validator_id: i32,
+) -> anyhow::Result<Option<BigDecimal>> {
+    pos_rewards::table
+        .filter(
+            pos_rewards::owner
+                .eq(owner)
+                .and(pos_rewards::validator_id.eq(validator_id)),
+        )
+        .select(pos_rewards::raw_amount)
+        .first(transaction_conn)
+        .optional()
+        .context("Failed to read reward from db")
+}
+
+pub fn get_voting_power(
+    transaction_conn: &mut PgConnection,
+    validator_id: i32,
+) -> anyhow::Result<Option<i32>> {
+    validators::table
+        .filter(validators::id.eq(validator_id))
+        .select(validators::voting_power)
+        .first(transaction_conn)
+        .optional()
+        .context("Failed to read voting power from db")
+}
+
+pub fn insert_mismatches(
+    transaction_conn: &mut PgConnection,
+    mismatches: Vec<AuditMismatchInsertDb>,
+) -> anyhow::Result<()> {
+    diesel::insert_into(audit_mismatches::table)
+        .values::<&Vec<AuditMismatchInsertDb>>(&mismatches)
+        .execute(transaction_conn)
+        .context("Failed to insert audit mismatches in db")?;
+
+    anyhow::Ok(())
+}
+
+pub fn repair_balance(
+    transaction_conn: &mut PgConnection,
+    balance: BalancesInsertDb,
+) -> anyhow::Result<()> {
+    diesel::insert_into(balances::table)
+        .values::<&BalancesInsertDb>(&balance)
+        .on_conflict((balances::owner, balances::token))
+        .do_update()
+        .set(balances::raw_amount.eq(excluded(balances::raw_amount)))
+        .execute(transaction_conn)
+        .context("Failed to repair balance in db")?;
+
+    anyhow::Ok(())
+}
+
+/// Replaces every bond and unbond of the delegator to the validator
+pub fn repair_delegation(
+    transaction_conn: &mut PgConnection,
+    address: &str,
+    validator_id: i32,
+    bonds: Vec<BondInsertDb>,
+    unbonds: Vec<UnbondInsertDb>,
+) -> anyhow::Result<()> {
+    diesel::delete(bonds::table)
+        .filter(
+            bonds::address
+                .eq(address)
+                .and(bonds::validator_id.eq(validator_id)),
+        )
+        .execute(transaction_conn)
+        .context("Failed to remove bonds from db")?;
+
+    diesel::delete(unbonds::table)
+        .filter(
+            unbonds::address
+                .eq(address)
+                .and(unbonds::validator_id.eq(validator_id)),
+        )
+        .execute(transaction_conn)
+        .context("Failed to remove unbonds from db")?;
+
+    diesel::insert_into(bonds::table)
+        .values::<&Vec<BondInsertDb>>(&bonds)
+        .execute(transaction_conn)
+        .context("Failed to repair bonds in db")?;
+
+    diesel::insert_into(unbonds::table)
+        .values::<&Vec<UnbondInsertDb>>(&unbonds)
+        .execute(transaction_conn)
+        .context("Failed to repair unbonds in db")?;
+
+    anyhow::Ok(())
+}
+
+pub fn repair_reward(
+    transaction_conn: &mut PgConnection,
+    reward: PosRewardInsertDb,
+) -> anyhow::Result<()> {
+    diesel::insert_into(pos_rewards::table)
+        .values::<&PosRewardInsertDb>(&reward)
+        .on_conflict((pos_rewards::owner, pos_rewards::validator_id))
+        .do_update()
+        .set(pos_rewards::raw_amount.eq(excluded(pos_rewards::raw_amount)))
+        .execute(transaction_conn)
+        .context("Failed to repair reward in db")?;
+
+    anyhow::Ok(())
+}
+
+pub fn repair_voting_power(
+    transaction_conn: &mut PgConnection,
+    validator_id: i32,
+    voting_power: i32,
+) -> anyhow::Result<()> {
+    diesel::update(validators::table)
+        .filter(validators::id.eq(validator_id))
+        .set(validators::voting_power.eq(voting_power))
+        .execute(transaction_conn)
+        .context("Failed to repair voting power in db")?;
+
+    anyhow::Ok(())
+}
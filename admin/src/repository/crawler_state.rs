@@ -0,0 +1,55 @@
+use anyhow::Context;
+use diesel::{
+    ExpressionMethods, OptionalExtension, PgConnection, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+};
+use orm::crawler_state::{
+    CrawlerNameDb, CrawlerStateDb, TransactionsRangeStateDb,
+};
+use orm::schema::{crawler_state, transactions_crawler_ranges};
+
+pub fn get_crawler_states(
+    transaction_conn: &mut PgConnection,
+) -> anyhow::Result<Vec<CrawlerStateDb>> {
+    crawler_state::table
+        .select(CrawlerStateDb::as_select())
+        .load(transaction_conn)
+        .context("Failed to read crawlers state from db")
+}
+
+pub fn get_crawler_state(
+    transaction_conn: &mut PgConnection,
+    crawler: CrawlerNameDb,
+) -> anyhow::Result<Option<CrawlerStateDb>> {
+    crawler_state::table
+        .filter(crawler_state::name.eq(crawler))
+        .select(CrawlerStateDb::as_select())
+        .first(transaction_conn)
+        .optional()
+        .context("Failed to read crawler state from db")
+}
+
+/// Locks the crawler state row until the end of the transaction, so the
+/// crawler can't commit its next block or epoch meanwhile
+pub fn lock_crawler_state(
+    transaction_conn: &mut PgConnection,
+    crawler: CrawlerNameDb,
+) -> anyhow::Result<Option<CrawlerStateDb>> {
+    crawler_state::table
+        .filter(crawler_state::name.eq(crawler))
+        .select(CrawlerStateDb::as_select())
+        .for_update()
+        .first(transaction_conn)
+        .optional()
+        .context("Failed to lock crawler state in db")
+}
+
+pub fn get_transactions_ranges(
+    transaction_conn: &mut PgConnection,
+) -> anyhow::Result<Vec<TransactionsRangeStateDb>> {
+    transactions_crawler_ranges::table
+        .select(TransactionsRangeStateDb::as_select())
+        .order(transactions_crawler_ranges::from_block_height.asc())
+        .load(transaction_conn)
+        .context("Failed to read transactions ranges from db")
+}
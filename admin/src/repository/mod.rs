@@ -0,0 +1,3 @@
+pub mod audit;
+pub mod crawler_state;
+pub mod rewind;
@@ -0,0 +1,382 @@
+use anyhow::{bail, Context};
+use chrono::NaiveDateTime;
+use diesel::{
+    ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use orm::crawler_state::{
+    CrawlerNameDb, CrawlerStateDb, TransactionsRangeStateDb,
+};
+use orm::schema::{
+    balance_changes, blocks, pos_rewards_claims, transactions_crawler_ranges,
+    validator_blocks, wrapper_transactions,
+};
+use shared::block::{BlockHeight, Epoch};
+use shared::crawler_state::{BlockCrawlerState, ChainCrawlerState};
+
+use crate::repository::crawler_state;
+
+/// A crawler state updated more recently than this is taken as a running
+/// crawler, which would overwrite the rewound state with its next block
+pub const STOPPED_FOR_SECONDS: i64 = 60;
+
+/// Moves the chain crawler back to `block_height`, deleting the rows it
+/// derived from the blocks above. The crawler must be stopped.
+pub fn rewind_chain(
+    transaction_conn: &mut PgConnection,
+    block_height: BlockHeight,
+    now: NaiveDateTime,
+) -> anyhow::Result<()> {
+    let state =
+        lock_stopped_crawler(transaction_conn, CrawlerNameDb::Chain, now)?;
+
+    delete_chain_rows_after(transaction_conn, block_height as i32)?;
+
+    // The epoch is refreshed with the next block processed
+    chain::repository::crawler_state::upsert_crawler_state(
+        transaction_conn,
+        ChainCrawlerState {
+            last_processed_block: block_height,
+            last_processed_epoch: state.last_processed_epoch.unwrap_or_default()
+                as Epoch,
+            first_block_in_epoch: state.first_block_in_epoch.unwrap_or_default()
+                as BlockHeight,
+            timestamp: now.and_utc().timestamp(),
+        },
+    )
+}
+
+/// Moves the live transactions crawler and the range instances back to
+/// `block_height`, deleting the transactions of the blocks above. Every
+/// instance must be stopped.
+pub fn rewind_transactions(
+    transaction_conn: &mut PgConnection,
+    block_height: BlockHeight,
+    now: NaiveDateTime,
+) -> anyhow::Result<()> {
+    lock_stopped_crawler(transaction_conn, CrawlerNameDb::Transactions, now)?;
+
+    let ranges: Vec<TransactionsRangeStateDb> =
+        transactions_crawler_ranges::table
+            .select(TransactionsRangeStateDb::as_select())
+            .for_update()
+            .load(transaction_conn)
+            .context("Failed to lock transactions ranges in db")?;
+    if let Some(range) = ranges.iter().find(|range| {
+        (now - range.timestamp).num_seconds() < STOPPED_FOR_SECONDS
+    }) {
+        bail!(
+            "The transactions crawler of blocks {} to {} is still running, \
+             stop it before rewinding",
+            range.from_block_height,
+            range.to_block_height
+        );
+    }
+
+    delete_transactions_rows_between(
+        transaction_conn,
+        block_height as i32 + 1,
+        i32::MAX,
+    )?;
+    rewind_transactions_ranges(transaction_conn, block_height as i32)?;
+
+    transactions::repository::transactions::insert_crawler_state(
+        transaction_conn,
+        BlockCrawlerState {
+            last_processed_block: block_height,
+            timestamp: now.and_utc().timestamp(),
+        },
+    )
+}
+
+/// Locks the crawler state until the end of the transaction, refusing if the
+/// crawler still updates it
+fn lock_stopped_crawler(
+    transaction_conn: &mut PgConnection,
+    crawler: CrawlerNameDb,
+    now: NaiveDateTime,
+) -> anyhow::Result<CrawlerStateDb> {
+    let name = crawler.to_string();
+    let state = crawler_state::lock_crawler_state(transaction_conn, crawler)?
+        .with_context(|| {
+        format!("The {} crawler has no state to rewind", name)
+    })?;
+
+    let idle_for = (now - state.timestamp).num_seconds();
+    if idle_for < STOPPED_FOR_SECONDS {
+        bail!(
+            "The {} crawler updated its state {}s ago, stop it before \
+             rewinding",
+            name,
+            idle_for
+        );
+    }
+
+    anyhow::Ok(state)
+}
+
+/// Deletes the rows the chain crawler derived from the blocks above
+/// `block_height`. Balances, bonds and proposals only hold the latest state
+/// and are refreshed as the crawler catches up again.
+pub fn delete_chain_rows_after(
+    transaction_conn: &mut PgConnection,
+    block_height: i32,
+) -> anyhow::Result<()> {
+    diesel::delete(balance_changes::table)
+        .filter(balance_changes::height.gt(block_height))
+        .execute(transaction_conn)
+        .context("Failed to delete balance changes from db")?;
+
+    diesel::delete(pos_rewards_claims::table)
+        .filter(pos_rewards_claims::block_height.gt(block_height))
+        .execute(transaction_conn)
+        .context("Failed to delete reward claims from db")?;
+
+    diesel::delete(validator_blocks::table)
+        .filter(validator_blocks::height.gt(block_height))
+        .execute(transaction_conn)
+        .context("Failed to delete validator blocks from db")?;
+
+    anyhow::Ok(())
+}
+
+/// Deletes the transactions of the blocks in `from..=to`, inner transactions
+/// go along with their wrapper
+pub fn delete_transactions_rows_between(
+    transaction_conn: &mut PgConnection,
+    from: i32,
+    to: i32,
+) -> anyhow::Result<()> {
+    diesel::delete(wrapper_transactions::table)
+        .filter(wrapper_transactions::block_height.between(from, to))
+        .execute(transaction_conn)
+        .context("Failed to delete wrapper transactions from db")?;
+
+    diesel::delete(blocks::table)
+        .filter(blocks::height.between(from, to))
+        .execute(transaction_conn)
+        .context("Failed to delete blocks from db")?;
+
+    anyhow::Ok(())
+}
+
+/// Ranges starting after `block_height` are indexed again from their start,
+/// the others resume after `block_height`
+pub fn rewind_transactions_ranges(
+    transaction_conn: &mut PgConnection,
+    block_height: i32,
+) -> anyhow::Result<()> {
+    diesel::delete(transactions_crawler_ranges::table)
+        .filter(transactions_crawler_ranges::from_block_height.gt(block_height))
+        .execute(transaction_conn)
+        .context("Failed to delete transactions ranges from db")?;
+
+    diesel::update(transactions_crawler_ranges::table)
+        .filter(
+            transactions_crawler_ranges::last_processed_block.gt(block_height),
+        )
+        .set(transactions_crawler_ranges::last_processed_block.eq(block_height))
+        .execute(transaction_conn)
+        .context("Failed to update transactions ranges in db")?;
+
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use bigdecimal::BigDecimal;
+    use chrono::{DateTime, Duration};
+    use orm::balances::BalanceChangesInsertDb;
+    use orm::transactions::{TransactionResultDb, WrapperTransactionInsertDb};
+    use shared::id::Id;
+    use shared::token::Token;
+    use test_helpers::db::TestDb;
+    use transactions::repository::transactions as transactions_repository;
+
+    use super::*;
+
+    const STOPPED_AT: i64 = 1_700_000_000;
+
+    fn stopped_at() -> NaiveDateTime {
+        DateTime::from_timestamp(STOPPED_AT, 0).unwrap().naive_utc()
+    }
+
+    /// Test that rewinding the transactions crawler keeps the blocks up to
+    /// the target height and moves the ranges back.
+    #[tokio::test]
+    async fn test_rewind_transactions() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            seed_transactions(conn, 1..=10)?;
+            transactions_repository::insert_crawler_state(
+                conn,
+                BlockCrawlerState {
+                    last_processed_block: 10,
+                    timestamp: STOPPED_AT,
+                },
+            )?;
+            for (from, to, last_processed_block) in
+                [(2, 4, 4), (4, 8, 6), (9, 10, 10)]
+            {
+                transactions_repository::upsert_range_state(
+                    conn,
+                    TransactionsRangeStateDb {
+                        from_block_height: from,
+                        to_block_height: to,
+                        last_processed_block,
+                        timestamp: stopped_at(),
+                    },
+                )?;
+            }
+
+            rewind_transactions(conn, 5, stopped_at() + Duration::hours(1))?;
+
+            let heights: Vec<i32> = wrapper_transactions::table
+                .select(wrapper_transactions::block_height)
+                .order(wrapper_transactions::block_height.asc())
+                .load(conn)?;
+            assert_eq!(heights, (1..=5).collect::<Vec<_>>());
+
+            let block_heights: Vec<i32> = blocks::table
+                .select(blocks::height)
+                .order(blocks::height.asc())
+                .load(conn)?;
+            assert_eq!(block_heights, (1..=5).collect::<Vec<_>>());
+
+            let ranges = crawler_state::get_transactions_ranges(conn)?
+                .into_iter()
+                .map(|range| {
+                    (
+                        range.from_block_height,
+                        range.to_block_height,
+                        range.last_processed_block,
+                    )
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(ranges, vec![(2, 4, 4), (4, 8, 5)]);
+
+            let state = crawler_state::get_crawler_state(
+                conn,
+                CrawlerNameDb::Transactions,
+            )?
+            .expect("Missing transactions crawler state");
+            assert_eq!(state.last_processed_block, Some(5));
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that a crawler which updated its state recently is not rewound.
+    #[tokio::test]
+    async fn test_rewind_transactions_with_running_crawler() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            seed_transactions(conn, 1..=10)?;
+            transactions_repository::insert_crawler_state(
+                conn,
+                BlockCrawlerState {
+                    last_processed_block: 10,
+                    timestamp: STOPPED_AT,
+                },
+            )?;
+
+            let result = rewind_transactions(
+                conn,
+                5,
+                stopped_at() + Duration::seconds(5),
+            );
+            assert!(result.is_err());
+
+            let count: i64 =
+                wrapper_transactions::table.count().get_result(conn)?;
+            assert_eq!(count, 10);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that rewinding the chain crawler keeps the balance changes up to
+    /// the target height.
+    #[tokio::test]
+    async fn test_rewind_chain() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            chain::repository::crawler_state::upsert_crawler_state(
+                conn,
+                ChainCrawlerState {
+                    last_processed_block: 10,
+                    last_processed_epoch: 2,
+                    first_block_in_epoch: 8,
+                    timestamp: STOPPED_AT,
+                },
+            )?;
+            let token = Token::Native(Id::Account("tnam1token".to_string()));
+            chain::repository::balance::insert_tokens(conn, vec![token])?;
+            diesel::insert_into(balance_changes::table)
+                .values(
+                    (1..=10)
+                        .map(|height| BalanceChangesInsertDb {
+                            owner: "tnam1owner".to_string(),
+                            token: "tnam1token".to_string(),
+                            raw_amount: BigDecimal::from(height),
+                            height,
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)
+                .context("Failed to insert balance changes")?;
+
+            rewind_chain(conn, 5, stopped_at() + Duration::hours(1))?;
+
+            let heights: Vec<i32> = balance_changes::table
+                .select(balance_changes::height)
+                .order(balance_changes::height.asc())
+                .load(conn)?;
+            assert_eq!(heights, (1..=5).collect::<Vec<_>>());
+
+            let state =
+                crawler_state::get_crawler_state(conn, CrawlerNameDb::Chain)?
+                    .expect("Missing chain crawler state");
+            assert_eq!(state.last_processed_block, Some(5));
+            assert_eq!(state.last_processed_epoch, Some(2));
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    fn seed_transactions(
+        conn: &mut PgConnection,
+        heights: std::ops::RangeInclusive<i32>,
+    ) -> anyhow::Result<()> {
+        for height in heights {
+            transactions_repository::insert_block(
+                conn,
+                height as BlockHeight,
+                STOPPED_AT,
+            )?;
+            diesel::insert_into(wrapper_transactions::table)
+                .values(WrapperTransactionInsertDb {
+                    id: format!("wrapper{}", height),
+                    fee_payer: "tnam1payer".to_string(),
+                    fee_token: "tnam1token".to_string(),
+                    gas_limit: "10000".to_string(),
+                    block_height: height,
+                    exit_code: TransactionResultDb::Applied,
+                    atomic: true,
+                })
+                .execute(conn)
+                .context("Failed to insert wrapper transaction")?;
+        }
+
+        anyhow::Ok(())
+    }
+}
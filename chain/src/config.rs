@@ -26,6 +26,11 @@ pub struct AppConfig {
     #[clap(long, env)]
     pub initial_query_retry_time: u64,
 
+    /// Number of most recent blocks whose validator signatures are kept,
+    /// should cover the webserver largest uptime window
+    #[clap(long, env, default_value_t = 10000)]
+    pub validator_blocks_retention: u32,
+
     #[command(flatten)]
     pub verbosity: Verbosity<InfoLevel>,
 }
@@ -0,0 +1,458 @@
+use std::collections::HashSet;
+use std::convert::identity;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Context;
+use chrono::{NaiveDateTime, Utc};
+use deadpool_diesel::postgres::{Object, Pool};
+use namada_sdk::time::DateTimeUtc;
+use orm::notifications::{self, IndexerEvent};
+use shared::block::{Block, BlockHeight};
+use shared::block_result::BlockResult;
+use shared::checksums::Checksums;
+use shared::crawler::crawl;
+use shared::crawler_state::ChainCrawlerState;
+use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
+use shared::id::Id;
+use shared::token::Token;
+use tendermint_rpc::HttpClient;
+
+use crate::services::namada::query_bonds;
+use crate::services::{
+    db as db_service, namada as namada_service,
+    tendermint as tendermint_service,
+};
+use crate::{repository, snapshot};
+
+/// Crawls blocks from the last one processed, the database behind `pool` must
+/// be migrated
+pub async fn run(
+    client: Arc<HttpClient>,
+    pool: Pool,
+    initial_query_retry_time: u64,
+    validator_blocks_retention: u32,
+) -> Result<(), MainError> {
+    let mut checksums = Checksums::default();
+    for code_path in Checksums::code_paths() {
+        let code = namada_service::query_tx_code_hash(&client, &code_path)
+            .await
+            .unwrap_or_else(|| {
+                panic!("{} must be defined in namada storage.", code_path)
+            });
+        checksums.add(code_path, code.to_lowercase());
+    }
+
+    let conn = get_db_connection(&pool).await?;
+
+    snapshot::take(&client, &conn, initial_query_retry_time).await?;
+
+    let crawler_state = db_service::get_chain_crawler_state(&conn)
+        .await
+        .into_db_error()?;
+
+    backfill_tokens_metadata(
+        &client,
+        &conn,
+        crawler_state.last_processed_block,
+    )
+    .await?;
+
+    // Tokens whose metadata is indexed, so it's only queried once per token
+    let known_tokens = Arc::new(Mutex::new(
+        db_service::get_tokens_with_metadata(&conn)
+            .await
+            .into_db_error()?,
+    ));
+    drop(conn);
+
+    crawl(
+        move |block_height| {
+            crawling_fn(
+                block_height,
+                client.clone(),
+                pool.clone(),
+                checksums.clone(),
+                known_tokens.clone(),
+                validator_blocks_retention,
+            )
+        },
+        crawler_state.last_processed_block,
+        Some(1000),
+    )
+    .await
+}
+
+async fn crawling_fn(
+    block_height: u32,
+    client: Arc<HttpClient>,
+    pool: Pool,
+    checksums: Checksums,
+    known_tokens: Arc<Mutex<HashSet<Id>>>,
+    validator_blocks_retention: u32,
+) -> Result<(), MainError> {
+    let conn = get_db_connection(&pool).await?;
+
+    let should_process = can_process(block_height, client.clone()).await?;
+
+    if !should_process {
+        let timestamp = Utc::now().naive_utc();
+        update_crawler_timestamp(&conn, timestamp).await?;
+
+        tracing::warn!("Block {} was not processed, retry...", block_height);
+
+        return Err(MainError::NoAction);
+    }
+
+    tracing::info!("Query block...");
+    let tm_block_response =
+        tendermint_service::query_raw_block_at_height(&client, block_height)
+            .await
+            .into_rpc_error()?;
+    tracing::info!(
+        "Raw block contains {} txs...",
+        tm_block_response.block.data.len()
+    );
+
+    tracing::info!("Query block results...");
+    let tm_block_results_response =
+        tendermint_service::query_raw_block_results_at_height(
+            &client,
+            block_height,
+        )
+        .await
+        .into_rpc_error()?;
+    let block_results = BlockResult::from(tm_block_results_response);
+
+    tracing::info!("Query epoch...");
+    let epoch =
+        namada_service::get_epoch_at_block_height(&client, block_height)
+            .await
+            .into_rpc_error()?;
+
+    tracing::info!("Query first block in epoch...");
+    let first_block_in_epoch =
+        namada_service::get_first_block_in_epoch(&client)
+            .await
+            .into_rpc_error()?;
+
+    let block_timestamp = tm_block_response.block.header.time.unix_timestamp();
+    let block = Block::from(
+        tm_block_response,
+        &block_results,
+        checksums,
+        epoch,
+        block_height,
+    );
+    tracing::info!("Deserialized {} txs...", block.transactions.len());
+
+    tracing::info!("Query last commit validator set...");
+    let commit_validator_set = match &block.last_commit {
+        Some(last_commit) => tendermint_service::query_validator_set_at_height(
+            &client,
+            last_commit.height,
+        )
+        .await
+        .into_rpc_error()?,
+        None => vec![],
+    };
+    let validator_blocks = block.validator_blocks(&commit_validator_set);
+
+    let native_token = namada_service::get_native_token(&client)
+        .await
+        .into_rpc_error()?;
+
+    let ibc_tokens = block
+        .ibc_tokens()
+        .into_iter()
+        .map(Token::Ibc)
+        .collect::<Vec<_>>();
+    let new_tokens = {
+        let known_tokens = known_tokens.lock().unwrap();
+        ibc_tokens
+            .iter()
+            .filter(|token| !known_tokens.contains(&token.address()))
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+    let tokens_metadata = namada_service::query_tokens_metadata(
+        &client,
+        &new_tokens,
+        block_height,
+    )
+    .await
+    .into_rpc_error()?;
+    let indexed_tokens = tokens_metadata
+        .iter()
+        .map(|metadata| metadata.address.clone())
+        .collect::<Vec<_>>();
+
+    let addresses = block.addresses_with_balance_change(native_token);
+    let balances = namada_service::query_balance(&client, &addresses)
+        .await
+        .into_rpc_error()?;
+    tracing::info!("Updating balance for {} addresses...", addresses.len());
+
+    let next_governance_proposal_id =
+        namada_service::query_next_governance_id(&client, block_height)
+            .await
+            .into_rpc_error()?;
+
+    let proposals = block.governance_proposal(next_governance_proposal_id);
+    tracing::info!("Creating {} governance proposals...", proposals.len());
+
+    let proposals_with_tally =
+        namada_service::query_tallies(&client, proposals)
+            .await
+            .into_rpc_error()?;
+
+    let proposals_votes = block.governance_votes();
+    tracing::info!("Creating {} governance votes...", proposals_votes.len());
+
+    let addresses = block.bond_addresses();
+    let bonds = query_bonds(&client, addresses).await.into_rpc_error()?;
+    tracing::info!("Updating bonds for {} addresses", bonds.len());
+
+    let bonds_updates = bonds
+        .iter()
+        .cloned()
+        .filter_map(|(_, _, bond)| bond)
+        .collect::<Vec<_>>();
+
+    let removed_bonds_addresses = bonds
+        .iter()
+        .cloned()
+        .map(|(source, validator, _)| (source, validator))
+        .collect::<Vec<(Id, Id)>>();
+
+    let addresses = block.unbond_addresses();
+    let unbonds = namada_service::query_unbonds(&client, addresses)
+        .await
+        .into_rpc_error()?;
+    tracing::info!("Updating unbonds for {} addresses", unbonds.len());
+
+    let withdraw_addreses = block.withdraw_addresses();
+
+    let revealed_pks = block.revealed_pks();
+    tracing::info!(
+        "Updating revealed pks for {} addresses",
+        revealed_pks.len()
+    );
+
+    let metadata_change = block.validator_metadata();
+
+    let reward_claims = block.pos_rewards();
+    let claimed_rewards = namada_service::query_claimed_rewards(
+        &client,
+        &reward_claims,
+        block_height,
+    )
+    .await
+    .into_rpc_error()?;
+    tracing::info!("Recording {} reward claims", claimed_rewards.len());
+
+    let redelegations = block.redelegations();
+    tracing::info!("Indexing {} redelegations", redelegations.len());
+
+    let timestamp_in_sec = DateTimeUtc::now().0.timestamp();
+
+    let crawler_state = ChainCrawlerState {
+        last_processed_block: block_height,
+        last_processed_epoch: epoch,
+        first_block_in_epoch,
+        timestamp: timestamp_in_sec,
+    };
+
+    conn.interact(move |conn| {
+        conn.build_transaction()
+            .read_write()
+            .run(|transaction_conn| {
+                repository::block::insert_block(
+                    transaction_conn,
+                    block_height,
+                    block_timestamp,
+                )?;
+
+                repository::balance::insert_tokens(
+                    transaction_conn,
+                    ibc_tokens,
+                )?;
+                repository::balance::insert_tokens_metadata(
+                    transaction_conn,
+                    tokens_metadata,
+                )?;
+
+                repository::balance::insert_balance(
+                    transaction_conn,
+                    balances.clone(),
+                )?;
+
+                repository::balance::insert_balance_changes(
+                    transaction_conn,
+                    balances,
+                    block_height,
+                )?;
+
+                repository::gov::insert_proposals(
+                    transaction_conn,
+                    proposals_with_tally,
+                )?;
+                repository::gov::insert_votes(
+                    transaction_conn,
+                    proposals_votes,
+                )?;
+
+                // We first remove all the bonds and then insert the new ones
+                repository::pos::clear_bonds(
+                    transaction_conn,
+                    removed_bonds_addresses,
+                )?;
+                repository::pos::insert_bonds(transaction_conn, bonds_updates)?;
+
+                repository::pos::insert_unbonds(transaction_conn, unbonds)?;
+                repository::pos::remove_withdraws(
+                    transaction_conn,
+                    epoch,
+                    withdraw_addreses,
+                )?;
+
+                repository::pos::insert_redelegations(
+                    transaction_conn,
+                    redelegations,
+                )?;
+
+                repository::pos::insert_claimed_rewards(
+                    transaction_conn,
+                    claimed_rewards,
+                    epoch,
+                    block_height,
+                )?;
+                repository::pos::delete_claimed_rewards(
+                    transaction_conn,
+                    reward_claims,
+                )?;
+
+                repository::pos::update_validator_metadata(
+                    transaction_conn,
+                    metadata_change,
+                )?;
+
+                repository::pos::insert_validator_blocks(
+                    transaction_conn,
+                    validator_blocks,
+                )?;
+                repository::pos::prune_validator_blocks(
+                    transaction_conn,
+                    block_height.saturating_sub(validator_blocks_retention),
+                )?;
+
+                repository::revealed_pk::insert_revealed_pks(
+                    transaction_conn,
+                    revealed_pks,
+                )?;
+
+                repository::crawler_state::upsert_crawler_state(
+                    transaction_conn,
+                    crawler_state,
+                )?;
+
+                notifications::notify(
+                    transaction_conn,
+                    vec![IndexerEvent::Block {
+                        height: block_height as i32,
+                        epoch: epoch as i32,
+                    }],
+                );
+
+                anyhow::Ok(())
+            })
+    })
+    .await
+    .context_db_interact_error()
+    .into_db_error()?
+    .context("Commit block db transaction error")
+    .into_db_error()?;
+
+    known_tokens.lock().unwrap().extend(indexed_tokens);
+
+    Ok(())
+}
+
+/// Indexes the metadata of the tokens indexed without it, e.g. by a version
+/// of the crawler that predates it. Their first seen height is not known, so
+/// the last processed block is used as an upper bound.
+async fn backfill_tokens_metadata(
+    client: &HttpClient,
+    conn: &Object,
+    last_processed_block: BlockHeight,
+) -> Result<(), MainError> {
+    let tokens = db_service::get_tokens_without_metadata(conn)
+        .await
+        .into_db_error()?;
+
+    if tokens.is_empty() {
+        return Ok(());
+    }
+
+    tracing::info!("Backfilling metadata of {} tokens...", tokens.len());
+
+    let tokens_metadata = namada_service::query_tokens_metadata(
+        client,
+        &tokens,
+        last_processed_block,
+    )
+    .await
+    .into_rpc_error()?;
+
+    conn.interact(move |conn| {
+        repository::balance::insert_tokens_metadata(conn, tokens_metadata)
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()
+}
+
+async fn can_process(
+    block_height: u32,
+    client: Arc<HttpClient>,
+) -> Result<bool, MainError> {
+    tracing::info!("Attempting to process block: {}...", block_height);
+
+    let last_block_height = namada_service::query_last_block_height(&client)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to query Namada's last committed block: {}",
+                e
+            );
+            MainError::RpcError
+        })?;
+
+    Ok(last_block_height >= block_height)
+}
+
+async fn update_crawler_timestamp(
+    conn: &Object,
+    timestamp: NaiveDateTime,
+) -> Result<(), MainError> {
+    conn.interact(move |transaction_conn| {
+        repository::crawler_state::update_crawler_timestamp(
+            transaction_conn,
+            timestamp,
+        )?;
+
+        anyhow::Ok(())
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()
+}
+
+/// Connections are only held for the block being processed
+async fn get_db_connection(pool: &Pool) -> Result<Object, MainError> {
+    pool.get()
+        .await
+        .context("Failed to get db connection handle from deadpool")
+        .into_db_error()
+}
@@ -1,4 +1,6 @@
 pub mod app_state;
 pub mod config;
+pub mod crawler;
 pub mod repository;
 pub mod services;
+pub mod snapshot;
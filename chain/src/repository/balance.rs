@@ -2,13 +2,17 @@ use anyhow::Context;
 use diesel::sql_types::BigInt;
 use diesel::upsert::excluded;
 use diesel::{
-    sql_query, ExpressionMethods, PgConnection, QueryableByName, RunQueryDsl,
+    sql_query, ExpressionMethods, PgConnection, QueryDsl, QueryableByName,
+    RunQueryDsl,
 };
-use orm::balances::BalancesInsertDb;
-use orm::schema::{balances, ibc_token, token};
-use orm::token::{IbcTokenInsertDb, TokenInsertDb};
+use orm::balances::{BalanceChangesInsertDb, BalancesInsertDb};
+use orm::schema::{
+    balance_changes, balances, ibc_token, token, token_metadata,
+};
+use orm::token::{IbcTokenInsertDb, TokenInsertDb, TokenMetadataInsertDb};
 use shared::balance::Balances;
-use shared::token::Token;
+use shared::block::BlockHeight;
+use shared::token::{Token, TokenMetadata};
 pub const MAX_PARAM_SIZE: u16 = u16::MAX;
 
 #[derive(QueryableByName)]
@@ -40,6 +44,36 @@ pub fn insert_balance(
     anyhow::Ok(())
 }
 
+pub fn insert_balance_changes(
+    transaction_conn: &mut PgConnection,
+    balances: Balances,
+    block_height: BlockHeight,
+) -> anyhow::Result<()> {
+    diesel::insert_into(balance_changes::table)
+        .values::<&Vec<BalanceChangesInsertDb>>(
+            &balances
+                .into_iter()
+                .map(|balance| {
+                    BalanceChangesInsertDb::from_balance(balance, block_height)
+                })
+                .collect::<Vec<_>>(),
+        )
+        .on_conflict((
+            balance_changes::columns::owner,
+            balance_changes::columns::token,
+            balance_changes::columns::height,
+        ))
+        .do_update()
+        .set(
+            balance_changes::columns::raw_amount
+                .eq(excluded(balance_changes::columns::raw_amount)),
+        )
+        .execute(transaction_conn)
+        .context("Failed to insert balance changes in db")?;
+
+    anyhow::Ok(())
+}
+
 pub fn insert_balance_in_chunks(
     transaction_conn: &mut PgConnection,
     balances: Balances,
@@ -64,6 +98,38 @@ pub fn insert_balance_in_chunks(
     anyhow::Ok(())
 }
 
+/// Removes every balance of `token`, before its balances are copied again
+pub fn delete_token_balances(
+    transaction_conn: &mut PgConnection,
+    token: &Token,
+) -> anyhow::Result<()> {
+    diesel::delete(balances::table)
+        .filter(balances::columns::token.eq(token.to_string()))
+        .execute(transaction_conn)
+        .context("Failed to remove token balances from db")?;
+
+    anyhow::Ok(())
+}
+
+/// Copies a page of balances using `COPY`, which has no bind parameter
+/// limit. Their owners must not hold a balance of the token yet.
+pub fn copy_token_balances(
+    transaction_conn: &mut PgConnection,
+    balances: Balances,
+) -> anyhow::Result<()> {
+    diesel::copy_from(balances::table)
+        .from_insertable(
+            &balances
+                .into_iter()
+                .map(BalancesInsertDb::from_balance)
+                .collect::<Vec<_>>(),
+        )
+        .execute(transaction_conn)
+        .context("Failed to copy token balances in db")?;
+
+    anyhow::Ok(())
+}
+
 pub fn insert_tokens(
     transaction_conn: &mut PgConnection,
     tokens: Vec<Token>,
@@ -90,6 +156,25 @@ pub fn insert_tokens(
     anyhow::Ok(())
 }
 
+/// Tokens metadata is written once, so that the first seen height is kept
+pub fn insert_tokens_metadata(
+    transaction_conn: &mut PgConnection,
+    tokens_metadata: Vec<TokenMetadata>,
+) -> anyhow::Result<()> {
+    diesel::insert_into(token_metadata::table)
+        .values(
+            tokens_metadata
+                .into_iter()
+                .map(TokenMetadataInsertDb::from)
+                .collect::<Vec<_>>(),
+        )
+        .on_conflict_do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert tokens metadata in db")?;
+
+    anyhow::Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -97,9 +182,11 @@ mod tests {
     use diesel::{BoolExpressionMethods, QueryDsl, SelectableHelper};
     use namada_sdk::token::Amount as NamadaAmount;
     use namada_sdk::uint::MAX_SIGNED_VALUE;
-    use orm::balances::BalanceDb;
+    use orm::balances::{BalanceChangeDb, BalanceDb};
+    use orm::token::TokenMetadataDb;
     use shared::balance::{Amount, Balance};
     use shared::id::Id;
+    use shared::token::{IbcToken, IbcTokenTrace};
     use test_helpers::db::TestDb;
 
     use super::*;
@@ -426,6 +513,169 @@ mod tests {
         .expect("Failed to run test");
     }
 
+    /// Test that the metadata of a token seen again keeps its first seen
+    /// height.
+    #[tokio::test]
+    async fn test_insert_tokens_metadata_keeps_first_seen_height() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let address = Id::Account(
+                "tnam1pkg30gnt4q0zn7j00r6hms4ajrxn6f5ysyyl7w9m".to_string(),
+            );
+            let trace = "transfer/channel-2/uatom";
+            let token = Token::Ibc(IbcToken {
+                address: address.clone(),
+                trace: Id::IbcTrace(trace.to_string()),
+            });
+
+            let metadata = |first_seen_height| TokenMetadata {
+                address: address.clone(),
+                denomination: 0,
+                symbol: Some("uatom".to_string()),
+                ibc_trace: IbcTokenTrace::parse(trace),
+                origin_chain: None,
+                first_seen_height,
+            };
+
+            insert_tokens(conn, vec![token.clone()])?;
+            insert_tokens_metadata(conn, vec![metadata(10)])?;
+
+            insert_tokens(conn, vec![token])?;
+            insert_tokens_metadata(conn, vec![metadata(20)])?;
+
+            let queried_metadata = token_metadata::table
+                .select(TokenMetadataDb::as_select())
+                .get_results(conn)?;
+
+            assert_eq!(queried_metadata.len(), 1);
+            assert_eq!(queried_metadata[0].first_seen_height, 10);
+            assert_eq!(
+                queried_metadata[0].ibc_port.as_deref(),
+                Some("transfer")
+            );
+            assert_eq!(
+                queried_metadata[0].ibc_channel.as_deref(),
+                Some("channel-2")
+            );
+            assert_eq!(
+                queried_metadata[0].ibc_base_denom.as_deref(),
+                Some("uatom")
+            );
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that balance changes keep one row per height and update the row
+    /// of a height seen again.
+    #[tokio::test]
+    async fn test_insert_balance_changes_keeps_history() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let owner = Id::Account(
+                "tnam1qqshvryx9pngpk7mmzpzkjkm6klelgusuvmkc0uz".to_string(),
+            );
+            let token = Token::Native(Id::Account(
+                "tnam1q87wtaqqtlwkw927gaff34hgda36huk0kgry692a".to_string(),
+            ));
+            let balance = |amount| Balance {
+                owner: owner.clone(),
+                token: token.clone(),
+                amount: Amount::from(NamadaAmount::from_u64(amount)),
+            };
+
+            insert_tokens(conn, vec![token.clone()])?;
+
+            insert_balance_changes(conn, vec![balance(100)], 10)?;
+            insert_balance_changes(conn, vec![balance(200)], 20)?;
+            insert_balance_changes(conn, vec![balance(300)], 20)?;
+
+            let queried_changes = balance_changes::table
+                .order(balance_changes::height.asc())
+                .select(BalanceChangeDb::as_select())
+                .get_results(conn)?;
+
+            assert_eq!(queried_changes.len(), 2);
+            assert_eq!(queried_changes[0].height, 10);
+            assert_eq!(
+                Amount::from(queried_changes[0].raw_amount.clone()),
+                balance(100).amount
+            );
+            assert_eq!(queried_changes[1].height, 20);
+            assert_eq!(
+                Amount::from(queried_changes[1].raw_amount.clone()),
+                balance(300).amount
+            );
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that copy_token_balances copies the pages of a token after its
+    /// balances are deleted, leaving the other tokens untouched.
+    #[tokio::test]
+    async fn test_copy_token_balances_in_pages() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let token = Token::Native(Id::Account(
+                "tnam1q87wtaqqtlwkw927gaff34hgda36huk0kgry692a".to_string(),
+            ));
+            let other_token = Token::Ibc(IbcToken {
+                address: Id::Account(
+                    "tnam1p5z8ruwyu7ha8urhq2l0dhpk2f5dv3ts7uyf6wgp".to_string(),
+                ),
+                trace: Id::IbcTrace("transfer/channel-0/uosmo".to_string()),
+            });
+            let stale_balance = Balance::fake_with_token(token.clone());
+            let other_balance = Balance::fake_with_token(other_token.clone());
+            seed_balance(conn, vec![stale_balance, other_balance.clone()])?;
+
+            let balances = (0..5)
+                .map(|_| Balance::fake_with_token(token.clone()))
+                .collect::<Vec<_>>();
+
+            delete_token_balances(conn, &token)?;
+            for page in balances.chunks(2) {
+                copy_token_balances(conn, page.to_vec())?;
+            }
+
+            let queried_balances = query_all_balances(conn)?;
+            assert_eq!(queried_balances.len(), balances.len() + 1);
+
+            for balance in balances {
+                let queried_balance = query_balance_by_address(
+                    conn,
+                    balance.owner,
+                    token.clone(),
+                )?;
+                assert_eq!(
+                    Amount::from(queried_balance.raw_amount),
+                    balance.amount
+                );
+            }
+            let queried_balance = query_balance_by_address(
+                conn,
+                other_balance.owner,
+                other_token,
+            )?;
+            assert_eq!(
+                Amount::from(queried_balance.raw_amount),
+                other_balance.amount
+            );
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
     fn seed_tokens_from_balance(
         conn: &mut PgConnection,
         balance: Vec<Balance>,
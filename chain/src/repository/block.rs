@@ -0,0 +1,25 @@
+use anyhow::Context;
+use diesel::{PgConnection, RunQueryDsl};
+use orm::blocks::BlockInsertDb;
+use orm::schema::blocks;
+use shared::block::BlockHeight;
+
+/// Records the block time, so that the rows written by this crawler can be
+/// dated even when the transactions crawler lags behind
+pub fn insert_block(
+    transaction_conn: &mut PgConnection,
+    block_height: BlockHeight,
+    timestamp: i64,
+) -> anyhow::Result<()> {
+    diesel::insert_into(blocks::table)
+        .values::<&BlockInsertDb>(&BlockInsertDb::from(
+            block_height,
+            timestamp,
+        )?)
+        // Both crawlers read the time from the block header
+        .on_conflict_do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert block in db")?;
+
+    anyhow::Ok(())
+}
@@ -1,5 +1,7 @@
 pub mod balance;
+pub mod block;
 pub mod crawler_state;
 pub mod gov;
 pub mod pos;
 pub mod revealed_pk;
+pub mod snapshot;
@@ -1,20 +1,29 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Context;
 use diesel::upsert::excluded;
 use diesel::{
-    BoolExpressionMethods, ExpressionMethods, PgConnection, QueryDsl,
-    RunQueryDsl, SelectableHelper,
+    BoolExpressionMethods, ExpressionMethods, NullableExpressionMethods,
+    PgConnection, QueryDsl, RunQueryDsl, SelectableHelper,
 };
 use orm::bond::BondInsertDb;
-use orm::schema::{bonds, pos_rewards, unbonds, validators};
+use orm::pos_rewards::PosRewardClaimInsertDb;
+use orm::redelegations::RedelegationInsertDb;
+use orm::schema::{
+    bonds, pos_rewards, pos_rewards_claims, redelegations, unbonds,
+    validator_blocks, validator_consensus_addresses, validators,
+};
 use orm::unbond::UnbondInsertDb;
+use orm::validator_blocks::ValidatorBlockInsertDb;
 use orm::validators::{ValidatorDb, ValidatorUpdateMetadataDb};
-use shared::block::Epoch;
+use shared::block::{BlockHeight, Epoch};
 use shared::bond::Bonds;
 use shared::id::Id;
+use shared::redelegation::Redelegations;
+use shared::rewards::Reward;
 use shared::unbond::{UnbondAddresses, Unbonds};
-use shared::validator::ValidatorMetadataChange;
+use shared::utils::DelegationPair;
+use shared::validator::{ValidatorBlock, ValidatorMetadataChange};
 
 pub fn clear_bonds(
     transaction_conn: &mut PgConnection,
@@ -48,6 +57,47 @@ pub fn clear_bonds(
     anyhow::Ok(())
 }
 
+/// Replaces every bond and unbond of the validator using `COPY`, `bonds` and
+/// `unbonds` must all target it
+pub fn copy_validator_bonds(
+    transaction_conn: &mut PgConnection,
+    validator_id: i32,
+    bonds: Bonds,
+    unbonds: Unbonds,
+) -> anyhow::Result<()> {
+    diesel::delete(bonds::table)
+        .filter(bonds::columns::validator_id.eq(validator_id))
+        .execute(transaction_conn)
+        .context("Failed to remove validator bonds from db")?;
+
+    diesel::delete(unbonds::table)
+        .filter(unbonds::columns::validator_id.eq(validator_id))
+        .execute(transaction_conn)
+        .context("Failed to remove validator unbonds from db")?;
+
+    diesel::copy_from(bonds::table)
+        .from_insertable(
+            &bonds
+                .into_iter()
+                .map(|bond| BondInsertDb::from_bond(bond, validator_id))
+                .collect::<Vec<_>>(),
+        )
+        .execute(transaction_conn)
+        .context("Failed to copy validator bonds in db")?;
+
+    diesel::copy_from(unbonds::table)
+        .from_insertable(
+            &unbonds
+                .into_iter()
+                .map(|unbond| UnbondInsertDb::from_unbond(unbond, validator_id))
+                .collect::<Vec<_>>(),
+        )
+        .execute(transaction_conn)
+        .context("Failed to copy validator unbonds in db")?;
+
+    anyhow::Ok(())
+}
+
 pub fn insert_bonds(
     transaction_conn: &mut PgConnection,
     bonds: Bonds,
@@ -120,6 +170,8 @@ pub fn insert_unbonds(
             // part of on_conflict, it's most likely redundant
             unbonds::columns::withdraw_epoch
                 .eq(excluded(unbonds::columns::withdraw_epoch)),
+            unbonds::columns::start_epoch
+                .eq(excluded(unbonds::columns::start_epoch)),
         ))
         .execute(transaction_conn)
         .context("Failed to update unbonds in db")?;
@@ -159,18 +211,75 @@ pub fn remove_withdraws(
     anyhow::Ok(())
 }
 
+/// Records the rewards claimed by each delegation pair in the block
+pub fn insert_claimed_rewards(
+    transaction_conn: &mut PgConnection,
+    claimed_rewards: Vec<Reward>,
+    epoch: Epoch,
+    block_height: BlockHeight,
+) -> anyhow::Result<()> {
+    let claimed_rewards = claimed_rewards
+        .into_iter()
+        .map(|reward| {
+            let validator_id: i32 =
+                validators::table
+                    .filter(validators::namada_address.eq(
+                        &reward.delegation_pair.validator_address.to_string(),
+                    ))
+                    .select(validators::id)
+                    .first(transaction_conn)
+                    .context("Failed to get validator")?;
+
+            anyhow::Ok(PosRewardClaimInsertDb::from_reward(
+                reward,
+                validator_id,
+                epoch,
+                block_height,
+            ))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    diesel::insert_into(pos_rewards_claims::table)
+        .values::<&Vec<PosRewardClaimInsertDb>>(&claimed_rewards)
+        .on_conflict_do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert claimed rewards in db")?;
+
+    anyhow::Ok(())
+}
+
 pub fn delete_claimed_rewards(
     transaction_conn: &mut PgConnection,
-    reward_claimers: HashSet<Id>,
+    reward_claims: HashSet<DelegationPair>,
 ) -> anyhow::Result<()> {
-    diesel::delete(
-        pos_rewards::table.filter(
-            pos_rewards::dsl::owner
-                .eq_any(reward_claimers.into_iter().map(|id| id.to_string())),
-        ),
-    )
-    .execute(transaction_conn)
-    .context("Failed to update reawrds in db")?;
+    // If there are no claims, return early.
+    // Without this check, the query would delete all rewards in the table.
+    if reward_claims.is_empty() {
+        return Ok(());
+    }
+
+    let mut query = diesel::delete(pos_rewards::table).into_boxed();
+
+    for claim in reward_claims {
+        query = query.or_filter(
+            pos_rewards::owner
+                .eq(claim.delegator_address.to_string())
+                .and(
+                    pos_rewards::validator_id.eq_any(
+                        validators::table
+                            .select(validators::columns::id)
+                            .filter(
+                                validators::columns::namada_address
+                                    .eq(claim.validator_address.to_string()),
+                            ),
+                    ),
+                ),
+        );
+    }
+
+    query
+        .execute(transaction_conn)
+        .context("Failed to update reawrds in db")?;
 
     anyhow::Ok(())
 }
@@ -181,7 +290,9 @@ pub fn update_validator_metadata(
 ) -> anyhow::Result<()> {
     for metadata in metadata_change {
         let metadata_change_db = ValidatorUpdateMetadataDb {
-            commission: metadata.commission,
+            // Commission changes only take effect at the end of the
+            // pipeline, the PoS crawler updates the current rate then
+            pending_commission: metadata.commission,
             name: metadata.name,
             email: metadata.email,
             website: metadata.website,
@@ -198,15 +309,138 @@ pub fn update_validator_metadata(
     anyhow::Ok(())
 }
 
+pub fn insert_validator_blocks(
+    transaction_conn: &mut PgConnection,
+    validator_blocks: Vec<ValidatorBlock>,
+) -> anyhow::Result<()> {
+    let consensus_addresses = validator_blocks
+        .iter()
+        .map(|block| block.consensus_address.clone())
+        .collect::<HashSet<_>>();
+
+    // Blocks are attributed with every consensus address the validator had,
+    // not only the current one, as the key might have been rotated since
+    let mut validator_ids: HashMap<String, i32> =
+        validator_consensus_addresses::table
+            .filter(
+                validator_consensus_addresses::consensus_address
+                    .eq_any(&consensus_addresses),
+            )
+            .select((
+                validator_consensus_addresses::consensus_address,
+                validator_consensus_addresses::validator_id,
+            ))
+            .load::<(String, i32)>(transaction_conn)
+            .context("Failed to query previous validator consensus addresses")?
+            .into_iter()
+            .collect();
+    validator_ids.extend(
+        validators::table
+            .filter(validators::consensus_address.eq_any(&consensus_addresses))
+            .select((
+                validators::consensus_address.assume_not_null(),
+                validators::id,
+            ))
+            .load::<(String, i32)>(transaction_conn)
+            .context("Failed to query validators by consensus address")?,
+    );
+
+    // Validators that the PoS crawler didn't index yet are skipped
+    let validator_blocks_db = validator_blocks
+        .into_iter()
+        .filter_map(|block| {
+            let validator_id = *validator_ids.get(&block.consensus_address)?;
+            Some(ValidatorBlockInsertDb::from_validator_block(
+                block,
+                validator_id,
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    diesel::insert_into(validator_blocks::table)
+        .values::<&Vec<ValidatorBlockInsertDb>>(&validator_blocks_db)
+        .on_conflict_do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert validator blocks in db")?;
+
+    anyhow::Ok(())
+}
+
+/// Deletes the validator blocks below `from_height`, only the most recent ones
+/// are needed to compute uptimes
+pub fn prune_validator_blocks(
+    transaction_conn: &mut PgConnection,
+    from_height: BlockHeight,
+) -> anyhow::Result<()> {
+    diesel::delete(validator_blocks::table)
+        .filter(validator_blocks::height.lt(from_height as i32))
+        .execute(transaction_conn)
+        .context("Failed to prune validator blocks in db")?;
+
+    anyhow::Ok(())
+}
+
+pub fn insert_redelegations(
+    transaction_conn: &mut PgConnection,
+    redelegations: Redelegations,
+) -> anyhow::Result<()> {
+    if redelegations.is_empty() {
+        return anyhow::Ok(());
+    }
+
+    let validator_addresses = redelegations
+        .iter()
+        .flat_map(|redelegation| {
+            [
+                redelegation.src_validator.to_string(),
+                redelegation.dest_validator.to_string(),
+            ]
+        })
+        .collect::<HashSet<_>>();
+
+    let validator_ids: HashMap<String, i32> = validators::table
+        .filter(validators::namada_address.eq_any(validator_addresses))
+        .select((validators::namada_address, validators::id))
+        .load::<(String, i32)>(transaction_conn)
+        .context("Failed to query validators by address")?
+        .into_iter()
+        .collect();
+
+    let redelegations_db = redelegations
+        .into_iter()
+        .filter_map(|redelegation| {
+            let src_validator_id =
+                *validator_ids.get(&redelegation.src_validator.to_string())?;
+            let dest_validator_id =
+                *validator_ids.get(&redelegation.dest_validator.to_string())?;
+            Some(RedelegationInsertDb::from_redelegation(
+                redelegation,
+                src_validator_id,
+                dest_validator_id,
+            ))
+        })
+        .collect::<Vec<_>>();
+
+    diesel::insert_into(redelegations::table)
+        .values::<&Vec<RedelegationInsertDb>>(&redelegations_db)
+        .on_conflict_do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert redelegations in db")?;
+
+    anyhow::Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use orm::bond::BondDb;
+    use orm::pos_rewards::{PoSRewardClaimDb, PoSRewardDb, PosRewardInsertDb};
     use orm::unbond::UnbondDb;
+    use orm::validator_blocks::ValidatorBlockDb;
     use orm::validators::ValidatorInsertDb;
     use shared::balance::Amount;
     use shared::bond::Bond;
     use shared::unbond::Unbond;
-    use shared::validator::Validator;
+    use shared::validator::{Validator, ValidatorBlockKind};
     use test_helpers::db::TestDb;
 
     use super::*;
@@ -562,6 +796,206 @@ mod tests {
         .expect("Failed to run test");
     }
 
+    /// Test that the insert_validator_blocks function skips blocks of unknown
+    /// consensus addresses.
+    #[tokio::test]
+    async fn test_insert_validator_blocks_with_unknown_validator() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let fake_validator = Validator::fake();
+            let consensus_address =
+                fake_validator.consensus_address.clone().unwrap();
+
+            seed_validator(conn, fake_validator)?;
+
+            let validator_blocks = vec![
+                ValidatorBlock {
+                    consensus_address: consensus_address.clone(),
+                    height: 10,
+                    kind: ValidatorBlockKind::Proposed,
+                },
+                ValidatorBlock {
+                    consensus_address,
+                    height: 9,
+                    kind: ValidatorBlockKind::Missed,
+                },
+                ValidatorBlock {
+                    consensus_address: "unknown".to_string(),
+                    height: 9,
+                    kind: ValidatorBlockKind::Signed,
+                },
+            ];
+
+            insert_validator_blocks(conn, validator_blocks.clone())?;
+            // Reprocessing the same block should not duplicate rows
+            insert_validator_blocks(conn, validator_blocks)?;
+
+            let queried_validator_blocks = query_validator_blocks(conn);
+
+            assert_eq!(queried_validator_blocks.len(), 2);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that blocks signed with a previous consensus key are attributed to
+    /// the validator.
+    #[tokio::test]
+    async fn test_insert_validator_blocks_with_rotated_key() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            seed_validator(conn, Validator::fake())?;
+            let validator_id: i32 =
+                validators::table.select(validators::id).first(conn)?;
+            diesel::insert_into(validator_consensus_addresses::table)
+                .values((
+                    validator_consensus_addresses::consensus_address
+                        .eq("previous"),
+                    validator_consensus_addresses::validator_id
+                        .eq(validator_id),
+                ))
+                .execute(conn)?;
+
+            insert_validator_blocks(
+                conn,
+                vec![ValidatorBlock {
+                    consensus_address: "previous".to_string(),
+                    height: 9,
+                    kind: ValidatorBlockKind::Signed,
+                }],
+            )?;
+
+            let queried_validator_blocks = query_validator_blocks(conn);
+
+            assert_eq!(queried_validator_blocks.len(), 1);
+            assert_eq!(queried_validator_blocks[0].validator_id, validator_id);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that claiming rewards from a validator records the amount actually
+    /// claimed, not the stale claimable snapshot, and only removes the rewards
+    /// of that delegation pair.
+    #[tokio::test]
+    async fn test_claimed_rewards() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let validator1 = Validator::fake();
+            let validator2 = Validator::fake();
+            let delegator = Id::Account(
+                namada_core::address::gen_established_address("namada-indexer")
+                    .to_string(),
+            );
+
+            let rewards = [&validator1, &validator2]
+                .into_iter()
+                .map(|validator| Reward {
+                    delegation_pair: DelegationPair {
+                        validator_address: validator.address.clone(),
+                        delegator_address: delegator.clone(),
+                    },
+                    amount: Amount::fake(),
+                })
+                .collect::<Vec<_>>();
+
+            seed_validator(conn, validator1.clone())?;
+            seed_validator(conn, validator2)?;
+            seed_rewards(conn, rewards.clone())?;
+
+            let claimed_reward = Reward {
+                delegation_pair: rewards[0].delegation_pair.clone(),
+                amount: Amount::fake(),
+            };
+            let reward_claims =
+                HashSet::from([claimed_reward.delegation_pair.clone()]);
+
+            insert_claimed_rewards(conn, vec![claimed_reward.clone()], 5, 100)?;
+            delete_claimed_rewards(conn, reward_claims)?;
+
+            let queried_claims = query_reward_claims(conn);
+            let queried_rewards = query_rewards(conn);
+
+            assert_eq!(queried_claims.len(), 1);
+            assert_eq!(queried_claims[0].owner, delegator.to_string());
+            assert_eq!(queried_claims[0].block_height, 100);
+            assert_eq!(
+                queried_claims[0].raw_amount.to_string(),
+                claimed_reward.amount.to_string()
+            );
+            assert_eq!(queried_rewards.len(), 1);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that copy_validator_bonds replaces the bonds and unbonds of the
+    /// validator only.
+    #[tokio::test]
+    async fn test_copy_validator_bonds() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let validator = Validator::fake();
+            let other_validator = Validator::fake();
+            seed_bonds(
+                conn,
+                validator.clone(),
+                vec![Bond::fake(validator.address.clone())],
+            )?;
+            seed_unbonds(
+                conn,
+                other_validator.clone(),
+                vec![Unbond::fake(other_validator.address.clone())],
+            )?;
+
+            let validator_id: i32 = validators::table
+                .filter(
+                    validators::namada_address
+                        .eq(validator.address.to_string()),
+                )
+                .select(validators::id)
+                .first(conn)?;
+            let bonds: Vec<Bond> = (0..3)
+                .map(|_| Bond::fake(validator.address.clone()))
+                .collect();
+            let unbonds: Vec<Unbond> = (0..2)
+                .map(|_| Unbond::fake(validator.address.clone()))
+                .collect();
+
+            copy_validator_bonds(conn, validator_id, bonds, unbonds)?;
+
+            let queried_bonds = query_bonds(conn);
+            let queried_unbonds = query_unbonds(conn);
+
+            assert_eq!(queried_bonds.len(), 3);
+            assert!(queried_bonds
+                .iter()
+                .all(|bond| bond.validator_id == validator_id));
+            assert_eq!(queried_unbonds.len(), 3);
+            assert_eq!(
+                queried_unbonds
+                    .iter()
+                    .filter(|unbond| unbond.validator_id == validator_id)
+                    .count(),
+                2
+            );
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
     fn seed_bonds(
         conn: &mut PgConnection,
         validator: Validator,
@@ -622,6 +1056,29 @@ mod tests {
         anyhow::Ok(())
     }
 
+    fn seed_rewards(
+        conn: &mut PgConnection,
+        rewards: Vec<Reward>,
+    ) -> anyhow::Result<()> {
+        for reward in rewards {
+            let validator_id: i32 =
+                validators::table
+                    .filter(validators::namada_address.eq(
+                        reward.delegation_pair.validator_address.to_string(),
+                    ))
+                    .select(validators::id)
+                    .first(conn)
+                    .context("Failed to query validator")?;
+
+            diesel::insert_into(pos_rewards::table)
+                .values(PosRewardInsertDb::from_reward(reward, validator_id))
+                .execute(conn)
+                .context("Failed to insert rewards")?;
+        }
+
+        anyhow::Ok(())
+    }
+
     fn query_bonds(conn: &mut PgConnection) -> Vec<BondDb> {
         bonds::table
             .select(BondDb::as_select())
@@ -635,4 +1092,65 @@ mod tests {
             .load::<UnbondDb>(conn)
             .expect("Failed to query bonds")
     }
+
+    fn query_rewards(conn: &mut PgConnection) -> Vec<PoSRewardDb> {
+        pos_rewards::table
+            .select(PoSRewardDb::as_select())
+            .load::<PoSRewardDb>(conn)
+            .expect("Failed to query rewards")
+    }
+
+    fn query_reward_claims(conn: &mut PgConnection) -> Vec<PoSRewardClaimDb> {
+        pos_rewards_claims::table
+            .select(PoSRewardClaimDb::as_select())
+            .load::<PoSRewardClaimDb>(conn)
+            .expect("Failed to query reward claims")
+    }
+
+    /// Test that the prune_validator_blocks function only keeps the blocks
+    /// from the given height.
+    #[tokio::test]
+    async fn test_prune_validator_blocks() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let fake_validator = Validator::fake();
+            let consensus_address =
+                fake_validator.consensus_address.clone().unwrap();
+
+            seed_validator(conn, fake_validator)?;
+
+            let validator_blocks = (1..=10)
+                .map(|height| ValidatorBlock {
+                    consensus_address: consensus_address.clone(),
+                    height,
+                    kind: ValidatorBlockKind::Signed,
+                })
+                .collect::<Vec<_>>();
+            insert_validator_blocks(conn, validator_blocks)?;
+
+            prune_validator_blocks(conn, 8)?;
+
+            let mut heights = query_validator_blocks(conn)
+                .into_iter()
+                .map(|block| block.height)
+                .collect::<Vec<_>>();
+            heights.sort();
+
+            assert_eq!(heights, vec![8, 9, 10]);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    fn query_validator_blocks(
+        conn: &mut PgConnection,
+    ) -> Vec<ValidatorBlockDb> {
+        validator_blocks::table
+            .select(ValidatorBlockDb::as_select())
+            .load::<ValidatorBlockDb>(conn)
+            .expect("Failed to query validator blocks")
+    }
 }
@@ -0,0 +1,35 @@
+use anyhow::Context;
+use diesel::{ExpressionMethods, PgConnection, RunQueryDsl};
+use orm::schema::chain_snapshot;
+use orm::snapshot::{ChainSnapshotInsertDb, SnapshotPhaseDb};
+
+pub fn insert_snapshot(
+    transaction_conn: &mut PgConnection,
+    snapshot: ChainSnapshotInsertDb,
+) -> anyhow::Result<()> {
+    diesel::insert_into(chain_snapshot::table)
+        .values::<&ChainSnapshotInsertDb>(&snapshot)
+        .execute(transaction_conn)
+        .context("Failed to insert chain snapshot in db")?;
+
+    anyhow::Ok(())
+}
+
+pub fn update_snapshot_progress(
+    transaction_conn: &mut PgConnection,
+    block_height: i32,
+    phase: SnapshotPhaseDb,
+    cursor: Option<String>,
+) -> anyhow::Result<()> {
+    diesel::update(chain_snapshot::table)
+        .filter(chain_snapshot::block_height.eq(block_height))
+        .set((
+            chain_snapshot::phase.eq(phase),
+            chain_snapshot::cursor.eq(cursor),
+            chain_snapshot::timestamp.eq(chrono::Utc::now().naive_utc()),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to update chain snapshot progress in db")?;
+
+    anyhow::Ok(())
+}
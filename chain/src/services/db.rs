@@ -1,13 +1,24 @@
+use std::collections::HashSet;
+
 use anyhow::Context;
 use deadpool_diesel::postgres::Object;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use diesel::{
+    ExpressionMethods, NullableExpressionMethods, OptionalExtension, QueryDsl,
+    RunQueryDsl, SelectableHelper,
+};
 use orm::crawler_state::{
     ChainCrawlerStateDb, CrawlerNameDb, EpochCrawlerStateDb,
 };
-use orm::schema::crawler_state;
+use orm::schema::{
+    chain_snapshot, crawler_state, ibc_token, token, token_metadata, validators,
+};
+use orm::snapshot::ChainSnapshotDb;
+use orm::validators::ValidatorDb;
 use shared::block::{BlockHeight, Epoch};
 use shared::crawler_state::{ChainCrawlerState, EpochCrawlerState};
 use shared::error::ContextDbInteractError;
+use shared::id::Id;
+use shared::token::{IbcToken, Token};
 
 pub async fn get_chain_crawler_state(
     conn: &Object,
@@ -58,3 +69,87 @@ pub async fn get_pos_crawler_state(
         timestamp: crawler_state.timestamp.and_utc().timestamp(),
     })
 }
+
+/// The most recent initial snapshot, if one was ever started
+pub async fn get_chain_snapshot(
+    conn: &Object,
+) -> anyhow::Result<Option<ChainSnapshotDb>> {
+    conn.interact(move |conn| {
+        chain_snapshot::table
+            .order(chain_snapshot::block_height.desc())
+            .select(ChainSnapshotDb::as_select())
+            .first(conn)
+            .optional()
+    })
+    .await
+    .context_db_interact_error()?
+    .context("Failed to read chain snapshot from the db")
+}
+
+/// First validator, by address, that comes after `after`
+pub async fn get_next_validator(
+    conn: &Object,
+    after: Option<String>,
+) -> anyhow::Result<Option<ValidatorDb>> {
+    conn.interact(move |conn| {
+        let mut query = validators::table
+            .order(validators::namada_address.asc())
+            .select(ValidatorDb::as_select())
+            .into_boxed();
+
+        if let Some(after) = after {
+            query = query.filter(validators::namada_address.gt(after));
+        }
+
+        query.first(conn).optional()
+    })
+    .await
+    .context_db_interact_error()?
+    .context("Failed to read next validator from the db")
+}
+
+/// Tokens indexed without metadata, e.g. before the metadata was indexed
+pub async fn get_tokens_without_metadata(
+    conn: &Object,
+) -> anyhow::Result<Vec<Token>> {
+    let tokens: Vec<(String, Option<String>)> = conn
+        .interact(move |conn| {
+            token::table
+                .left_join(ibc_token::table)
+                .left_join(token_metadata::table)
+                .filter(token_metadata::address.nullable().is_null())
+                .select((token::address, ibc_token::ibc_trace.nullable()))
+                .load(conn)
+        })
+        .await
+        .context_db_interact_error()?
+        .context("Failed to read tokens without metadata from the db")?;
+
+    Ok(tokens
+        .into_iter()
+        .map(|(address, ibc_trace)| match ibc_trace {
+            Some(ibc_trace) => Token::Ibc(IbcToken {
+                address: Id::Account(address),
+                trace: Id::IbcTrace(ibc_trace),
+            }),
+            None => Token::Native(Id::Account(address)),
+        })
+        .collect())
+}
+
+/// Addresses of the tokens whose metadata is indexed
+pub async fn get_tokens_with_metadata(
+    conn: &Object,
+) -> anyhow::Result<HashSet<Id>> {
+    let addresses: Vec<String> = conn
+        .interact(move |conn| {
+            token_metadata::table
+                .select(token_metadata::address)
+                .load(conn)
+        })
+        .await
+        .context_db_interact_error()?
+        .context("Failed to read tokens metadata from the db")?;
+
+    Ok(addresses.into_iter().map(Id::Account).collect())
+}
@@ -9,9 +9,17 @@ use namada_core::chain::{
 use namada_sdk::address::{Address as NamadaSdkAddress, InternalAddress};
 use namada_sdk::collections::HashMap;
 use namada_sdk::hash::Hash;
-use namada_sdk::ibc::storage::{ibc_trace_key_prefix, is_ibc_trace_key};
+use namada_sdk::ibc::clients::tendermint::types::ClientState as TmClientState;
+use namada_sdk::ibc::core::channel::types::channel::ChannelEnd;
+use namada_sdk::ibc::core::connection::types::ConnectionEnd;
+use namada_sdk::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use namada_sdk::ibc::primitives::proto::{Any, Protobuf};
+use namada_sdk::ibc::storage::{
+    channel_key, client_state_key, connection_key, ibc_trace_key_prefix,
+    is_ibc_trace_key,
+};
 use namada_sdk::ibc::IbcTokenHash;
-use namada_sdk::queries::RPC;
+use namada_sdk::queries::{Client as _, RPC};
 use namada_sdk::rpc::{
     bonds_and_unbonds, query_proposal_by_id, query_storage_value,
 };
@@ -23,15 +31,18 @@ use shared::block::{BlockHeight, Epoch};
 use shared::bond::{Bond, BondAddresses, Bonds};
 use shared::id::Id;
 use shared::proposal::{GovernanceProposal, TallyType};
-use shared::token::{IbcToken, Token};
+use shared::rewards::Reward;
+use shared::token::{IbcToken, IbcTokenTrace, Token, TokenMetadata};
 use shared::unbond::{Unbond, UnbondAddresses, Unbonds};
-use shared::utils::BalanceChange;
+use shared::utils::{BalanceChange, DelegationPair};
 use shared::vote::{GovernanceVote, ProposalVoteKind};
 use subtle_encoding::hex;
 use tendermint_rpc::HttpClient;
 
 use super::utils::query_storage_prefix;
 
+const NATIVE_TOKEN_SYMBOL: &str = "NAM";
+
 pub async fn get_native_token(client: &HttpClient) -> anyhow::Result<Id> {
     let native_token = RPC
         .shell()
@@ -159,21 +170,102 @@ async fn query_ibc_tokens(
     Ok(tokens)
 }
 
-pub async fn query_all_balances(
+pub async fn query_tokens_metadata(
     client: &HttpClient,
-) -> anyhow::Result<Balances> {
-    let tokens = query_tokens(client).await?;
-    let mut all_balances: Balances = vec![];
+    tokens: &[Token],
+    block_height: BlockHeight,
+) -> anyhow::Result<Vec<TokenMetadata>> {
+    let mut tokens_metadata = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        let (address, ibc_trace) = match token {
+            Token::Native(address) => (address.clone(), None),
+            Token::Ibc(IbcToken { address, trace }) => {
+                (address.clone(), IbcTokenTrace::parse(&trace.to_string()))
+            }
+        };
+
+        let denomination =
+            rpc::query_denom(client, &NamadaSdkAddress::from(address.clone()))
+                .await
+                .map(|denom| denom.0)
+                .unwrap_or_default();
+
+        let symbol = match (token, &ibc_trace) {
+            (Token::Native(_), _) => Some(NATIVE_TOKEN_SYMBOL.to_string()),
+            (Token::Ibc(_), Some(trace)) => Some(trace.base_denom.clone()),
+            (Token::Ibc(_), None) => None,
+        };
+
+        // Further hops are only known by the chains the token went through
+        let origin_chain = match &ibc_trace {
+            Some(trace) if trace.hops == 1 => {
+                query_counterparty_chain_id(client, &trace.port, &trace.channel)
+                    .await
+            }
+            _ => None,
+        };
 
-    for token in tokens.into_iter() {
-        let balances = add_balance(client, token).await?;
-        all_balances.extend(balances);
+        tokens_metadata.push(TokenMetadata {
+            address,
+            denomination,
+            symbol,
+            ibc_trace,
+            origin_chain,
+            first_seen_height: block_height,
+        });
     }
 
-    anyhow::Ok(all_balances)
+    anyhow::Ok(tokens_metadata)
+}
+
+async fn query_counterparty_chain_id(
+    client: &HttpClient,
+    port: &str,
+    channel: &str,
+) -> Option<String> {
+    let port_id = PortId::from_str(port).ok()?;
+    let channel_id = ChannelId::from_str(channel).ok()?;
+
+    let (channel_bytes, _) = rpc::query_storage_value_bytes(
+        client,
+        &channel_key(&port_id, &channel_id),
+        None,
+        false,
+    )
+    .await
+    .ok()?;
+    let channel_end = ChannelEnd::decode_vec(&channel_bytes?).ok()?;
+    let connection_id = channel_end.connection_hops().first()?;
+
+    let (connection_bytes, _) = rpc::query_storage_value_bytes(
+        client,
+        &connection_key(connection_id),
+        None,
+        false,
+    )
+    .await
+    .ok()?;
+    let connection_end = ConnectionEnd::decode_vec(&connection_bytes?).ok()?;
+
+    let (client_state_bytes, _) = rpc::query_storage_value_bytes(
+        client,
+        &client_state_key(connection_end.client_id()),
+        None,
+        false,
+    )
+    .await
+    .ok()?;
+    let client_state =
+        <TmClientState as Protobuf<Any>>::decode_vec(&client_state_bytes?)
+            .ok()?;
+
+    Some(client_state.chain_id().to_string())
 }
 
-async fn add_balance(
+/// Queries the balances of every holder of `token`, they all come back in a
+/// single storage prefix response
+pub async fn query_token_balances(
     client: &HttpClient,
     token: Token,
 ) -> anyhow::Result<Vec<Balance>> {
@@ -212,6 +304,43 @@ async fn add_balance(
     Ok(all_balances)
 }
 
+/// Balance of `owner` in `token` as of `block_height`, zero if it holds none
+pub async fn query_balance_at_height(
+    client: &HttpClient,
+    owner: &Id,
+    token: &Id,
+    block_height: BlockHeight,
+) -> anyhow::Result<Amount> {
+    let balance_key = namada_token::storage_key::balance_key(
+        &NamadaSdkAddress::from(token.clone()),
+        &NamadaSdkAddress::from(owner.clone()),
+    );
+
+    let query_result = RPC
+        .shell()
+        .storage_value(
+            client,
+            None,
+            Some(to_block_height(block_height)),
+            false,
+            &balance_key,
+        )
+        .await
+        .with_context(|| {
+            format!("Failed to query balance of {} in {}", owner, token)
+        })?;
+
+    if query_result.data.is_empty() {
+        return Ok(Amount::from(NamadaSdkAmount::zero()));
+    }
+
+    let amount: NamadaSdkAmount =
+        borsh::BorshDeserialize::try_from_slice(&query_result.data)
+            .context("Failed to deserialize balance")?;
+
+    Ok(Amount::from(amount))
+}
+
 pub async fn query_last_block_height(
     client: &HttpClient,
 ) -> anyhow::Result<BlockHeight> {
@@ -242,7 +371,7 @@ pub async fn query_all_bonds_and_unbonds(
     type BondsMap = HashMap<BondKey, NamadaSdkAmount>;
 
     type UnbondKey = (Source, Validator, WithdrawEpoch);
-    type UnbondsMap = HashMap<UnbondKey, NamadaSdkAmount>;
+    type UnbondsMap = HashMap<UnbondKey, (NamadaSdkAmount, StartEpoch)>;
 
     let bonds_and_unbonds = bonds_and_unbonds(
         client,
@@ -273,10 +402,11 @@ pub async fn query_all_bonds_and_unbonds(
             let id = bond_id.clone();
             let key = (id.source, id.validator, ud.withdraw);
 
-            if let Some(record) = unbonds.get_mut(&key) {
-                *record = record.checked_add(ud.amount).unwrap();
+            if let Some((amount, start)) = unbonds.get_mut(&key) {
+                *amount = amount.checked_add(ud.amount).unwrap();
+                *start = (*start).min(ud.start);
             } else {
-                unbonds.insert(key, ud.amount);
+                unbonds.insert(key, (ud.amount, ud.start));
             }
         }
     }
@@ -294,64 +424,65 @@ pub async fn query_all_bonds_and_unbonds(
 
     let unbonds = unbonds
         .into_iter()
-        .map(|((source, target, withdraw), amount)| Unbond {
+        .map(|((source, target, withdraw), (amount, start))| Unbond {
             source: Id::from(source),
             target: Id::from(target),
             amount: Amount::from(amount),
             withdraw_at: withdraw.0 as Epoch,
+            start: start.0 as Epoch,
         })
         .collect();
 
     Ok((bonds, unbonds))
 }
 
-pub async fn query_all_proposals(
-    client: &HttpClient,
-) -> anyhow::Result<Vec<GovernanceProposal>> {
+/// Number of proposals submitted so far, ids go from 0 to count - 1
+pub async fn query_proposals_count(client: &HttpClient) -> anyhow::Result<u64> {
     let last_proposal_id_key =
         namada_governance::storage::keys::get_counter_key();
-    let last_proposal_id: u64 =
-        query_storage_value(client, &last_proposal_id_key)
-            .await
-            .unwrap();
 
-    let mut proposals: Vec<GovernanceProposal> = vec![];
-
-    for id in 0..last_proposal_id {
-        let proposal = query_proposal_by_id(client, id)
-            .await
-            .unwrap()
-            .expect("Proposal should be written to storage.");
-        let proposal_type = proposal.r#type.clone();
-
-        // Create a governance proposal from the namada proposal, without the
-        // data
-        let mut governance_proposal = GovernanceProposal::from(proposal);
-
-        // Get the proposal data based on the proposal type
-        let proposal_data = match proposal_type {
-            namada_governance::ProposalType::DefaultWithWasm(_) => {
-                let wasm_code = query_proposal_code(client, id).await?;
-                let hex_encoded = String::from_utf8(hex::encode(wasm_code))
-                    .unwrap_or_default();
-                Some(hex_encoded)
-            }
-            namada_governance::ProposalType::PGFSteward(data) => {
-                Some(serde_json::to_string(&data).unwrap())
-            }
-            namada_governance::ProposalType::PGFPayment(data) => {
-                Some(serde_json::to_string(&data).unwrap())
-            }
-            namada_governance::ProposalType::Default => None,
-        };
+    query_storage_value(client, &last_proposal_id_key)
+        .await
+        .context("Failed to query proposals count")
+}
 
-        // Add the proposal data to the governance proposal
-        governance_proposal.data = proposal_data;
+pub async fn query_proposal(
+    client: &HttpClient,
+    id: u64,
+) -> anyhow::Result<GovernanceProposal> {
+    let proposal = query_proposal_by_id(client, id)
+        .await
+        .with_context(|| format!("Failed to query proposal {}", id))?
+        .with_context(|| {
+            format!("Proposal {} is not written to storage", id)
+        })?;
+    let proposal_type = proposal.r#type.clone();
+
+    // Create a governance proposal from the namada proposal, without the
+    // data
+    let mut governance_proposal = GovernanceProposal::from(proposal);
+
+    // Get the proposal data based on the proposal type
+    let proposal_data = match proposal_type {
+        namada_governance::ProposalType::DefaultWithWasm(_) => {
+            let wasm_code = query_proposal_code(client, id).await?;
+            let hex_encoded =
+                String::from_utf8(hex::encode(wasm_code)).unwrap_or_default();
+            Some(hex_encoded)
+        }
+        namada_governance::ProposalType::PGFSteward(data) => {
+            Some(serde_json::to_string(&data).unwrap())
+        }
+        namada_governance::ProposalType::PGFPayment(data) => {
+            Some(serde_json::to_string(&data).unwrap())
+        }
+        namada_governance::ProposalType::Default => None,
+    };
 
-        proposals.push(governance_proposal);
-    }
+    // Add the proposal data to the governance proposal
+    governance_proposal.data = proposal_data;
 
-    anyhow::Ok(proposals)
+    anyhow::Ok(governance_proposal)
 }
 
 pub async fn query_proposal_code(
@@ -455,31 +586,24 @@ pub async fn query_unbonds(
                     .context("Failed to query unbond amount")
                     .ok()?;
 
-                let mut unbonds_map: HashMap<(Id, Id, Epoch), Amount> =
+                // Unbonds with the same withdraw epoch are merged into one,
+                // otherwise we can't insert them into the db
+                let mut unbonds_map: HashMap<Epoch, (Amount, Epoch)> =
                     HashMap::new();
 
-                for ((_, withdraw_epoch), amount) in unbonds {
-                    let record = unbonds_map.get_mut(&(
-                        Id::from(source.clone()),
-                        Id::from(validator.clone()),
-                        withdraw_epoch.0 as Epoch,
-                    ));
-
-                    // We have  to merge the unbonds with the same withdraw
-                    // epoch into one otherwise we can't
-                    // insert them into the db
-                    match record {
-                        Some(r) => {
-                            *r = r.checked_add(&Amount::from(amount)).unwrap();
+                for ((start_epoch, withdraw_epoch), amount) in unbonds {
+                    let amount = Amount::from(amount);
+                    let start_epoch = start_epoch.0 as Epoch;
+
+                    match unbonds_map.get_mut(&(withdraw_epoch.0 as Epoch)) {
+                        Some((total, start)) => {
+                            *total = total.checked_add(&amount).unwrap();
+                            *start = (*start).min(start_epoch);
                         }
                         None => {
                             unbonds_map.insert(
-                                (
-                                    Id::from(source.clone()),
-                                    Id::from(validator.clone()),
-                                    withdraw_epoch.0 as Epoch,
-                                ),
-                                Amount::from(amount),
+                                withdraw_epoch.0 as Epoch,
+                                (amount, start_epoch),
                             );
                         }
                     }
@@ -487,11 +611,12 @@ pub async fn query_unbonds(
 
                 let unbonds: Vec<Unbond> = unbonds_map
                     .into_iter()
-                    .map(|((source, target, start), amount)| Unbond {
-                        source,
-                        target,
+                    .map(|(withdraw_at, (amount, start))| Unbond {
+                        source: Id::from(source.clone()),
+                        target: Id::from(validator.clone()),
                         amount,
-                        withdraw_at: start,
+                        withdraw_at,
+                        start,
                     })
                     .collect();
 
@@ -596,6 +721,99 @@ pub async fn query_all_votes(
     anyhow::Ok(votes.iter().flatten().cloned().collect())
 }
 
+/// Stake of the validator at `epoch`, formatted like the PoS crawler stores
+/// it
+pub async fn query_validator_stake(
+    client: &HttpClient,
+    validator: &Id,
+    epoch: Epoch,
+) -> anyhow::Result<String> {
+    let stake = rpc::get_validator_stake(
+        client,
+        NamadaSdkEpoch::from(epoch as u64),
+        &NamadaSdkAddress::from(validator.clone()),
+    )
+    .await
+    .with_context(|| {
+        format!(
+            "Failed to query the stake of {} at epoch {}",
+            validator, epoch
+        )
+    })?;
+
+    Ok(stake.to_string_native())
+}
+
+/// Rewards of `source` delegating to the validator, not yet claimed
+pub async fn query_rewards(
+    client: &HttpClient,
+    source: &Id,
+    validator: &Id,
+) -> anyhow::Result<Amount> {
+    let rewards = RPC
+        .vp()
+        .pos()
+        .rewards(
+            client,
+            &NamadaSdkAddress::from(validator.clone()),
+            &Some(NamadaSdkAddress::from(source.clone())),
+        )
+        .await
+        .with_context(|| {
+            format!("Failed to query rewards of {} from {}", source, validator)
+        })?;
+
+    Ok(Amount::from(rewards))
+}
+
+/// Amount each delegation pair claimed in the block, that is the rewards it
+/// could claim right before the block was applied
+pub async fn query_claimed_rewards(
+    client: &HttpClient,
+    reward_claims: &HashSet<DelegationPair>,
+    block_height: BlockHeight,
+) -> anyhow::Result<Vec<Reward>> {
+    let claim_height = to_block_height(block_height.saturating_sub(1));
+
+    futures::stream::iter(reward_claims.iter().cloned())
+        .map(|delegation_pair| async move {
+            let path = RPC.vp().pos().rewards_path(
+                &NamadaSdkAddress::from(
+                    delegation_pair.validator_address.clone(),
+                ),
+                &Some(NamadaSdkAddress::from(
+                    delegation_pair.delegator_address.clone(),
+                )),
+            );
+
+            let query_result = client
+                .request(path, None, Some(claim_height), false)
+                .await
+                .with_context(|| {
+                    format!(
+                        "Failed to query rewards of {} from {} at height {}",
+                        delegation_pair.delegator_address,
+                        delegation_pair.validator_address,
+                        claim_height
+                    )
+                })?;
+
+            let amount: NamadaSdkAmount =
+                borsh::BorshDeserialize::try_from_slice(&query_result.data)
+                    .context("Failed to deserialize rewards")?;
+
+            anyhow::Ok(Reward {
+                delegation_pair,
+                amount: Amount::from(amount),
+            })
+        })
+        .buffer_unordered(20)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect()
+}
+
 fn to_block_height(block_height: u32) -> NamadaSdkBlockHeight {
     NamadaSdkBlockHeight::from(block_height as u64)
 }
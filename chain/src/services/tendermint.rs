@@ -1,7 +1,7 @@
 use anyhow::Context;
 use tendermint_rpc::endpoint::block::Response as TendermintBlockResponse;
 use tendermint_rpc::endpoint::block_results::Response as TendermintBlockResultResponse;
-use tendermint_rpc::{Client, HttpClient};
+use tendermint_rpc::{Client, HttpClient, Paging};
 
 // TODO: map return to our type
 pub async fn query_raw_block_at_height(
@@ -24,3 +24,21 @@ pub async fn query_raw_block_results_at_height(
         .await
         .context("Failed to query CometBFT's block results")
 }
+
+/// Returns the consensus addresses of the validator set at the given height,
+/// in the same order as the signatures of the commit for that height
+pub async fn query_validator_set_at_height(
+    client: &HttpClient,
+    height: u32,
+) -> anyhow::Result<Vec<String>> {
+    let response = client
+        .validators(height, Paging::All)
+        .await
+        .context("Failed to query CometBFT's validator set")?;
+
+    Ok(response
+        .validators
+        .into_iter()
+        .map(|validator| validator.address.to_string().to_lowercase())
+        .collect())
+}
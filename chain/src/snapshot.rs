@@ -0,0 +1,517 @@
+use std::cmp::Ordering;
+use std::convert::identity;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::Utc;
+use deadpool_diesel::postgres::Object;
+use diesel::PgConnection;
+use namada_sdk::time::DateTimeUtc;
+use orm::snapshot::{ChainSnapshotDb, ChainSnapshotInsertDb, SnapshotPhaseDb};
+use shared::block::{BlockHeight, Epoch};
+use shared::crawler_state::ChainCrawlerState;
+use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
+use shared::id::Id;
+use shared::token::Token;
+use tendermint_rpc::HttpClient;
+use tokio::time::sleep;
+
+use crate::repository;
+use crate::services::{db as db_service, namada as namada_service};
+
+/// Number of balances committed at once while copying a token's balances
+const BALANCES_PAGE_SIZE: usize = 10_000;
+
+/// Takes the initial snapshot of the chain the crawler starts from. The
+/// snapshot is loaded one page at a time (a page of a token's balances, a
+/// validator's bonds, a proposal) and each page is committed together with the
+/// snapshot progress, so a restart resumes after the last committed page.
+pub async fn take(
+    client: &HttpClient,
+    conn: &Object,
+    initial_query_retry_time: u64,
+) -> Result<(), MainError> {
+    let mut snapshot =
+        match db_service::get_chain_snapshot(conn).await.into_db_error()? {
+            Some(snapshot) => snapshot,
+            None => start(client, conn, initial_query_retry_time).await?,
+        };
+
+    if snapshot.phase != SnapshotPhaseDb::Done {
+        tracing::info!(
+            "Taking initial snapshot at block {}, from {:?} phase...",
+            snapshot.block_height,
+            snapshot.phase
+        );
+    }
+
+    // Queried once, the balances phase goes through them in order
+    let mut sorted_tokens: Option<Vec<Token>> = None;
+
+    loop {
+        snapshot = match snapshot.phase {
+            SnapshotPhaseDb::Tokens => tokens(client, conn, snapshot).await?,
+            SnapshotPhaseDb::Balances => {
+                if sorted_tokens.is_none() {
+                    let mut tokens = namada_service::query_tokens(client)
+                        .await
+                        .into_rpc_error()?;
+                    tokens.sort_by_key(|token| token.to_string());
+                    sorted_tokens = Some(tokens);
+                }
+
+                balances(
+                    client,
+                    conn,
+                    snapshot,
+                    sorted_tokens.as_deref().unwrap_or_default(),
+                )
+                .await?
+            }
+            SnapshotPhaseDb::Bonds => bonds(client, conn, snapshot).await?,
+            SnapshotPhaseDb::Proposals => {
+                proposals(client, conn, snapshot).await?
+            }
+            SnapshotPhaseDb::Done => return Ok(()),
+        };
+    }
+}
+
+async fn start(
+    client: &HttpClient,
+    conn: &Object,
+    initial_query_retry_time: u64,
+) -> Result<ChainSnapshotDb, MainError> {
+    let block_height = namada_service::query_last_block_height(client)
+        .await
+        .into_rpc_error()?;
+    let mut epoch =
+        namada_service::get_epoch_at_block_height(client, block_height)
+            .await
+            .into_rpc_error()?;
+    let first_block_in_epoch = namada_service::get_first_block_in_epoch(client)
+        .await
+        .into_rpc_error()?;
+
+    loop {
+        let pos_crawler_state = db_service::get_pos_crawler_state(conn)
+            .await
+            .into_db_error();
+
+        match pos_crawler_state {
+            // >= in case epochs are really short
+            Ok(pos_crawler_state)
+                if pos_crawler_state.last_processed_epoch >= epoch =>
+            {
+                // We assign pos crawler epoch as epoch to process
+                epoch = pos_crawler_state.last_processed_epoch;
+                break;
+            }
+            _ => {}
+        }
+
+        tracing::info!("Waiting for PoS service update...");
+
+        sleep(Duration::from_secs(initial_query_retry_time)).await;
+    }
+
+    let snapshot = ChainSnapshotInsertDb {
+        block_height: block_height as i32,
+        epoch: epoch as i32,
+        first_block_in_epoch: first_block_in_epoch as i32,
+        phase: SnapshotPhaseDb::Tokens,
+        cursor: None,
+        timestamp: Utc::now().naive_utc(),
+    };
+
+    let snapshot_db = snapshot.clone();
+    commit(conn, move |transaction_conn| {
+        repository::snapshot::insert_snapshot(transaction_conn, snapshot_db)
+    })
+    .await?;
+
+    Ok(snapshot)
+}
+
+async fn tokens(
+    client: &HttpClient,
+    conn: &Object,
+    snapshot: ChainSnapshotDb,
+) -> Result<ChainSnapshotDb, MainError> {
+    tracing::info!("Querying tokens...");
+    let tokens = namada_service::query_tokens(client)
+        .await
+        .into_rpc_error()?;
+    let tokens_metadata = namada_service::query_tokens_metadata(
+        client,
+        &tokens,
+        snapshot.block_height as BlockHeight,
+    )
+    .await
+    .into_rpc_error()?;
+
+    let block_height = snapshot.block_height;
+    commit(conn, move |transaction_conn| {
+        repository::balance::insert_tokens(transaction_conn, tokens)?;
+        repository::balance::insert_tokens_metadata(
+            transaction_conn,
+            tokens_metadata,
+        )?;
+
+        repository::snapshot::update_snapshot_progress(
+            transaction_conn,
+            block_height,
+            SnapshotPhaseDb::Balances,
+            None,
+        )
+    })
+    .await?;
+
+    Ok(advance(snapshot, SnapshotPhaseDb::Balances, None))
+}
+
+/// Copies the balances of the next token, one page at a time. The cursor is
+/// `<token>/<owner>` while a token is being copied, `<owner>` being the last
+/// one committed, and `<token>` once it is done.
+async fn balances(
+    client: &HttpClient,
+    conn: &Object,
+    snapshot: ChainSnapshotDb,
+    tokens: &[Token],
+) -> Result<ChainSnapshotDb, MainError> {
+    let block_height = snapshot.block_height;
+    let Some((token, last_owner)) =
+        next_balances(tokens, snapshot.cursor.as_deref())
+    else {
+        commit(conn, move |transaction_conn| {
+            repository::snapshot::update_snapshot_progress(
+                transaction_conn,
+                block_height,
+                SnapshotPhaseDb::Bonds,
+                None,
+            )
+        })
+        .await?;
+
+        return Ok(advance(snapshot, SnapshotPhaseDb::Bonds, None));
+    };
+
+    tracing::info!("Querying balances of token {}...", token);
+    let mut balances =
+        namada_service::query_token_balances(client, token.clone())
+            .await
+            .into_rpc_error()?;
+    balances.sort_by_key(|balance| balance.owner.to_string());
+    if let Some(last_owner) = &last_owner {
+        balances.retain(|balance| balance.owner.to_string() > *last_owner);
+    }
+    tracing::info!("Copying {} balances of token {}...", balances.len(), token);
+
+    if last_owner.is_none() {
+        let token = token.clone();
+        commit(conn, move |transaction_conn| {
+            // The token might have been created after the tokens phase
+            repository::balance::insert_tokens(
+                transaction_conn,
+                vec![token.clone()],
+            )?;
+            repository::balance::delete_token_balances(transaction_conn, &token)
+        })
+        .await?;
+    }
+
+    for page in balances.chunks(BALANCES_PAGE_SIZE) {
+        let page = page.to_vec();
+        let cursor = page
+            .last()
+            .map(|balance| format!("{}/{}", token, balance.owner));
+        commit(conn, move |transaction_conn| {
+            repository::balance::copy_token_balances(transaction_conn, page)?;
+
+            repository::snapshot::update_snapshot_progress(
+                transaction_conn,
+                block_height,
+                SnapshotPhaseDb::Balances,
+                cursor,
+            )
+        })
+        .await?;
+    }
+
+    let cursor = token.to_string();
+    let cursor_db = cursor.clone();
+    commit(conn, move |transaction_conn| {
+        repository::snapshot::update_snapshot_progress(
+            transaction_conn,
+            block_height,
+            SnapshotPhaseDb::Balances,
+            Some(cursor_db),
+        )
+    })
+    .await?;
+
+    Ok(advance(snapshot, SnapshotPhaseDb::Balances, Some(cursor)))
+}
+
+/// The token whose balances are copied next and the last owner already
+/// committed, `tokens` being sorted. A token missing from `tokens` since the
+/// cursor was committed is skipped.
+fn next_balances(
+    tokens: &[Token],
+    cursor: Option<&str>,
+) -> Option<(Token, Option<String>)> {
+    let Some(cursor) = cursor else {
+        return tokens.first().map(|token| (token.clone(), None));
+    };
+    let (last_token, last_owner) = match cursor.split_once('/') {
+        Some((token, owner)) => (token, Some(owner)),
+        None => (cursor, None),
+    };
+
+    tokens.iter().find_map(|token| {
+        match (token.to_string().as_str().cmp(last_token), last_owner) {
+            (Ordering::Equal, Some(owner)) => {
+                Some((token.clone(), Some(owner.to_string())))
+            }
+            (Ordering::Greater, _) => Some((token.clone(), None)),
+            _ => None,
+        }
+    })
+}
+
+async fn bonds(
+    client: &HttpClient,
+    conn: &Object,
+    snapshot: ChainSnapshotDb,
+) -> Result<ChainSnapshotDb, MainError> {
+    let next_validator =
+        db_service::get_next_validator(conn, snapshot.cursor.clone())
+            .await
+            .into_db_error()?;
+
+    let block_height = snapshot.block_height;
+    let Some(validator) = next_validator else {
+        commit(conn, move |transaction_conn| {
+            repository::snapshot::update_snapshot_progress(
+                transaction_conn,
+                block_height,
+                SnapshotPhaseDb::Proposals,
+                None,
+            )
+        })
+        .await?;
+
+        return Ok(advance(snapshot, SnapshotPhaseDb::Proposals, None));
+    };
+
+    tracing::info!(
+        "Querying bonds and unbonds of validator {}...",
+        validator.namada_address
+    );
+    let (bonds, unbonds) = namada_service::query_all_bonds_and_unbonds(
+        client,
+        None,
+        Some(Id::Account(validator.namada_address.clone())),
+    )
+    .await
+    .into_rpc_error()?;
+    tracing::info!(
+        "Copying {} bonds and {} unbonds of validator {}...",
+        bonds.len(),
+        unbonds.len(),
+        validator.namada_address
+    );
+
+    let cursor = validator.namada_address;
+    let cursor_db = cursor.clone();
+    commit(conn, move |transaction_conn| {
+        repository::pos::copy_validator_bonds(
+            transaction_conn,
+            validator.id,
+            bonds,
+            unbonds,
+        )?;
+
+        repository::snapshot::update_snapshot_progress(
+            transaction_conn,
+            block_height,
+            SnapshotPhaseDb::Bonds,
+            Some(cursor_db),
+        )
+    })
+    .await?;
+
+    Ok(advance(snapshot, SnapshotPhaseDb::Bonds, Some(cursor)))
+}
+
+async fn proposals(
+    client: &HttpClient,
+    conn: &Object,
+    snapshot: ChainSnapshotDb,
+) -> Result<ChainSnapshotDb, MainError> {
+    let proposals_count = namada_service::query_proposals_count(client)
+        .await
+        .into_rpc_error()?;
+
+    // The cursor is the id of the last proposal loaded
+    let next_id = match &snapshot.cursor {
+        Some(cursor) => {
+            cursor
+                .parse::<u64>()
+                .context("Invalid chain snapshot proposals cursor")
+                .into_db_error()?
+                + 1
+        }
+        None => 0,
+    };
+
+    let block_height = snapshot.block_height;
+    if next_id >= proposals_count {
+        let crawler_state = ChainCrawlerState {
+            last_processed_block: snapshot.block_height as BlockHeight,
+            last_processed_epoch: snapshot.epoch as Epoch,
+            first_block_in_epoch: snapshot.first_block_in_epoch as BlockHeight,
+            timestamp: DateTimeUtc::now().0.timestamp(),
+        };
+
+        commit(conn, move |transaction_conn| {
+            repository::crawler_state::upsert_crawler_state(
+                transaction_conn,
+                crawler_state,
+            )?;
+
+            repository::snapshot::update_snapshot_progress(
+                transaction_conn,
+                block_height,
+                SnapshotPhaseDb::Done,
+                None,
+            )
+        })
+        .await?;
+
+        tracing::info!("Initial snapshot taken at block {}", block_height);
+
+        return Ok(advance(snapshot, SnapshotPhaseDb::Done, None));
+    }
+
+    tracing::info!("Querying proposal {}...", next_id);
+    let proposal = namada_service::query_proposal(client, next_id)
+        .await
+        .into_rpc_error()?;
+    let proposals_with_tally =
+        namada_service::query_tallies(client, vec![proposal])
+            .await
+            .into_rpc_error()?;
+    let proposals_votes =
+        namada_service::query_all_votes(client, vec![next_id])
+            .await
+            .into_rpc_error()?;
+
+    let cursor = next_id.to_string();
+    let cursor_db = cursor.clone();
+    commit(conn, move |transaction_conn| {
+        repository::gov::insert_proposals(
+            transaction_conn,
+            proposals_with_tally,
+        )?;
+        repository::gov::insert_votes(transaction_conn, proposals_votes)?;
+
+        repository::snapshot::update_snapshot_progress(
+            transaction_conn,
+            block_height,
+            SnapshotPhaseDb::Proposals,
+            Some(cursor_db),
+        )
+    })
+    .await?;
+
+    Ok(advance(snapshot, SnapshotPhaseDb::Proposals, Some(cursor)))
+}
+
+fn advance(
+    snapshot: ChainSnapshotDb,
+    phase: SnapshotPhaseDb,
+    cursor: Option<String>,
+) -> ChainSnapshotDb {
+    ChainSnapshotDb {
+        phase,
+        cursor,
+        timestamp: Utc::now().naive_utc(),
+        ..snapshot
+    }
+}
+
+async fn commit<F>(conn: &Object, f: F) -> Result<(), MainError>
+where
+    F: FnOnce(&mut PgConnection) -> anyhow::Result<()> + Send + 'static,
+{
+    conn.interact(move |conn| conn.build_transaction().read_write().run(f))
+        .await
+        .context_db_interact_error()
+        .and_then(identity)
+        .into_db_error()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(address: &str) -> Token {
+        Token::Native(Id::Account(address.to_string()))
+    }
+
+    fn next_token(
+        tokens: &[Token],
+        cursor: Option<&str>,
+    ) -> Option<(String, Option<String>)> {
+        next_balances(tokens, cursor)
+            .map(|(token, owner)| (token.to_string(), owner))
+    }
+
+    /// Test that the balances phase starts with the first token.
+    #[test]
+    fn test_next_balances_without_cursor() {
+        let tokens = vec![token("tnam1a"), token("tnam1b")];
+
+        assert_eq!(
+            next_token(&tokens, None),
+            Some(("tnam1a".to_string(), None))
+        );
+        assert_eq!(next_token(&[], None), None);
+    }
+
+    /// Test that a token partially copied resumes after its last owner.
+    #[test]
+    fn test_next_balances_resumes_from_owner() {
+        let tokens = vec![token("tnam1a"), token("tnam1b")];
+
+        assert_eq!(
+            next_token(&tokens, Some("tnam1b/tnam1owner")),
+            Some(("tnam1b".to_string(), Some("tnam1owner".to_string())))
+        );
+    }
+
+    /// Test that a token fully copied moves on to the next one, and that the
+    /// phase ends after the last one.
+    #[test]
+    fn test_next_balances_after_token() {
+        let tokens = vec![token("tnam1a"), token("tnam1b")];
+
+        assert_eq!(
+            next_token(&tokens, Some("tnam1a")),
+            Some(("tnam1b".to_string(), None))
+        );
+        assert_eq!(next_token(&tokens, Some("tnam1b")), None);
+    }
+
+    /// Test that a token no longer served by the node is skipped.
+    #[test]
+    fn test_next_balances_with_missing_token() {
+        let tokens = vec![token("tnam1a"), token("tnam1c")];
+
+        assert_eq!(
+            next_token(&tokens, Some("tnam1b/tnam1owner")),
+            Some(("tnam1c".to_string(), None))
+        );
+    }
+}
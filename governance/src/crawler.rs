@@ -0,0 +1,192 @@
+use std::convert::identity;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::{NaiveDateTime, Utc};
+use deadpool_diesel::postgres::{Object, Pool};
+use namada_sdk::time::DateTimeUtc;
+use orm::governance_proposal::GovernanceProposalUpdateStatusDb;
+use orm::notifications::{self, IndexerEvent};
+use shared::crawler;
+use shared::crawler_state::{CrawlerName, IntervalCrawlerState};
+use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
+use tendermint_rpc::HttpClient;
+use tokio::sync::{Mutex, MutexGuard};
+use tokio::time::Instant;
+
+use crate::repository;
+use crate::services::namada as namada_service;
+
+/// Crawls every `sleep_for` seconds, the database behind `pool` must be
+/// migrated
+pub async fn run(
+    client: Arc<HttpClient>,
+    pool: Pool,
+    sleep_for: u64,
+) -> Result<(), MainError> {
+    // Initially set the instant to the current time minus the sleep_for
+    // so we can start processing right away
+    let instant = Arc::new(Mutex::new(
+        Instant::now()
+            .checked_sub(Duration::from_secs(sleep_for))
+            .unwrap(),
+    ));
+
+    crawler::crawl(
+        move |_| {
+            crawling_fn(
+                pool.clone(),
+                client.clone(),
+                instant.clone(),
+                sleep_for,
+            )
+        },
+        0,
+        None,
+    )
+    .await
+}
+
+async fn crawling_fn(
+    pool: Pool,
+    client: Arc<HttpClient>,
+    instant: Arc<Mutex<Instant>>,
+    sleep_for: u64,
+) -> Result<(), MainError> {
+    let conn = get_db_connection(&pool).await?;
+
+    let mut instant = instant.lock().await;
+
+    let should_process = can_process(&instant, sleep_for);
+
+    if !should_process {
+        let timestamp = Utc::now().naive_utc();
+        update_crawler_timestamp(&conn, timestamp).await?;
+
+        tracing::warn!(
+            "Not enough time has passed since last crawl, skipping..."
+        );
+
+        return Err(MainError::NoAction);
+    }
+
+    tracing::info!("Starting to update proposals...");
+
+    tracing::info!("Query epoch...");
+    let epoch = namada_service::query_last_epoch(&client)
+        .await
+        .into_rpc_error()?;
+
+    let running_governance_proposals = conn
+        .interact(move |conn| {
+            repository::governance::get_all_running_proposals(conn)
+        })
+        .await
+        .context_db_interact_error()
+        .and_then(identity)
+        .into_db_error()?;
+
+    tracing::info!(
+        "Got {} proposals to be tallied...",
+        running_governance_proposals.len()
+    );
+
+    let proposals_statuses = namada_service::get_governance_proposals_updates(
+        &client,
+        running_governance_proposals,
+        epoch as u32,
+    )
+    .await
+    .map_err(|_| MainError::RpcError)?;
+    tracing::info!(
+        "Got {} proposals statuses updates...",
+        proposals_statuses.len()
+    );
+
+    let timestamp = DateTimeUtc::now().0.timestamp();
+    let crawler_state = IntervalCrawlerState { timestamp };
+
+    conn.interact(move |conn| {
+        conn.build_transaction().read_write().run(
+            |transaction_conn: &mut diesel::prelude::PgConnection| {
+                let mut events = vec![];
+
+                for proposal_status in proposals_statuses {
+                    let proposal_id = proposal_status.id;
+                    let status =
+                        GovernanceProposalUpdateStatusDb::from(proposal_status);
+
+                    let previous_result =
+                        repository::governance::get_proposal_result(
+                            transaction_conn,
+                            proposal_id,
+                        )?;
+                    if previous_result != status.result {
+                        events.push(IndexerEvent::ProposalStatus {
+                            id: proposal_id as i32,
+                            result: status.result.clone(),
+                        });
+                    }
+
+                    repository::governance::update_proposal_status(
+                        transaction_conn,
+                        proposal_id,
+                        status,
+                    )?;
+                }
+
+                repository::crawler_state::upsert_crawler_state(
+                    transaction_conn,
+                    (CrawlerName::Governance, crawler_state).into(),
+                )?;
+
+                notifications::notify(transaction_conn, events);
+
+                anyhow::Ok(())
+            },
+        )
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()?;
+
+    // Once we are done processing, we reset the instant
+    *instant = Instant::now();
+
+    Ok(())
+}
+
+fn can_process(instant: &MutexGuard<Instant>, sleep_for: u64) -> bool {
+    tracing::info!("Attempting to process goverance data");
+
+    let time_elapsed = instant.elapsed().as_secs();
+    time_elapsed >= sleep_for
+}
+
+async fn update_crawler_timestamp(
+    conn: &Object,
+    timestamp: NaiveDateTime,
+) -> Result<(), MainError> {
+    conn.interact(move |transaction_conn| {
+        repository::crawler_state::update_timestamp(
+            transaction_conn,
+            timestamp,
+        )?;
+
+        anyhow::Ok(())
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()
+}
+
+/// Connections are only held for the crawl being processed
+async fn get_db_connection(pool: &Pool) -> Result<Object, MainError> {
+    pool.get()
+        .await
+        .context("Failed to get db connection handle from deadpool")
+        .into_db_error()
+}
@@ -41,6 +41,17 @@ pub fn get_all_running_proposals(
         .collect::<Result<Vec<GovernanceProposalShort>, _>>()
 }
 
+pub fn get_proposal_result(
+    transaction_conn: &mut PgConnection,
+    proposal_id: u64,
+) -> anyhow::Result<GovernanceProposalResultDb> {
+    governance_proposals::table
+        .find(proposal_id as i32)
+        .select(governance_proposals::dsl::result)
+        .first(transaction_conn)
+        .context("Failed to get governance proposal result from db")
+}
+
 pub fn update_proposal_status(
     transaction_conn: &mut PgConnection,
     proposal_id: u64,
@@ -0,0 +1,6 @@
+. ../.env
+export TENDERMINT_URL
+export DATABASE_URL
+export CACHE_URL
+export PORT
+cargo run
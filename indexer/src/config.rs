@@ -0,0 +1,82 @@
+use core::fmt;
+use std::fmt::Display;
+
+use clap_verbosity_flag::{InfoLevel, Verbosity};
+
+#[derive(clap::ValueEnum, Clone, Debug, Copy, PartialEq, Eq)]
+pub enum Crawler {
+    Chain,
+    Transactions,
+    Pos,
+    Rewards,
+    Governance,
+    Parameters,
+}
+
+impl Display for Crawler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(clap::Parser)]
+pub struct AppConfig {
+    /// Webserver options, the database and RPC urls are shared with the
+    /// crawlers
+    #[command(flatten)]
+    pub webserver: webserver::config::AppConfig,
+
+    /// Crawlers run alongside the webserver
+    #[clap(
+        long,
+        env,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = [
+            Crawler::Chain,
+            Crawler::Transactions,
+            Crawler::Pos,
+            Crawler::Rewards,
+            Crawler::Governance,
+            Crawler::Parameters,
+        ]
+    )]
+    pub crawlers: Vec<Crawler>,
+
+    /// Seconds before restarting a failed crawler, doubled after each
+    /// consecutive failure
+    #[clap(long, env, default_value_t = 5)]
+    pub restart_delay: u64,
+
+    #[clap(long, env, default_value_t = 15)]
+    pub initial_query_retry_time: u64,
+
+    /// Number of most recent blocks whose validator signatures are kept,
+    /// should cover the largest of `--uptime-windows`
+    #[clap(long, env, default_value_t = 10000)]
+    pub validator_blocks_retention: u32,
+
+    #[clap(long, env, default_value_t = 1)]
+    pub from_block_height: u32,
+
+    #[clap(long, env, default_value_t = 60)]
+    pub rewards_sleep_for: u64,
+
+    /// Maximum number of reward queries in flight at once
+    #[clap(long, env, default_value_t = 20)]
+    pub rewards_concurrency: usize,
+
+    /// Share of the epoch duration the reward queries are spread across, 0
+    /// queries them as fast as possible
+    #[clap(long, env, default_value_t = 0.5)]
+    pub rewards_epoch_spread: f64,
+
+    #[clap(long, env, default_value_t = 60)]
+    pub governance_sleep_for: u64,
+
+    #[clap(long, env, default_value_t = 30)]
+    pub parameters_sleep_for: u64,
+
+    #[command(flatten)]
+    pub verbosity: Verbosity<InfoLevel>,
+}
@@ -0,0 +1,3 @@
+pub mod app_state;
+pub mod config;
+pub mod supervisor;
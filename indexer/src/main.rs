@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use clap::Parser;
+use clap_verbosity_flag::LevelFilter;
+use indexer::app_state::AppState;
+use indexer::config::{AppConfig, Crawler};
+use indexer::supervisor::supervise;
+use orm::migrations::run_migrations;
+use shared::error::{AsDbError, ContextDbInteractError, MainError};
+use tendermint_rpc::HttpClient;
+use tracing::Level;
+use tracing_subscriber::FmtSubscriber;
+use webserver::app::ApplicationServer;
+
+#[tokio::main]
+async fn main() -> Result<(), MainError> {
+    let config = Arc::new(AppConfig::parse());
+
+    let log_level = match config.verbosity.log_level_filter() {
+        LevelFilter::Off => None,
+        LevelFilter::Error => Some(Level::ERROR),
+        LevelFilter::Warn => Some(Level::WARN),
+        LevelFilter::Info => Some(Level::INFO),
+        LevelFilter::Debug => Some(Level::DEBUG),
+        LevelFilter::Trace => Some(Level::TRACE),
+    };
+    if let Some(log_level) = log_level {
+        let subscriber =
+            FmtSubscriber::builder().with_max_level(log_level).finish();
+        tracing::subscriber::set_global_default(subscriber).unwrap();
+    }
+
+    tracing::info!("version: {}", env!("VERGEN_GIT_SHA").to_string());
+
+    let client = Arc::new(
+        HttpClient::new(config.webserver.tendermint_url.as_str()).unwrap(),
+    );
+
+    let app_state =
+        AppState::new(config.webserver.database_url.clone()).into_db_error()?;
+
+    // Run migrations
+    let conn = app_state.get_db_connection().await.into_db_error()?;
+    run_migrations(&conn)
+        .await
+        .context_db_interact_error()
+        .into_db_error()?;
+    drop(conn);
+
+    let crawlers = config.crawlers.iter().map(|crawler| {
+        let crawler = *crawler;
+        let client = client.clone();
+        let app_state = app_state.clone();
+        let config = config.clone();
+
+        supervise(crawler, config.restart_delay, move || {
+            run_crawler(
+                crawler,
+                client.clone(),
+                app_state.clone(),
+                config.clone(),
+            )
+        })
+    });
+
+    let web_app_state = webserver::appstate::AppState::with_pool(
+        app_state.pool(),
+        config.webserver.cache_url.clone(),
+    );
+    let server = ApplicationServer::serve_with(
+        config.webserver.clone(),
+        web_app_state,
+        client.as_ref().clone(),
+    );
+
+    let (server, _) = tokio::join!(server, futures::future::join_all(crawlers));
+    if let Err(e) = server {
+        tracing::error!("Webserver failed: {}", e);
+    }
+
+    Ok(())
+}
+
+/// Crawlers take their connections from the shared pool as they go, so a
+/// broken connection is replaced on the next block or epoch
+async fn run_crawler(
+    crawler: Crawler,
+    client: Arc<HttpClient>,
+    app_state: AppState,
+    config: Arc<AppConfig>,
+) -> Result<(), MainError> {
+    let pool = app_state.pool();
+
+    match crawler {
+        Crawler::Chain => {
+            chain::crawler::run(
+                client,
+                pool,
+                config.initial_query_retry_time,
+                config.validator_blocks_retention,
+            )
+            .await
+        }
+        Crawler::Transactions => {
+            transactions::crawler::run(
+                client,
+                pool,
+                config.from_block_height,
+                None,
+            )
+            .await
+        }
+        Crawler::Pos => pos::crawler::run(client, pool).await,
+        Crawler::Rewards => {
+            rewards::crawler::run(
+                client,
+                pool,
+                config.rewards_sleep_for,
+                config.rewards_concurrency,
+                config.rewards_epoch_spread,
+            )
+            .await
+        }
+        Crawler::Governance => {
+            governance::crawler::run(client, pool, config.governance_sleep_for)
+                .await
+        }
+        Crawler::Parameters => {
+            parameters::crawler::run(client, pool, config.parameters_sleep_for)
+                .await
+        }
+    }
+}
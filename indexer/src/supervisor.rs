@@ -0,0 +1,55 @@
+use std::future::Future;
+use std::time::Duration;
+
+use shared::error::MainError;
+use tokio::signal;
+use tokio::time::{sleep, Instant};
+
+use crate::config::Crawler;
+
+/// Upper bound of the restart delay, a crawler running for longer than this
+/// before failing is restarted after the initial delay again
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(300);
+
+/// Runs `task` in its own tokio task until it stops on its own, i.e. on
+/// interrupt, restarting it whenever it fails or panics. Crawlers retry a
+/// failing block or epoch in place until it succeeds, so only the errors
+/// raised while a crawler starts up, and panics, lead to a restart.
+pub async fn supervise<F, Fut>(crawler: Crawler, restart_delay: u64, task: F)
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<(), MainError>> + Send + 'static,
+{
+    let restart_delay = Duration::from_secs(restart_delay);
+    let mut delay = restart_delay;
+
+    loop {
+        let started_at = Instant::now();
+
+        match tokio::spawn(task()).await {
+            Ok(Ok(())) => {
+                tracing::info!("{} crawler stopped", crawler);
+                return;
+            }
+            Ok(Err(e)) => tracing::error!("{} crawler failed: {}", crawler, e),
+            Err(e) => tracing::error!("{} crawler panicked: {}", crawler, e),
+        }
+
+        if started_at.elapsed() > MAX_RESTART_DELAY {
+            delay = restart_delay;
+        }
+
+        tracing::info!(
+            "Restarting {} crawler in {} seconds...",
+            crawler,
+            delay.as_secs()
+        );
+
+        tokio::select! {
+            _ = sleep(delay) => {}
+            _ = signal::ctrl_c() => return,
+        }
+
+        delay = (delay * 2).min(MAX_RESTART_DELAY);
+    }
+}
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS validator_blocks;
+
+DROP TYPE VALIDATOR_BLOCK_KIND;
+
+DROP INDEX IF EXISTS index_validators_consensus_address;
+
+ALTER TABLE validators DROP COLUMN consensus_address;
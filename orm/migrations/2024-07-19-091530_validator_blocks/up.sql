@@ -0,0 +1,20 @@
+-- Your SQL goes here
+
+ALTER TABLE validators ADD COLUMN consensus_address VARCHAR;
+
+CREATE INDEX index_validators_consensus_address ON validators USING HASH (consensus_address);
+
+CREATE TYPE VALIDATOR_BLOCK_KIND AS ENUM ('proposed', 'signed', 'missed');
+
+CREATE TABLE validator_blocks (
+  id SERIAL PRIMARY KEY,
+  validator_id INT NOT NULL,
+  height INT NOT NULL,
+  kind VALIDATOR_BLOCK_KIND NOT NULL,
+  CONSTRAINT fk_validator_id FOREIGN KEY(validator_id) REFERENCES validators(id) ON DELETE CASCADE
+);
+
+ALTER TABLE validator_blocks ADD UNIQUE (validator_id, height, kind);
+
+-- For computing uptime over the last N blocks
+CREATE INDEX index_validator_blocks_height ON validator_blocks (height);
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+
+CREATE TYPE SLASH_TYPE AS ENUM ('duplicate_vote', 'light_client_attack');
+
+CREATE TABLE slashes (
+  id SERIAL PRIMARY KEY,
+  validator_id INT NOT NULL,
+  slash_type SLASH_TYPE NOT NULL,
+  block_height INT NOT NULL,
+  epoch INT NOT NULL,
+  rate VARCHAR NOT NULL,
+  CONSTRAINT fk_validator_id FOREIGN KEY(validator_id) REFERENCES validators(id) ON DELETE CASCADE
+);
+
+ALTER TABLE slashes ADD UNIQUE (validator_id, slash_type, block_height);
+
+CREATE INDEX index_slashes_validator_id ON slashes (validator_id);
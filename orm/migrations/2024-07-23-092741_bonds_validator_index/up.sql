@@ -0,0 +1,4 @@
+-- Your SQL goes here
+
+-- For listing the delegations of a validator
+CREATE INDEX index_bonds_validator_id ON bonds (validator_id);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS pos_rewards_claims;
+
+DROP TABLE IF EXISTS pos_rewards_history;
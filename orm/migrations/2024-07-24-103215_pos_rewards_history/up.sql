@@ -0,0 +1,28 @@
+-- Your SQL goes here
+
+CREATE TABLE pos_rewards_history (
+  id SERIAL PRIMARY KEY,
+  owner VARCHAR NOT NULL,
+  validator_id INT NOT NULL,
+  raw_amount NUMERIC(78, 0) NOT NULL,
+  epoch INT NOT NULL,
+  CONSTRAINT fk_validator_id FOREIGN KEY(validator_id) REFERENCES validators(id) ON DELETE CASCADE
+);
+
+ALTER TABLE pos_rewards_history ADD UNIQUE (owner, validator_id, epoch);
+
+CREATE INDEX index_pos_rewards_history_owner ON pos_rewards_history USING HASH (owner);
+
+CREATE TABLE pos_rewards_claims (
+  id SERIAL PRIMARY KEY,
+  owner VARCHAR NOT NULL,
+  validator_id INT NOT NULL,
+  raw_amount NUMERIC(78, 0) NOT NULL,
+  epoch INT NOT NULL,
+  block_height INT NOT NULL,
+  CONSTRAINT fk_validator_id FOREIGN KEY(validator_id) REFERENCES validators(id) ON DELETE CASCADE
+);
+
+ALTER TABLE pos_rewards_claims ADD UNIQUE (owner, validator_id, block_height);
+
+CREATE INDEX index_pos_rewards_claims_owner ON pos_rewards_claims USING HASH (owner);
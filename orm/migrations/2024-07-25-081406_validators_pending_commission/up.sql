@@ -0,0 +1,3 @@
+-- Your SQL goes here
+
+ALTER TABLE validators ADD COLUMN pending_commission VARCHAR;
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS redelegations;
+
+ALTER TABLE chain_parameters DROP COLUMN cubic_slashing_window_length;
@@ -0,0 +1,21 @@
+-- Your SQL goes here
+
+CREATE TABLE redelegations (
+  id SERIAL PRIMARY KEY,
+  tx_id VARCHAR(64) NOT NULL,
+  delegator VARCHAR NOT NULL,
+  src_validator_id INT NOT NULL,
+  dest_validator_id INT NOT NULL,
+  raw_amount NUMERIC(78, 0) NOT NULL,
+  epoch INT NOT NULL,
+  CONSTRAINT fk_src_validator_id FOREIGN KEY(src_validator_id) REFERENCES validators(id) ON DELETE CASCADE,
+  CONSTRAINT fk_dest_validator_id FOREIGN KEY(dest_validator_id) REFERENCES validators(id) ON DELETE CASCADE
+);
+
+ALTER TABLE redelegations ADD UNIQUE (tx_id);
+
+CREATE INDEX index_redelegations_delegator ON redelegations USING HASH (delegator);
+
+-- Needed to tell how long redelegated stake stays slashable for its source
+-- validator
+ALTER TABLE chain_parameters ADD COLUMN cubic_slashing_window_length INT NOT NULL DEFAULT 1;
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+CREATE TABLE token_supplies_per_epoch (
+  id SERIAL PRIMARY KEY,
+  address VARCHAR(45) NOT NULL,
+  epoch INT NOT NULL,
+  total NUMERIC(78, 0) NOT NULL,
+  effective NUMERIC(78, 0) NOT NULL,
+  bonded NUMERIC(78, 0) NOT NULL,
+  pgf NUMERIC(78, 0) NOT NULL,
+  masp NUMERIC(78, 0) NOT NULL,
+  CONSTRAINT fk_token_supplies_token FOREIGN KEY(address) REFERENCES token(address) ON DELETE CASCADE
+);
+
+ALTER TABLE token_supplies_per_epoch ADD UNIQUE (address, epoch);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+DROP MATERIALIZED VIEW IF EXISTS token_distribution;
+
+DROP INDEX IF EXISTS index_balances_token_raw_amount;
+
+DROP TABLE IF EXISTS internal_addresses;
@@ -0,0 +1,53 @@
+-- Your SQL goes here
+
+CREATE TABLE internal_addresses (
+  address VARCHAR PRIMARY KEY
+);
+
+CREATE INDEX index_balances_token_raw_amount ON balances (token, raw_amount DESC);
+
+-- Per token holders statistics, both with and without internal addresses.
+-- The gini coefficient is computed over the holders sorted by ascending
+-- balance as (2 * sum(i * x_i)) / (n * sum(x_i)) - (n + 1) / n
+CREATE MATERIALIZED VIEW token_distribution AS
+WITH holders AS (
+  SELECT
+    balances.token,
+    balances.raw_amount,
+    internal_addresses.address IS NOT NULL AS internal
+  FROM balances
+  LEFT JOIN internal_addresses ON internal_addresses.address = balances.owner
+  WHERE balances.raw_amount > 0
+),
+ranked AS (
+  SELECT
+    token,
+    raw_amount,
+    FALSE AS exclude_internal,
+    ROW_NUMBER() OVER (PARTITION BY token ORDER BY raw_amount ASC) AS asc_rank,
+    ROW_NUMBER() OVER (PARTITION BY token ORDER BY raw_amount DESC) AS desc_rank
+  FROM holders
+  UNION ALL
+  SELECT
+    token,
+    raw_amount,
+    TRUE AS exclude_internal,
+    ROW_NUMBER() OVER (PARTITION BY token ORDER BY raw_amount ASC) AS asc_rank,
+    ROW_NUMBER() OVER (PARTITION BY token ORDER BY raw_amount DESC) AS desc_rank
+  FROM holders
+  WHERE NOT internal
+)
+SELECT
+  token,
+  exclude_internal,
+  COUNT(*) AS holders_count,
+  SUM(raw_amount) AS total,
+  SUM(raw_amount) FILTER (WHERE desc_rank <= 10) AS top_10_amount,
+  SUM(raw_amount) FILTER (WHERE desc_rank <= 100) AS top_100_amount,
+  (2 * SUM(asc_rank * raw_amount)) / (COUNT(*) * SUM(raw_amount))
+    - (COUNT(*) + 1)::NUMERIC / COUNT(*) AS gini
+FROM ranked
+GROUP BY token, exclude_internal;
+
+-- Required to refresh the view concurrently
+CREATE UNIQUE INDEX index_token_distribution_token ON token_distribution (token, exclude_internal);
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+
+CREATE TABLE token_metadata (
+  address VARCHAR(45) PRIMARY KEY,
+  denomination INT NOT NULL,
+  symbol VARCHAR,
+  ibc_port VARCHAR,
+  ibc_channel VARCHAR,
+  ibc_base_denom VARCHAR,
+  origin_chain VARCHAR,
+  first_seen_height INT NOT NULL,
+  CONSTRAINT fk_token_metadata_token FOREIGN KEY(address) REFERENCES token(address) ON DELETE CASCADE
+);
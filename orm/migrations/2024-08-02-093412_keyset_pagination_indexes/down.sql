@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS index_governance_votes_proposal_id;
+
+DROP INDEX IF EXISTS index_validator_blocks_validator_id_height;
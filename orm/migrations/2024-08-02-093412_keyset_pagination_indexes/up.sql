@@ -0,0 +1,4 @@
+-- Your SQL goes here
+CREATE INDEX index_governance_votes_proposal_id ON governance_votes (proposal_id, id);
+
+CREATE INDEX index_validator_blocks_validator_id_height ON validator_blocks (validator_id, height DESC);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE IF EXISTS inner_transaction_addresses;
+
+DROP TABLE IF EXISTS balance_changes;
+
+DROP TABLE IF EXISTS blocks;
@@ -0,0 +1,31 @@
+-- Your SQL goes here
+CREATE TABLE blocks (
+  height INT PRIMARY KEY,
+  timestamp TIMESTAMP NOT NULL
+);
+
+CREATE INDEX index_blocks_timestamp ON blocks (timestamp);
+
+CREATE TABLE balance_changes (
+  id SERIAL PRIMARY KEY,
+  owner VARCHAR NOT NULL,
+  token VARCHAR(64) NOT NULL,
+  raw_amount NUMERIC(78, 0) NOT NULL,
+  height INT NOT NULL,
+  CONSTRAINT fk_balance_changes_token FOREIGN KEY(token) REFERENCES token(address) ON DELETE CASCADE
+);
+
+ALTER TABLE balance_changes ADD UNIQUE (owner, token, height);
+
+CREATE INDEX index_balance_changes_owner ON balance_changes (owner, id);
+
+CREATE TABLE inner_transaction_addresses (
+  id SERIAL PRIMARY KEY,
+  inner_tx_id VARCHAR(64) NOT NULL,
+  address VARCHAR NOT NULL,
+  CONSTRAINT fk_inner_transaction_addresses_inner_tx_id FOREIGN KEY(inner_tx_id) REFERENCES inner_transactions(id) ON DELETE CASCADE
+);
+
+ALTER TABLE inner_transaction_addresses ADD UNIQUE (inner_tx_id, address);
+
+CREATE INDEX index_inner_transaction_addresses_address ON inner_transaction_addresses (address, id);
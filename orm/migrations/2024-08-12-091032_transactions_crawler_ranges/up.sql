@@ -0,0 +1,8 @@
+-- Your SQL goes here
+CREATE TABLE transactions_crawler_ranges (
+  from_block_height INT NOT NULL,
+  to_block_height INT NOT NULL,
+  last_processed_block INT NOT NULL,
+  timestamp TIMESTAMP NOT NULL,
+  PRIMARY KEY (from_block_height, to_block_height)
+);
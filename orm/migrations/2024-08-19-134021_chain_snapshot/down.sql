@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE IF EXISTS chain_snapshot;
+
+DROP TYPE IF EXISTS SNAPSHOT_PHASE;
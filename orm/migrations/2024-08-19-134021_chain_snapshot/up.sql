@@ -0,0 +1,11 @@
+-- Your SQL goes here
+CREATE TYPE SNAPSHOT_PHASE AS ENUM ('tokens', 'balances', 'bonds', 'proposals', 'done');
+
+CREATE TABLE chain_snapshot (
+  block_height INT PRIMARY KEY,
+  epoch INT NOT NULL,
+  first_block_in_epoch INT NOT NULL,
+  phase SNAPSHOT_PHASE NOT NULL,
+  cursor VARCHAR,
+  timestamp TIMESTAMP NOT NULL
+);
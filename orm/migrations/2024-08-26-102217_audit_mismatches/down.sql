@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE IF EXISTS audit_mismatches;
+
+DROP TYPE IF EXISTS AUDIT_KIND;
@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TYPE AUDIT_KIND AS ENUM ('balance', 'bond', 'unbond', 'reward', 'voting_power');
+
+CREATE TABLE audit_mismatches (
+  id SERIAL PRIMARY KEY,
+  kind AUDIT_KIND NOT NULL,
+  address VARCHAR NOT NULL,
+  target VARCHAR,
+  epoch INT,
+  indexed VARCHAR,
+  expected VARCHAR,
+  block_height INT,
+  repaired BOOLEAN NOT NULL,
+  timestamp TIMESTAMP NOT NULL
+);
+
+CREATE INDEX index_audit_mismatches_timestamp ON audit_mismatches (timestamp);
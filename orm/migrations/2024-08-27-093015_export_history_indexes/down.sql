@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX IF EXISTS index_pos_rewards_claims_block_height;
+DROP INDEX IF EXISTS index_balance_changes_height;
+DROP INDEX IF EXISTS index_inner_transactions_wrapper_id;
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+CREATE INDEX index_inner_transactions_wrapper_id ON inner_transactions (wrapper_id);
+CREATE INDEX index_balance_changes_height ON balance_changes (height);
+CREATE INDEX index_pos_rewards_claims_block_height ON pos_rewards_claims (block_height);
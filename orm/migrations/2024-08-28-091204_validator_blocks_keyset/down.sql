@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX IF EXISTS index_validator_blocks_validator_id_height_id;
+
+CREATE INDEX index_validator_blocks_validator_id_height ON validator_blocks (validator_id, height DESC);
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+DROP INDEX IF EXISTS index_validator_blocks_validator_id_height;
+
+CREATE INDEX index_validator_blocks_validator_id_height_id ON validator_blocks (validator_id, height DESC, id DESC);
@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX IF EXISTS index_inner_transaction_addresses_address_block_height;
+
+CREATE INDEX index_inner_transaction_addresses_address ON inner_transaction_addresses (address, id);
+
+ALTER TABLE inner_transaction_addresses DROP COLUMN block_height;
@@ -0,0 +1,14 @@
+-- Your SQL goes here
+ALTER TABLE inner_transaction_addresses ADD COLUMN block_height INT;
+
+UPDATE inner_transaction_addresses
+SET block_height = wrapper_transactions.block_height
+FROM inner_transactions
+JOIN wrapper_transactions ON wrapper_transactions.id = inner_transactions.wrapper_id
+WHERE inner_transactions.id = inner_transaction_addresses.inner_tx_id;
+
+ALTER TABLE inner_transaction_addresses ALTER COLUMN block_height SET NOT NULL;
+
+DROP INDEX IF EXISTS index_inner_transaction_addresses_address;
+
+CREATE INDEX index_inner_transaction_addresses_address_block_height ON inner_transaction_addresses (address, block_height DESC, id DESC);
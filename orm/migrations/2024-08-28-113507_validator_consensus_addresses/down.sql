@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX IF EXISTS index_validator_blocks_validator_id_height_id;
+
+CREATE INDEX index_validator_blocks_validator_id_height_id ON validator_blocks (validator_id, height DESC, id DESC);
+
+DROP TABLE IF EXISTS validator_consensus_addresses;
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+-- Every consensus address a validator signed with, so that blocks signed
+-- before a consensus key rotation are still attributed to the validator
+CREATE TABLE validator_consensus_addresses (
+  consensus_address VARCHAR PRIMARY KEY,
+  validator_id INT NOT NULL,
+  CONSTRAINT fk_validator_consensus_addresses_validator_id FOREIGN KEY(validator_id) REFERENCES validators(id) ON DELETE CASCADE
+);
+
+INSERT INTO validator_consensus_addresses (consensus_address, validator_id)
+SELECT consensus_address, id FROM validators WHERE consensus_address IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+-- Uptimes only need the kind of the blocks in the window, so they are
+-- counted from the index alone
+DROP INDEX IF EXISTS index_validator_blocks_validator_id_height_id;
+
+CREATE INDEX index_validator_blocks_validator_id_height_id ON validator_blocks (validator_id, height DESC, id DESC) INCLUDE (kind);
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+-- Earliest start epoch of the bonds merged into the unbond, unknown for the
+-- unbonds not refreshed since
+ALTER TABLE unbonds ADD COLUMN start_epoch INT;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE transactions_crawler_ranges DROP CONSTRAINT IF EXISTS transactions_crawler_ranges_no_overlap;
+
+ALTER TABLE crawler_state DROP COLUMN first_processed_block;
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- First block of the live transactions crawler, the blocks from there up to
+-- its last processed block can't be claimed by a range instance
+ALTER TABLE crawler_state ADD COLUMN first_processed_block INT;
+
+UPDATE crawler_state
+SET first_processed_block = (
+  SELECT MIN(blocks.height)
+  FROM blocks
+  WHERE NOT EXISTS (
+    SELECT 1
+    FROM transactions_crawler_ranges
+    WHERE blocks.height BETWEEN transactions_crawler_ranges.from_block_height AND transactions_crawler_ranges.to_block_height
+  )
+)
+WHERE name = 'transactions';
+
+-- Two instances can't claim overlapping ranges, even concurrently
+ALTER TABLE transactions_crawler_ranges ADD CONSTRAINT transactions_crawler_ranges_no_overlap EXCLUDE USING gist (int4range(from_block_height, to_block_height, '[]') WITH &&);
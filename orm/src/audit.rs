@@ -0,0 +1,38 @@
+use diesel::Insertable;
+use serde::{Deserialize, Serialize};
+
+use crate::schema::audit_mismatches;
+
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, diesel_derive_enum::DbEnum,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::AuditKind"]
+pub enum AuditKindDb {
+    Balance,
+    Bond,
+    Unbond,
+    Reward,
+    VotingPower,
+}
+
+/// An indexed value that differs from the node one, `indexed` or `expected`
+/// is `None` when the row is missing on that side
+#[derive(Serialize, Insertable, Clone, Debug)]
+#[diesel(table_name = audit_mismatches)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AuditMismatchInsertDb {
+    pub kind: AuditKindDb,
+    /// Owner, delegator or validator address
+    pub address: String,
+    /// Token or validator address
+    pub target: Option<String>,
+    /// Start epoch of a bond, withdraw epoch of an unbond, epoch the voting
+    /// power was audited at
+    pub epoch: Option<i32>,
+    pub indexed: Option<String>,
+    pub expected: Option<String>,
+    /// Block the indexed value was audited at, `None` for the voting power
+    pub block_height: Option<i32>,
+    pub repaired: bool,
+    pub timestamp: chrono::NaiveDateTime,
+}
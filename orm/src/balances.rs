@@ -3,9 +3,10 @@ use std::str::FromStr;
 use bigdecimal::BigDecimal;
 use diesel::{Insertable, Queryable, Selectable};
 use shared::balance::Balance;
+use shared::block::BlockHeight;
 use shared::token::Token;
 
-use crate::schema::balances;
+use crate::schema::{balance_changes, balances};
 
 #[derive(Insertable, Clone, Queryable, Selectable, Debug)]
 #[diesel(table_name = balances)]
@@ -33,3 +34,28 @@ impl BalancesInsertDb {
         }
     }
 }
+
+#[derive(Insertable, Clone, Queryable, Selectable, Debug)]
+#[diesel(table_name = balance_changes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BalanceChangesInsertDb {
+    pub owner: String,
+    pub token: String,
+    pub raw_amount: BigDecimal,
+    pub height: i32,
+}
+
+pub type BalanceChangeDb = BalanceChangesInsertDb;
+
+impl BalanceChangesInsertDb {
+    pub fn from_balance(balance: Balance, height: BlockHeight) -> Self {
+        let balance = BalancesInsertDb::from_balance(balance);
+
+        Self {
+            owner: balance.owner,
+            token: balance.token,
+            raw_amount: balance.raw_amount,
+            height: height as i32,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use anyhow::Context;
+use diesel::{Insertable, Queryable, Selectable};
+use shared::block::BlockHeight;
+
+use crate::schema::blocks;
+
+#[derive(Insertable, Clone, Queryable, Selectable, Debug)]
+#[diesel(table_name = blocks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BlockInsertDb {
+    pub height: i32,
+    pub timestamp: chrono::NaiveDateTime,
+}
+
+pub type BlockDb = BlockInsertDb;
+
+impl BlockInsertDb {
+    pub fn from(height: BlockHeight, timestamp: i64) -> anyhow::Result<Self> {
+        let timestamp = chrono::DateTime::from_timestamp(timestamp, 0)
+            .with_context(|| {
+                format!("Invalid timestamp {} for block {}", timestamp, height)
+            })?
+            .naive_utc();
+
+        Ok(Self {
+            height: height as i32,
+            timestamp,
+        })
+    }
+}
@@ -4,12 +4,13 @@ use diesel::pg::Pg;
 use diesel::sql_types::Nullable;
 use diesel::{Insertable, Queryable, Selectable};
 use serde::{Deserialize, Serialize};
+use shared::block::BlockHeight;
 use shared::crawler_state::{
     BlockCrawlerState, ChainCrawlerState, CrawlerName, EpochCrawlerState,
     IntervalCrawlerState,
 };
 
-use crate::schema::crawler_state;
+use crate::schema::{crawler_state, transactions_crawler_ranges};
 
 #[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
 #[ExistingTypePath = "crate::schema::sql_types::CrawlerName"]
@@ -57,6 +58,7 @@ pub struct CrawlerStateDb {
     pub last_processed_epoch: Option<i32>,
     pub first_block_in_epoch: Option<i32>,
     pub timestamp: chrono::NaiveDateTime,
+    pub first_processed_block: Option<i32>,
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -202,6 +204,20 @@ pub struct IntervalStateInsertDb {
     pub timestamp: chrono::NaiveDateTime,
 }
 
+/// Progress of a transactions crawler instance indexing a bounded height
+/// range, next to the live one tracked in `crawler_state`
+#[derive(Serialize, Queryable, Selectable, Insertable, Clone, Debug)]
+#[diesel(table_name = transactions_crawler_ranges)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TransactionsRangeStateInsertDb {
+    pub from_block_height: i32,
+    pub to_block_height: i32,
+    pub last_processed_block: i32,
+    pub timestamp: chrono::NaiveDateTime,
+}
+
+pub type TransactionsRangeStateDb = TransactionsRangeStateInsertDb;
+
 impl From<(CrawlerName, i64)> for CrawlerStateTimestampInsertDb {
     fn from((crawler_name, timestamp): (CrawlerName, i64)) -> Self {
         let timestamp = chrono::DateTime::from_timestamp(timestamp, 0)
@@ -273,3 +289,22 @@ impl From<(CrawlerName, IntervalCrawlerState)> for IntervalStateInsertDb {
         }
     }
 }
+
+impl TransactionsRangeStateInsertDb {
+    pub fn from(
+        from_block_height: BlockHeight,
+        to_block_height: BlockHeight,
+        state: BlockCrawlerState,
+    ) -> Self {
+        let timestamp = chrono::DateTime::from_timestamp(state.timestamp, 0)
+            .expect("Invalid timestamp")
+            .naive_utc();
+
+        Self {
+            from_block_height: from_block_height as i32,
+            to_block_height: to_block_height as i32,
+            last_processed_block: state.last_processed_block as i32,
+            timestamp,
+        }
+    }
+}
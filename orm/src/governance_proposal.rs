@@ -48,7 +48,9 @@ impl From<TallyType> for GovernanceProposalTallyTypeDb {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, diesel_derive_enum::DbEnum,
+)]
 #[ExistingTypePath = "crate::schema::sql_types::GovernanceResult"]
 pub enum GovernanceProposalResultDb {
     Passed,
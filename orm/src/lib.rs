@@ -1,4 +1,6 @@
+pub mod audit;
 pub mod balances;
+pub mod blocks;
 pub mod bond;
 pub mod crawler_state;
 pub mod gas;
@@ -7,11 +9,17 @@ pub mod governance_votes;
 pub mod group_by_macros;
 pub mod helpers;
 pub mod migrations;
+pub mod notifications;
 pub mod parameters;
 pub mod pos_rewards;
+pub mod redelegations;
 pub mod revealed_pk;
 pub mod schema;
+pub mod slashes;
+pub mod snapshot;
 pub mod token;
 pub mod transactions;
 pub mod unbond;
+pub mod validator_blocks;
 pub mod validators;
+pub mod views;
@@ -1,5 +1,6 @@
 use deadpool_diesel::postgres::Object;
 use deadpool_diesel::InteractError;
+use diesel::migration::Migration;
 use diesel_migrations::{
     embed_migrations, EmbeddedMigrations, MigrationHarness,
 };
@@ -15,3 +16,20 @@ pub async fn run_migrations(conn: &Object) -> Result<(), InteractError> {
     .await
     .map(|_| ())
 }
+
+/// Names of the embedded migrations the database is missing
+pub async fn pending_migrations(
+    conn: &Object,
+) -> Result<anyhow::Result<Vec<String>>, InteractError> {
+    conn.interact(|transaction_conn| {
+        let migrations = transaction_conn
+            .pending_migrations(MIGRATIONS)
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        Ok(migrations
+            .iter()
+            .map(|migration| migration.name().to_string())
+            .collect())
+    })
+    .await
+}
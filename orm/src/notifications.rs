@@ -0,0 +1,54 @@
+use anyhow::Context;
+use diesel::sql_types::Text;
+use diesel::{Connection, PgConnection, RunQueryDsl};
+use serde::{Deserialize, Serialize};
+
+use crate::governance_proposal::GovernanceProposalResultDb;
+
+/// Postgres channel the crawlers publish their events to
+pub const INDEXER_EVENTS_CHANNEL: &str = "indexer_events";
+
+/// Events published by the crawlers. Postgres caps notification payloads at
+/// 8000 bytes, so events only carry ids and listeners read the details from
+/// the db.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum IndexerEvent {
+    #[serde(rename_all = "camelCase")]
+    Block { height: i32, epoch: i32 },
+    #[serde(rename_all = "camelCase")]
+    Transaction { id: String },
+    #[serde(rename_all = "camelCase")]
+    ProposalStatus {
+        id: i32,
+        result: GovernanceProposalResultDb,
+    },
+    #[serde(rename_all = "camelCase")]
+    ValidatorSet { epoch: i32, validators: usize },
+}
+
+/// Publishes the events on `INDEXER_EVENTS_CHANNEL`. Postgres only delivers
+/// them once the surrounding transaction commits, so listeners never see
+/// events for data that was rolled back. Each event is sent under its own
+/// savepoint, so that failing to publish it never rolls back the indexed
+/// data: the event is dropped instead.
+pub fn notify(transaction_conn: &mut PgConnection, events: Vec<IndexerEvent>) {
+    for event in events {
+        let result = serde_json::to_string(&event)
+            .context("Failed to serialize indexer event")
+            .and_then(|payload| {
+                transaction_conn
+                    .transaction(|conn| {
+                        diesel::sql_query("SELECT pg_notify($1, $2)")
+                            .bind::<Text, _>(INDEXER_EVENTS_CHANNEL)
+                            .bind::<Text, _>(payload)
+                            .execute(conn)
+                    })
+                    .context("Failed to notify indexer event")
+            });
+
+        if let Err(e) = result {
+            tracing::warn!("Dropping indexer event {:?}: {:#}", event, e);
+        }
+    }
+}
@@ -2,9 +2,10 @@ use std::str::FromStr;
 
 use bigdecimal::BigDecimal;
 use diesel::{Insertable, Queryable, Selectable};
+use shared::block::{BlockHeight, Epoch};
 use shared::rewards::Reward;
 
-use crate::schema::pos_rewards;
+use crate::schema::{pos_rewards, pos_rewards_claims, pos_rewards_history};
 
 #[derive(Insertable, Clone, Queryable, Selectable)]
 #[diesel(table_name = pos_rewards)]
@@ -27,3 +28,62 @@ impl PosRewardInsertDb {
         }
     }
 }
+
+#[derive(Insertable, Clone, Queryable, Selectable)]
+#[diesel(table_name = pos_rewards_history)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PosRewardHistoryInsertDb {
+    pub owner: String,
+    pub validator_id: i32,
+    pub raw_amount: BigDecimal,
+    pub epoch: i32,
+}
+
+pub type PoSRewardHistoryDb = PosRewardHistoryInsertDb;
+
+impl PosRewardHistoryInsertDb {
+    pub fn from_reward(
+        reward: Reward,
+        validator_id: i32,
+        epoch: Epoch,
+    ) -> Self {
+        Self {
+            owner: reward.delegation_pair.delegator_address.to_string(),
+            raw_amount: BigDecimal::from_str(&reward.amount.to_string())
+                .expect("Invalid amount"),
+            validator_id,
+            epoch: epoch as i32,
+        }
+    }
+}
+
+#[derive(Insertable, Clone, Queryable, Selectable)]
+#[diesel(table_name = pos_rewards_claims)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PosRewardClaimInsertDb {
+    pub owner: String,
+    pub validator_id: i32,
+    pub raw_amount: BigDecimal,
+    pub epoch: i32,
+    pub block_height: i32,
+}
+
+pub type PoSRewardClaimDb = PosRewardClaimInsertDb;
+
+impl PosRewardClaimInsertDb {
+    pub fn from_reward(
+        reward: Reward,
+        validator_id: i32,
+        epoch: Epoch,
+        block_height: BlockHeight,
+    ) -> Self {
+        Self {
+            owner: reward.delegation_pair.delegator_address.to_string(),
+            validator_id,
+            raw_amount: BigDecimal::from_str(&reward.amount.to_string())
+                .expect("Invalid amount"),
+            epoch: epoch as i32,
+            block_height: block_height as i32,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use diesel::{Insertable, Queryable, Selectable};
+use shared::redelegation::Redelegation;
+
+use crate::schema::redelegations;
+
+#[derive(Insertable, Clone, Queryable, Selectable)]
+#[diesel(table_name = redelegations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RedelegationInsertDb {
+    pub tx_id: String,
+    pub delegator: String,
+    pub src_validator_id: i32,
+    pub dest_validator_id: i32,
+    pub raw_amount: BigDecimal,
+    pub epoch: i32,
+}
+
+pub type RedelegationDb = RedelegationInsertDb;
+
+impl RedelegationInsertDb {
+    pub fn from_redelegation(
+        redelegation: Redelegation,
+        src_validator_id: i32,
+        dest_validator_id: i32,
+    ) -> Self {
+        Self {
+            tx_id: redelegation.tx_id.to_string(),
+            delegator: redelegation.delegator.to_string(),
+            src_validator_id,
+            dest_validator_id,
+            raw_amount: BigDecimal::from_str(&redelegation.amount.to_string())
+                .expect("Invalid amount"),
+            epoch: redelegation.epoch as i32,
+        }
+    }
+}
@@ -1,6 +1,14 @@
 // @generated automatically by Diesel CLI.
 
 pub mod sql_types {
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "audit_kind"))]
+    pub struct AuditKind;
+
     #[derive(
         diesel::query_builder::QueryId,
         std::fmt::Debug,
@@ -33,6 +41,22 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "governance_tally_type"))]
     pub struct GovernanceTallyType;
 
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "slash_type"))]
+    pub struct SlashType;
+
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "snapshot_phase"))]
+    pub struct SnapshotPhase;
+
     #[derive(
         diesel::query_builder::QueryId,
         std::fmt::Debug,
@@ -57,6 +81,14 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "transaction_result"))]
     pub struct TransactionResult;
 
+    #[derive(
+        diesel::query_builder::QueryId,
+        std::fmt::Debug,
+        diesel::sql_types::SqlType,
+    )]
+    #[diesel(postgres_type(name = "validator_block_kind"))]
+    pub struct ValidatorBlockKind;
+
     #[derive(
         diesel::query_builder::QueryId,
         std::fmt::Debug,
@@ -74,6 +106,35 @@ pub mod sql_types {
     pub struct VoteKind;
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::AuditKind;
+
+    audit_mismatches (id) {
+        id -> Int4,
+        kind -> AuditKind,
+        address -> Varchar,
+        target -> Nullable<Varchar>,
+        epoch -> Nullable<Int4>,
+        indexed -> Nullable<Varchar>,
+        expected -> Nullable<Varchar>,
+        block_height -> Nullable<Int4>,
+        repaired -> Bool,
+        timestamp -> Timestamp,
+    }
+}
+
+diesel::table! {
+    balance_changes (id) {
+        id -> Int4,
+        owner -> Varchar,
+        #[max_length = 64]
+        token -> Varchar,
+        raw_amount -> Numeric,
+        height -> Int4,
+    }
+}
+
 diesel::table! {
     balances (id) {
         id -> Int4,
@@ -84,6 +145,13 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    blocks (height) {
+        height -> Int4,
+        timestamp -> Timestamp,
+    }
+}
+
 diesel::table! {
     bonds (id) {
         id -> Int4,
@@ -109,6 +177,21 @@ diesel::table! {
         genesis_time -> Int8,
         epoch_switch_blocks_delay -> Int4,
         checksums -> Jsonb,
+        cubic_slashing_window_length -> Int4,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::SnapshotPhase;
+
+    chain_snapshot (block_height) {
+        block_height -> Int4,
+        epoch -> Int4,
+        first_block_in_epoch -> Int4,
+        phase -> SnapshotPhase,
+        cursor -> Nullable<Varchar>,
+        timestamp -> Timestamp,
     }
 }
 
@@ -122,6 +205,7 @@ diesel::table! {
         first_block_in_epoch -> Nullable<Int4>,
         last_processed_epoch -> Nullable<Int4>,
         timestamp -> Timestamp,
+        first_processed_block -> Nullable<Int4>,
     }
 }
 
@@ -187,6 +271,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    inner_transaction_addresses (id) {
+        id -> Int4,
+        #[max_length = 64]
+        inner_tx_id -> Varchar,
+        address -> Varchar,
+        block_height -> Int4,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::TransactionKind;
@@ -204,6 +298,12 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    internal_addresses (address) {
+        address -> Varchar,
+    }
+}
+
 diesel::table! {
     pos_rewards (id) {
         id -> Int4,
@@ -213,6 +313,40 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    pos_rewards_claims (id) {
+        id -> Int4,
+        owner -> Varchar,
+        validator_id -> Int4,
+        raw_amount -> Numeric,
+        epoch -> Int4,
+        block_height -> Int4,
+    }
+}
+
+diesel::table! {
+    pos_rewards_history (id) {
+        id -> Int4,
+        owner -> Varchar,
+        validator_id -> Int4,
+        raw_amount -> Numeric,
+        epoch -> Int4,
+    }
+}
+
+diesel::table! {
+    redelegations (id) {
+        id -> Int4,
+        #[max_length = 64]
+        tx_id -> Varchar,
+        delegator -> Varchar,
+        src_validator_id -> Int4,
+        dest_validator_id -> Int4,
+        raw_amount -> Numeric,
+        epoch -> Int4,
+    }
+}
+
 diesel::table! {
     revealed_pk (id) {
         id -> Int4,
@@ -221,6 +355,20 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::SlashType;
+
+    slashes (id) {
+        id -> Int4,
+        validator_id -> Int4,
+        slash_type -> SlashType,
+        block_height -> Int4,
+        epoch -> Int4,
+        rate -> Varchar,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::TokenType;
@@ -232,6 +380,43 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    token_metadata (address) {
+        #[max_length = 45]
+        address -> Varchar,
+        denomination -> Int4,
+        symbol -> Nullable<Varchar>,
+        ibc_port -> Nullable<Varchar>,
+        ibc_channel -> Nullable<Varchar>,
+        ibc_base_denom -> Nullable<Varchar>,
+        origin_chain -> Nullable<Varchar>,
+        first_seen_height -> Int4,
+    }
+}
+
+diesel::table! {
+    token_supplies_per_epoch (id) {
+        id -> Int4,
+        #[max_length = 45]
+        address -> Varchar,
+        epoch -> Int4,
+        total -> Numeric,
+        effective -> Numeric,
+        bonded -> Numeric,
+        pgf -> Numeric,
+        masp -> Numeric,
+    }
+}
+
+diesel::table! {
+    transactions_crawler_ranges (from_block_height, to_block_height) {
+        from_block_height -> Int4,
+        to_block_height -> Int4,
+        last_processed_block -> Int4,
+        timestamp -> Timestamp,
+    }
+}
+
 diesel::table! {
     unbonds (id) {
         id -> Int4,
@@ -239,6 +424,26 @@ diesel::table! {
         validator_id -> Int4,
         raw_amount -> Numeric,
         withdraw_epoch -> Int4,
+        start_epoch -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ValidatorBlockKind;
+
+    validator_blocks (id) {
+        id -> Int4,
+        validator_id -> Int4,
+        height -> Int4,
+        kind -> ValidatorBlockKind,
+    }
+}
+
+diesel::table! {
+    validator_consensus_addresses (consensus_address) {
+        consensus_address -> Varchar,
+        validator_id -> Int4,
     }
 }
 
@@ -259,6 +464,8 @@ diesel::table! {
         discord_handle -> Nullable<Varchar>,
         avatar -> Nullable<Varchar>,
         state -> ValidatorState,
+        consensus_address -> Nullable<Varchar>,
+        pending_commission -> Nullable<Varchar>,
     }
 }
 
@@ -278,29 +485,53 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(balance_changes -> token (token));
 diesel::joinable!(balances -> token (token));
 diesel::joinable!(bonds -> validators (validator_id));
 diesel::joinable!(governance_votes -> governance_proposals (proposal_id));
 diesel::joinable!(ibc_token -> token (address));
+diesel::joinable!(inner_transaction_addresses -> inner_transactions (inner_tx_id));
 diesel::joinable!(inner_transactions -> wrapper_transactions (wrapper_id));
 diesel::joinable!(pos_rewards -> validators (validator_id));
+diesel::joinable!(pos_rewards_claims -> validators (validator_id));
+diesel::joinable!(pos_rewards_history -> validators (validator_id));
+diesel::joinable!(slashes -> validators (validator_id));
+diesel::joinable!(token_metadata -> token (address));
+diesel::joinable!(token_supplies_per_epoch -> token (address));
 diesel::joinable!(unbonds -> validators (validator_id));
+diesel::joinable!(validator_blocks -> validators (validator_id));
+diesel::joinable!(validator_consensus_addresses -> validators (validator_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    audit_mismatches,
+    balance_changes,
     balances,
+    blocks,
     bonds,
     chain_parameters,
+    chain_snapshot,
     crawler_state,
     gas,
     gas_price,
     governance_proposals,
     governance_votes,
     ibc_token,
+    inner_transaction_addresses,
     inner_transactions,
+    internal_addresses,
     pos_rewards,
+    pos_rewards_claims,
+    pos_rewards_history,
+    redelegations,
     revealed_pk,
+    slashes,
     token,
+    token_metadata,
+    token_supplies_per_epoch,
+    transactions_crawler_ranges,
     unbonds,
+    validator_blocks,
+    validator_consensus_addresses,
     validators,
     wrapper_transactions,
 );
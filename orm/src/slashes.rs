@@ -0,0 +1,46 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use shared::slash::{Slash, SlashType};
+
+use crate::schema::slashes;
+
+#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::SlashType"]
+pub enum SlashTypeDb {
+    DuplicateVote,
+    LightClientAttack,
+}
+
+impl From<SlashType> for SlashTypeDb {
+    fn from(value: SlashType) -> Self {
+        match value {
+            SlashType::DuplicateVote => Self::DuplicateVote,
+            SlashType::LightClientAttack => Self::LightClientAttack,
+        }
+    }
+}
+
+#[derive(Insertable, Clone, Queryable, Selectable)]
+#[diesel(table_name = slashes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SlashInsertDb {
+    pub validator_id: i32,
+    pub slash_type: SlashTypeDb,
+    pub block_height: i32,
+    pub epoch: i32,
+    pub rate: String,
+}
+
+pub type SlashDb = SlashInsertDb;
+
+impl SlashInsertDb {
+    pub fn from_slash(slash: Slash, validator_id: i32) -> Self {
+        Self {
+            validator_id,
+            slash_type: slash.slash_type.into(),
+            block_height: slash.block_height as i32,
+            epoch: slash.epoch as i32,
+            rate: slash.rate,
+        }
+    }
+}
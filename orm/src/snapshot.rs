@@ -0,0 +1,39 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+
+use crate::schema::chain_snapshot;
+
+/// Phases of the chain crawler initial snapshot, in the order they run
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Serialize,
+    Deserialize,
+    diesel_derive_enum::DbEnum,
+)]
+#[ExistingTypePath = "crate::schema::sql_types::SnapshotPhase"]
+pub enum SnapshotPhaseDb {
+    Tokens,
+    Balances,
+    Bonds,
+    Proposals,
+    Done,
+}
+
+#[derive(Serialize, Insertable, Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = chain_snapshot)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ChainSnapshotInsertDb {
+    pub block_height: i32,
+    pub epoch: i32,
+    pub first_block_in_epoch: i32,
+    pub phase: SnapshotPhaseDb,
+    /// Last item committed in the current phase, `None` if the phase has not
+    /// started yet
+    pub cursor: Option<String>,
+    pub timestamp: chrono::NaiveDateTime,
+}
+
+pub type ChainSnapshotDb = ChainSnapshotInsertDb;
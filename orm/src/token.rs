@@ -1,8 +1,15 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
 use diesel::{Insertable, Queryable, Selectable};
 use serde::{Deserialize, Serialize};
-use shared::token::Token;
+use shared::balance::Amount;
+use shared::token::{Token, TokenMetadata, TokenSupply};
 
-use crate::schema::{ibc_token, token};
+use crate::schema::{
+    ibc_token, token, token_metadata, token_supplies_per_epoch,
+};
+use crate::views::token_distribution;
 
 #[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
 #[ExistingTypePath = "crate::schema::sql_types::TokenType"]
@@ -57,3 +64,89 @@ impl IbcTokenDb {
         }
     }
 }
+
+#[derive(Debug, Clone, Queryable, Selectable, Insertable)]
+#[diesel(table_name = token_supplies_per_epoch)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TokenSupplyInsertDb {
+    pub address: String,
+    pub epoch: i32,
+    pub total: BigDecimal,
+    pub effective: BigDecimal,
+    pub bonded: BigDecimal,
+    pub pgf: BigDecimal,
+    pub masp: BigDecimal,
+}
+
+pub type TokenSupplyDb = TokenSupplyInsertDb;
+
+impl From<TokenSupply> for TokenSupplyInsertDb {
+    fn from(supply: TokenSupply) -> Self {
+        let to_big_decimal = |amount: Amount| {
+            BigDecimal::from_str(&amount.to_string()).expect("Invalid amount")
+        };
+
+        Self {
+            address: supply.address.to_string(),
+            epoch: supply.epoch as i32,
+            total: to_big_decimal(supply.total),
+            effective: to_big_decimal(supply.effective),
+            bonded: to_big_decimal(supply.bonded),
+            pgf: to_big_decimal(supply.pgf),
+            masp: to_big_decimal(supply.masp),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = token_distribution)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TokenDistributionDb {
+    pub token: String,
+    pub exclude_internal: bool,
+    pub holders_count: i64,
+    pub total: BigDecimal,
+    pub top_10_amount: BigDecimal,
+    pub top_100_amount: BigDecimal,
+    pub gini: BigDecimal,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Insertable)]
+#[diesel(table_name = token_metadata)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TokenMetadataInsertDb {
+    pub address: String,
+    pub denomination: i32,
+    pub symbol: Option<String>,
+    pub ibc_port: Option<String>,
+    pub ibc_channel: Option<String>,
+    pub ibc_base_denom: Option<String>,
+    pub origin_chain: Option<String>,
+    pub first_seen_height: i32,
+}
+
+pub type TokenMetadataDb = TokenMetadataInsertDb;
+
+impl From<TokenMetadata> for TokenMetadataInsertDb {
+    fn from(metadata: TokenMetadata) -> Self {
+        let (ibc_port, ibc_channel, ibc_base_denom) = match metadata.ibc_trace {
+            Some(trace) => (
+                Some(trace.port),
+                Some(trace.channel),
+                Some(trace.base_denom),
+            ),
+            None => (None, None, None),
+        };
+
+        Self {
+            address: metadata.address.to_string(),
+            denomination: metadata.denomination as i32,
+            symbol: metadata.symbol,
+            ibc_port,
+            ibc_channel,
+            ibc_base_denom,
+            origin_chain: metadata.origin_chain,
+            first_seen_height: metadata.first_seen_height as i32,
+        }
+    }
+}
@@ -1,11 +1,17 @@
+use std::collections::HashSet;
+
 use diesel::{Insertable, Queryable, Selectable};
 use serde::{Deserialize, Serialize};
+use shared::block::BlockHeight;
+use shared::id::Id;
 use shared::transaction::{
     InnerTransaction, TransactionExitStatus, TransactionKind,
     WrapperTransaction,
 };
 
-use crate::schema::{inner_transactions, wrapper_transactions};
+use crate::schema::{
+    inner_transaction_addresses, inner_transactions, wrapper_transactions,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
 #[ExistingTypePath = "crate::schema::sql_types::TransactionKind"]
@@ -102,6 +108,34 @@ impl InnerTransactionInsertDb {
     }
 }
 
+#[derive(Serialize, Queryable, Selectable, Insertable, Clone)]
+#[diesel(table_name = inner_transaction_addresses)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct InnerTransactionAddressInsertDb {
+    pub inner_tx_id: String,
+    pub address: String,
+    pub block_height: i32,
+}
+
+pub type InnerTransactionAddressDb = InnerTransactionAddressInsertDb;
+
+impl InnerTransactionAddressInsertDb {
+    pub fn from(
+        tx: &InnerTransaction,
+        addresses: &HashSet<Id>,
+        block_height: BlockHeight,
+    ) -> Vec<Self> {
+        addresses
+            .iter()
+            .map(|address| Self {
+                inner_tx_id: tx.tx_id.to_string(),
+                address: address.to_string(),
+                block_height: block_height as i32,
+            })
+            .collect()
+    }
+}
+
 #[derive(Serialize, Queryable, Selectable, Insertable, Clone)]
 #[diesel(table_name = wrapper_transactions)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
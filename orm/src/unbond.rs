@@ -16,6 +16,7 @@ pub struct UnbondInsertDb {
     pub validator_id: i32,
     pub raw_amount: BigDecimal,
     pub withdraw_epoch: i32,
+    pub start_epoch: Option<i32>,
 }
 
 #[derive(Identifiable, Clone, Queryable, Selectable, Associations)]
@@ -28,6 +29,7 @@ pub struct UnbondDb {
     pub validator_id: i32,
     pub raw_amount: BigDecimal,
     pub withdraw_epoch: i32,
+    pub start_epoch: Option<i32>,
 }
 
 impl UnbondInsertDb {
@@ -38,6 +40,7 @@ impl UnbondInsertDb {
             raw_amount: BigDecimal::from_str(&unbond.amount.to_string())
                 .expect("Invalid amount"),
             withdraw_epoch: unbond.withdraw_at as i32,
+            start_epoch: Some(unbond.start as i32),
         }
     }
 }
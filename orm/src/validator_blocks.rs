@@ -0,0 +1,47 @@
+use diesel::{Insertable, Queryable, Selectable};
+use serde::{Deserialize, Serialize};
+use shared::validator::{ValidatorBlock, ValidatorBlockKind};
+
+use crate::schema::validator_blocks;
+
+#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::ValidatorBlockKind"]
+pub enum ValidatorBlockKindDb {
+    Proposed,
+    Signed,
+    Missed,
+}
+
+impl From<ValidatorBlockKind> for ValidatorBlockKindDb {
+    fn from(value: ValidatorBlockKind) -> Self {
+        match value {
+            ValidatorBlockKind::Proposed => Self::Proposed,
+            ValidatorBlockKind::Signed => Self::Signed,
+            ValidatorBlockKind::Missed => Self::Missed,
+        }
+    }
+}
+
+#[derive(Insertable, Clone, Queryable, Selectable)]
+#[diesel(table_name = validator_blocks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ValidatorBlockInsertDb {
+    pub validator_id: i32,
+    pub height: i32,
+    pub kind: ValidatorBlockKindDb,
+}
+
+pub type ValidatorBlockDb = ValidatorBlockInsertDb;
+
+impl ValidatorBlockInsertDb {
+    pub fn from_validator_block(
+        validator_block: ValidatorBlock,
+        validator_id: i32,
+    ) -> Self {
+        Self {
+            validator_id,
+            height: validator_block.height as i32,
+            kind: validator_block.kind.into(),
+        }
+    }
+}
@@ -20,7 +20,15 @@ pub enum ValidatorSortByDb {
     Rank,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, diesel_derive_enum::DbEnum)]
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    diesel_derive_enum::DbEnum,
+)]
 #[ExistingTypePath = "crate::schema::sql_types::ValidatorState"]
 pub enum ValidatorStateDb {
     Consensus,
@@ -60,6 +68,8 @@ pub struct ValidatorDb {
     pub discord_handle: Option<String>,
     pub avatar: Option<String>,
     pub state: ValidatorStateDb,
+    pub consensus_address: Option<String>,
+    pub pending_commission: Option<String>,
 }
 
 #[derive(Serialize, Insertable, Clone)]
@@ -71,13 +81,15 @@ pub struct ValidatorInsertDb {
     pub max_commission: String,
     pub commission: String,
     pub state: ValidatorStateDb,
+    pub consensus_address: Option<String>,
+    pub pending_commission: Option<String>,
 }
 
 #[derive(Serialize, AsChangeset, Clone)]
 #[diesel(table_name = validators)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct ValidatorUpdateMetadataDb {
-    pub commission: Option<String>,
+    pub pending_commission: Option<String>,
     pub name: Option<String>,
     pub email: Option<String>,
     pub website: Option<String>,
@@ -95,6 +107,8 @@ impl ValidatorInsertDb {
             max_commission: validator.max_commission.clone(),
             commission: validator.commission.clone(),
             state: validator.state.into(),
+            consensus_address: validator.consensus_address,
+            pending_commission: validator.pending_commission,
         }
     }
 }
@@ -0,0 +1,23 @@
+// Diesel doesn't generate the schema of views, so they are declared here
+// instead of in schema.rs
+
+diesel::table! {
+    token_holders (token, owner) {
+        token -> Varchar,
+        owner -> Varchar,
+        raw_amount -> Numeric,
+        internal -> Bool,
+    }
+}
+
+diesel::table! {
+    token_distribution (token, exclude_internal) {
+        token -> Varchar,
+        exclude_internal -> Bool,
+        holders_count -> Int8,
+        total -> Numeric,
+        top_10_amount -> Numeric,
+        top_100_amount -> Numeric,
+        gini -> Numeric,
+    }
+}
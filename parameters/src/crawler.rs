@@ -0,0 +1,233 @@
+use std::convert::identity;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::NaiveDateTime;
+use deadpool_diesel::postgres::{Object, Pool};
+use namada_sdk::state::EPOCH_SWITCH_BLOCKS_DELAY;
+use namada_sdk::time::{DateTimeUtc, Utc};
+use orm::gas::GasPriceDb;
+use orm::parameters::ParametersInsertDb;
+use orm::token::TokenSupplyInsertDb;
+use shared::block::Epoch;
+use shared::crawler;
+use shared::crawler_state::{CrawlerName, IntervalCrawlerState};
+use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
+use shared::id::Id;
+use tendermint_rpc::HttpClient;
+use tokio::sync::{Mutex, MutexGuard};
+use tokio::time::Instant;
+
+use crate::repository;
+use crate::services::{
+    namada as namada_service, tendermint as tendermint_service,
+};
+
+/// Crawls every `sleep_for` seconds, the database behind `pool` must be
+/// migrated
+pub async fn run(
+    client: Arc<HttpClient>,
+    pool: Pool,
+    sleep_for: u64,
+) -> Result<(), MainError> {
+    // Initially set the instant to the current time minus the sleep_for
+    // so we can start processing right away
+    let instant = Arc::new(Mutex::new(
+        Instant::now()
+            .checked_sub(Duration::from_secs(sleep_for))
+            .unwrap(),
+    ));
+    let distribution_epoch = Arc::new(Mutex::new(None));
+
+    crawler::crawl(
+        move |_| {
+            crawling_fn(
+                pool.clone(),
+                client.clone(),
+                instant.clone(),
+                distribution_epoch.clone(),
+                sleep_for,
+            )
+        },
+        0,
+        None,
+    )
+    .await
+}
+
+async fn crawling_fn(
+    pool: Pool,
+    client: Arc<HttpClient>,
+    instant: Arc<Mutex<Instant>>,
+    distribution_epoch: Arc<Mutex<Option<Epoch>>>,
+    sleep_for: u64,
+) -> Result<(), MainError> {
+    let conn = get_db_connection(&pool).await?;
+
+    let mut instant = instant.lock().await;
+
+    let should_process = can_process(&instant, sleep_for);
+
+    if !should_process {
+        let timestamp = Utc::now().naive_utc();
+        update_crawler_timestamp(&conn, timestamp).await?;
+
+        tracing::warn!(
+            "Not enough time has passed since last crawl, skipping..."
+        );
+
+        return Err(MainError::NoAction);
+    }
+
+    let parameters = namada_service::get_parameters(&client)
+        .await
+        .into_rpc_error()?;
+
+    let genesis = tendermint_service::query_genesis(&client)
+        .await
+        .into_rpc_error()?;
+
+    let checksums = namada_service::query_checksums(&client).await;
+
+    let gas_price = namada_service::get_gas_price(&client).await;
+
+    let epoch = namada_service::get_current_epoch(&client)
+        .await
+        .into_rpc_error()?;
+
+    let token_addresses = conn
+        .interact(repository::token::get_token_addresses)
+        .await
+        .context_db_interact_error()
+        .and_then(identity)
+        .into_db_error()?
+        .into_iter()
+        .map(Id::Account)
+        .collect();
+
+    let native_token = Id::Account(parameters.native_token_address.clone());
+    let token_supplies = namada_service::get_token_supplies(
+        &client,
+        token_addresses,
+        &native_token,
+        epoch,
+    )
+    .await
+    .into_rpc_error()?;
+    tracing::info!(
+        "Updating supply for {} tokens at epoch {}",
+        token_supplies.len(),
+        epoch
+    );
+
+    let internal_addresses = namada_service::get_internal_addresses()
+        .into_iter()
+        .map(|address| address.to_string())
+        .collect();
+
+    let timestamp = DateTimeUtc::now().0.timestamp();
+    let crawler_state = IntervalCrawlerState { timestamp };
+
+    conn.interact(move |conn| {
+        conn.build_transaction()
+            .read_write()
+            .run(|transaction_conn| {
+                repository::parameters::upsert_chain_parameters(
+                    transaction_conn,
+                    ParametersInsertDb::from((
+                        parameters,
+                        genesis,
+                        checksums,
+                        EPOCH_SWITCH_BLOCKS_DELAY,
+                    )),
+                )?;
+
+                repository::parameters::upsert_gas_price(
+                    transaction_conn,
+                    gas_price
+                        .iter()
+                        .cloned()
+                        .map(GasPriceDb::from)
+                        .collect::<Vec<GasPriceDb>>(),
+                )?;
+
+                repository::token::insert_internal_addresses(
+                    transaction_conn,
+                    internal_addresses,
+                )?;
+
+                repository::token::upsert_token_supplies(
+                    transaction_conn,
+                    token_supplies
+                        .into_iter()
+                        .map(TokenSupplyInsertDb::from)
+                        .collect(),
+                )?;
+
+                repository::crawler_state::upsert_crawler_state(
+                    transaction_conn,
+                    (CrawlerName::Parameters, crawler_state).into(),
+                )?;
+
+                anyhow::Ok(())
+            })
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()?;
+
+    // Rebuilding the token distribution is expensive, so it's only done once
+    // per epoch
+    let mut distribution_epoch = distribution_epoch.lock().await;
+    if *distribution_epoch != Some(epoch) {
+        tracing::info!("Refreshing token distribution at epoch {}", epoch);
+
+        conn.interact(repository::token::refresh_token_distribution)
+            .await
+            .context_db_interact_error()
+            .and_then(identity)
+            .into_db_error()?;
+
+        *distribution_epoch = Some(epoch);
+    }
+
+    // Once we are done processing, we reset the instant
+    *instant = Instant::now();
+
+    Ok(())
+}
+
+fn can_process(instant: &MutexGuard<Instant>, sleep_for: u64) -> bool {
+    tracing::info!("Attempting to process parameters data");
+
+    let time_elapsed = instant.elapsed().as_secs();
+    time_elapsed >= sleep_for
+}
+
+async fn update_crawler_timestamp(
+    conn: &Object,
+    timestamp: NaiveDateTime,
+) -> Result<(), MainError> {
+    conn.interact(move |transaction_conn| {
+        repository::crawler_state::update_timestamp(
+            transaction_conn,
+            timestamp,
+        )?;
+
+        anyhow::Ok(())
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()
+}
+
+/// Connections are only held for the crawl being processed
+async fn get_db_connection(pool: &Pool) -> Result<Object, MainError> {
+    pool.get()
+        .await
+        .context("Failed to get db connection handle from deadpool")
+        .into_db_error()
+}
@@ -0,0 +1,188 @@
+use anyhow::Context;
+use diesel::upsert::excluded;
+use diesel::{
+    Connection, ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl,
+};
+use orm::schema::{internal_addresses, token, token_supplies_per_epoch};
+use orm::token::TokenSupplyInsertDb;
+
+pub fn get_token_addresses(
+    conn: &mut PgConnection,
+) -> anyhow::Result<Vec<String>> {
+    token::table
+        .select(token::address)
+        .load::<String>(conn)
+        .context("Failed to query tokens from db")
+}
+
+pub fn insert_internal_addresses(
+    transaction_conn: &mut PgConnection,
+    addresses: Vec<String>,
+) -> anyhow::Result<()> {
+    diesel::insert_into(internal_addresses::table)
+        .values(
+            addresses
+                .into_iter()
+                .map(|address| internal_addresses::address.eq(address))
+                .collect::<Vec<_>>(),
+        )
+        .on_conflict_do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert internal addresses in db")?;
+
+    anyhow::Ok(())
+}
+
+/// Recomputes the holders of every token and their statistics. The views are
+/// refreshed concurrently so that readers are not blocked while they're
+/// rebuilt, and in one transaction so that they always match.
+pub fn refresh_token_distribution(
+    conn: &mut PgConnection,
+) -> anyhow::Result<()> {
+    conn.build_transaction()
+        .read_write()
+        .run(|transaction_conn| {
+            diesel::sql_query(
+                "REFRESH MATERIALIZED VIEW CONCURRENTLY token_holders",
+            )
+            .execute(transaction_conn)
+            .context("Failed to refresh token holders")?;
+
+            diesel::sql_query(
+                "REFRESH MATERIALIZED VIEW CONCURRENTLY token_distribution",
+            )
+            .execute(transaction_conn)
+            .context("Failed to refresh token distribution")?;
+
+            anyhow::Ok(())
+        })
+}
+
+pub fn upsert_token_supplies(
+    transaction_conn: &mut PgConnection,
+    supplies: Vec<TokenSupplyInsertDb>,
+) -> anyhow::Result<()> {
+    if supplies.is_empty() {
+        return anyhow::Ok(());
+    }
+
+    diesel::insert_into(token_supplies_per_epoch::table)
+        .values::<&Vec<TokenSupplyInsertDb>>(&supplies)
+        .on_conflict((
+            token_supplies_per_epoch::address,
+            token_supplies_per_epoch::epoch,
+        ))
+        .do_update()
+        .set((
+            token_supplies_per_epoch::total
+                .eq(excluded(token_supplies_per_epoch::total)),
+            token_supplies_per_epoch::effective
+                .eq(excluded(token_supplies_per_epoch::effective)),
+            token_supplies_per_epoch::bonded
+                .eq(excluded(token_supplies_per_epoch::bonded)),
+            token_supplies_per_epoch::pgf
+                .eq(excluded(token_supplies_per_epoch::pgf)),
+            token_supplies_per_epoch::masp
+                .eq(excluded(token_supplies_per_epoch::masp)),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to update token supplies in db")?;
+
+    anyhow::Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bigdecimal::BigDecimal;
+    use diesel::SelectableHelper;
+    use orm::schema::balances;
+    use orm::token::{TokenDistributionDb, TokenTypeDb};
+    use orm::views::token_distribution;
+    use test_helpers::db::TestDb;
+
+    use super::*;
+
+    /// Test the gini coefficient and top holders of a known distribution,
+    /// with and without internal addresses.
+    #[tokio::test]
+    async fn test_refresh_token_distribution() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            diesel::insert_into(token::table)
+                .values((
+                    token::address.eq("tnam1token"),
+                    token::token_type.eq(TokenTypeDb::Native),
+                ))
+                .execute(conn)?;
+            insert_internal_addresses(conn, vec!["tnam1internal".to_string()])?;
+
+            let holders = [
+                ("tnam1a", 1),
+                ("tnam1b", 2),
+                ("tnam1c", 3),
+                ("tnam1d", 4),
+                ("tnam1internal", 10),
+                ("tnam1empty", 0),
+            ];
+            diesel::insert_into(balances::table)
+                .values(
+                    holders
+                        .iter()
+                        .map(|(owner, amount)| {
+                            (
+                                balances::owner.eq(*owner),
+                                balances::token.eq("tnam1token"),
+                                balances::raw_amount
+                                    .eq(BigDecimal::from(*amount)),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)?;
+
+            refresh_token_distribution(conn)?;
+
+            let distributions: Vec<TokenDistributionDb> =
+                token_distribution::table
+                    .order(token_distribution::exclude_internal)
+                    .select(TokenDistributionDb::as_select())
+                    .load(conn)?;
+
+            let summary = distributions
+                .iter()
+                .map(|distribution| {
+                    (
+                        distribution.exclude_internal,
+                        distribution.holders_count,
+                        distribution.total.clone(),
+                        distribution.gini.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            // (2 * 80) / (5 * 20) - 6 / 5 with the internal address and
+            // (2 * 30) / (4 * 10) - 5 / 4 without it
+            assert_eq!(
+                summary,
+                vec![
+                    (
+                        false,
+                        5,
+                        BigDecimal::from(20),
+                        "0.4".parse::<BigDecimal>()?
+                    ),
+                    (
+                        true,
+                        4,
+                        BigDecimal::from(10),
+                        "0.25".parse::<BigDecimal>()?
+                    ),
+                ]
+            );
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+}
@@ -3,7 +3,9 @@ use std::collections::BTreeMap;
 use anyhow::Context;
 use namada_core::chain::Epoch as NamadaEpoch;
 use namada_parameters::EpochDuration;
-use namada_sdk::address::Address as NamadaAddress;
+use namada_sdk::address::{
+    Address as NamadaAddress, ETH_BRIDGE, GOV, IBC, MASP, PARAMETERS, PGF, POS,
+};
 use namada_sdk::arith::checked;
 use namada_sdk::dec::Dec;
 use namada_sdk::hash::Hash;
@@ -18,7 +20,9 @@ use shared::balance::Amount;
 use shared::block::Epoch;
 use shared::checksums::Checksums;
 use shared::gas::GasPrice;
+use shared::id::Id;
 use shared::parameters::Parameters;
+use shared::token::TokenSupply;
 use tendermint_rpc::HttpClient;
 
 async fn query_tx_code_hash(
@@ -97,6 +101,8 @@ pub async fn get_parameters(client: &HttpClient) -> anyhow::Result<Parameters> {
         max_block_time: max_block_time.0,
         apr: apr.to_string(),
         native_token_address: native_token_address.to_string(),
+        cubic_slashing_window_length: pos_parameters
+            .cubic_slashing_window_length,
     })
 }
 
@@ -129,6 +135,88 @@ pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
     Ok(epoch.0 as Epoch)
 }
 
+/// Addresses of the protocol accounts that can hold tokens
+pub fn get_internal_addresses() -> Vec<Id> {
+    [PARAMETERS, POS, GOV, IBC, ETH_BRIDGE, PGF, MASP]
+        .into_iter()
+        .map(Id::from)
+        .collect()
+}
+
+pub async fn get_token_supplies(
+    client: &HttpClient,
+    tokens: Vec<Id>,
+    native_token: &Id,
+    epoch: Epoch,
+) -> anyhow::Result<Vec<TokenSupply>> {
+    let bonded_tokens =
+        get_total_staked_tokens(client, NamadaEpoch(epoch as u64))
+            .await
+            .context("Failed to query total staked tokens")?;
+
+    let mut supplies = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        let token_address = NamadaAddress::from(token.clone());
+
+        let total = get_token_total_supply(client, &token_address)
+            .await
+            .with_context(|| format!("Failed to query supply of {}", token))?;
+        let pgf = rpc::get_token_balance(client, &token_address, &PGF, None)
+            .await
+            .with_context(|| {
+                format!("Failed to query PGF balance of {}", token)
+            })?;
+        let masp = rpc::get_token_balance(client, &token_address, &MASP, None)
+            .await
+            .with_context(|| {
+                format!("Failed to query MASP balance of {}", token)
+            })?;
+
+        supplies.push(to_token_supply(
+            token,
+            native_token,
+            epoch,
+            total,
+            pgf,
+            masp,
+            bonded_tokens,
+        ));
+    }
+
+    Ok(supplies)
+}
+
+fn to_token_supply(
+    token: Id,
+    native_token: &Id,
+    epoch: Epoch,
+    total: NamadaSdkAmount,
+    pgf: NamadaSdkAmount,
+    masp: NamadaSdkAmount,
+    bonded_tokens: NamadaSdkAmount,
+) -> TokenSupply {
+    // Only the native token can be bonded
+    let bonded = if token == *native_token {
+        bonded_tokens
+    } else {
+        NamadaSdkAmount::zero()
+    };
+
+    // Tokens held by the PGF account are not circulating
+    let effective = total.checked_sub(pgf).unwrap_or_default();
+
+    TokenSupply {
+        address: token,
+        epoch,
+        total: Amount::from(total),
+        effective: Amount::from(effective),
+        bonded: Amount::from(bonded),
+        pgf: Amount::from(pgf),
+        masp: Amount::from(masp),
+    }
+}
+
 async fn _calc_apr(
     client: &HttpClient,
     epoch: NamadaEpoch,
@@ -173,3 +261,56 @@ async fn _calc_apr(
 
     Ok(apr_nom.to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the effective supply leaves out the PGF balance and that
+    /// only the native token reports bonded tokens.
+    #[test]
+    fn test_to_token_supply() {
+        let native_token = Id::Account("tnam1native".to_string());
+        let ibc_token = Id::Account("tnam1ibc".to_string());
+        let amount = NamadaSdkAmount::from_u64;
+
+        let native = to_token_supply(
+            native_token.clone(),
+            &native_token,
+            3,
+            amount(1000),
+            amount(300),
+            amount(50),
+            amount(400),
+        );
+        assert_eq!(native.total, Amount::from(amount(1000)));
+        assert_eq!(native.effective, Amount::from(amount(700)));
+        assert_eq!(native.bonded, Amount::from(amount(400)));
+        assert_eq!(native.pgf, Amount::from(amount(300)));
+        assert_eq!(native.masp, Amount::from(amount(50)));
+
+        let ibc = to_token_supply(
+            ibc_token,
+            &native_token,
+            3,
+            amount(100),
+            amount(0),
+            amount(100),
+            amount(400),
+        );
+        assert_eq!(ibc.effective, Amount::from(amount(100)));
+        assert!(ibc.bonded.is_zero());
+
+        // A PGF balance above the total supply can't make it negative
+        let underflow = to_token_supply(
+            native_token.clone(),
+            &native_token,
+            3,
+            amount(100),
+            amount(200),
+            amount(0),
+            amount(0),
+        );
+        assert!(underflow.effective.is_zero());
+    }
+}
@@ -26,6 +26,10 @@ impl AppState {
         Ok(Self { db: pool })
     }
 
+    pub fn pool(&self) -> DbPool {
+        self.db.clone()
+    }
+
     pub async fn get_db_connection(&self) -> anyhow::Result<Object> {
         self.db
             .get()
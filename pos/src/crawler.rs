@@ -0,0 +1,177 @@
+use std::convert::identity;
+use std::sync::Arc;
+
+use anyhow::Context;
+use chrono::{NaiveDateTime, Utc};
+use deadpool_diesel::postgres::{Object, Pool};
+use namada_sdk::time::DateTimeUtc;
+use orm::crawler_state::EpochStateInsertDb;
+use orm::notifications::{self, IndexerEvent};
+use orm::validators::ValidatorInsertDb;
+use shared::crawler;
+use shared::crawler_state::{CrawlerName, EpochCrawlerState};
+use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
+use tendermint_rpc::HttpClient;
+
+use crate::repository::{self};
+use crate::services::namada as namada_service;
+
+/// Crawls epochs from the current one, the database behind `pool` must be
+/// migrated
+pub async fn run(client: Arc<HttpClient>, pool: Pool) -> Result<(), MainError> {
+    // We always start from the current epoch
+    let next_epoch = namada_service::get_current_epoch(&client.clone())
+        .await
+        .into_rpc_error()?;
+
+    crawler::crawl(
+        move |epoch| crawling_fn(epoch, pool.clone(), client.clone()),
+        next_epoch,
+        None,
+    )
+    .await
+}
+
+async fn crawling_fn(
+    epoch_to_process: u32,
+    pool: Pool,
+    client: Arc<HttpClient>,
+) -> Result<(), MainError> {
+    let conn = get_db_connection(&pool).await?;
+
+    let should_process = can_process(epoch_to_process, client.clone()).await?;
+
+    if !should_process {
+        let timestamp = Utc::now().naive_utc();
+        update_crawler_timestamp(&conn, timestamp).await?;
+
+        tracing::warn!(
+            "Epoch {} was not processed, retry...",
+            epoch_to_process
+        );
+
+        return Err(MainError::NoAction);
+    }
+
+    let validators_set =
+        namada_service::get_validator_set_at_epoch(&client, epoch_to_process)
+            .await
+            .into_rpc_error()?;
+
+    let slashes = namada_service::get_slashes(&client)
+        .await
+        .into_rpc_error()?;
+
+    tracing::info!(
+        "Processing epoch {} with {} validators...",
+        epoch_to_process,
+        validators_set.validators.len()
+    );
+
+    let timestamp = DateTimeUtc::now().0.timestamp();
+    let crawler_state = EpochCrawlerState {
+        last_processed_epoch: epoch_to_process,
+        timestamp,
+    };
+    let crawler_state: EpochStateInsertDb =
+        (CrawlerName::Pos, crawler_state).into();
+
+    conn.interact(move |conn| {
+        conn.build_transaction()
+            .read_write()
+            .run(|transaction_conn| {
+                let validators_dbo = &validators_set
+                    .validators
+                    .into_iter()
+                    .map(ValidatorInsertDb::from_validator)
+                    .collect::<Vec<_>>();
+
+                let previous_set = repository::pos::get_validator_set(
+                    transaction_conn,
+                    validators_dbo
+                        .iter()
+                        .map(|validator| validator.namada_address.clone())
+                        .collect(),
+                )?;
+                let set_changed = validators_dbo.iter().any(|validator| {
+                    previous_set.get(&validator.namada_address)
+                        != Some(&(
+                            validator.voting_power,
+                            validator.state.clone(),
+                        ))
+                });
+                let events = if set_changed {
+                    vec![IndexerEvent::ValidatorSet {
+                        epoch: epoch_to_process as i32,
+                        validators: validators_dbo.len(),
+                    }]
+                } else {
+                    vec![]
+                };
+
+                repository::pos::upsert_validators(
+                    transaction_conn,
+                    validators_dbo,
+                )?;
+
+                repository::pos::upsert_slashes(transaction_conn, slashes)?;
+
+                repository::crawler_state::upsert_crawler_state(
+                    transaction_conn,
+                    crawler_state,
+                )?;
+
+                notifications::notify(transaction_conn, events);
+
+                anyhow::Ok(())
+            })
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()
+}
+
+async fn can_process(
+    epoch: u32,
+    client: Arc<HttpClient>,
+) -> Result<bool, MainError> {
+    tracing::info!("Attempting to process epoch: {}...", epoch);
+    let current_epoch = namada_service::get_current_epoch(&client.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to query Namada's last committed block: {}",
+                e
+            );
+            MainError::RpcError
+        })?;
+
+    Ok(current_epoch >= epoch)
+}
+
+async fn update_crawler_timestamp(
+    conn: &Object,
+    timestamp: NaiveDateTime,
+) -> Result<(), MainError> {
+    conn.interact(move |transaction_conn| {
+        repository::crawler_state::update_timestamp(
+            transaction_conn,
+            timestamp,
+        )?;
+
+        anyhow::Ok(())
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()
+}
+
+/// Connections are only held for the epoch being processed
+async fn get_db_connection(pool: &Pool) -> Result<Object, MainError> {
+    pool.get()
+        .await
+        .context("Failed to get db connection handle from deadpool")
+        .into_db_error()
+}
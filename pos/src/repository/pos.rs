@@ -1,8 +1,37 @@
+use std::collections::{HashMap, HashSet};
+
 use anyhow::Context;
 use diesel::upsert::excluded;
-use diesel::{ExpressionMethods, PgConnection, RunQueryDsl};
-use orm::schema::validators;
-use orm::validators::ValidatorInsertDb;
+use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
+use orm::schema::{slashes, validator_consensus_addresses, validators};
+use orm::slashes::SlashInsertDb;
+use orm::validators::{ValidatorInsertDb, ValidatorStateDb};
+use shared::slash::Slashes;
+
+/// Voting power and state of the given validators, by address
+pub fn get_validator_set(
+    transaction_conn: &mut PgConnection,
+    addresses: Vec<String>,
+) -> anyhow::Result<HashMap<String, (i32, ValidatorStateDb)>> {
+    let validators = validators::table
+        .filter(validators::namada_address.eq_any(addresses))
+        .select((
+            validators::namada_address,
+            validators::voting_power,
+            validators::state,
+        ))
+        .load::<(String, i32, ValidatorStateDb)>(transaction_conn)
+        .context("Failed to query validator set")?;
+
+    anyhow::Ok(
+        validators
+            .into_iter()
+            .map(|(address, voting_power, state)| {
+                (address, (voting_power, state))
+            })
+            .collect(),
+    )
+}
 
 pub fn upsert_validators(
     transaction_conn: &mut PgConnection,
@@ -29,9 +58,76 @@ pub fn upsert_validators(
             validators::columns::avatar
                 .eq(excluded(validators::columns::avatar)),
             validators::columns::state.eq(excluded(validators::columns::state)),
+            validators::columns::consensus_address
+                .eq(excluded(validators::columns::consensus_address)),
+            validators::columns::pending_commission
+                .eq(excluded(validators::columns::pending_commission)),
         ))
         .execute(transaction_conn)
         .context("Failed to update validators in db")?;
 
+    // Previous consensus addresses are kept, blocks signed before a key
+    // rotation still belong to the validator
+    diesel::insert_into(validator_consensus_addresses::table)
+        .values(
+            validators::table
+                .filter(validators::consensus_address.is_not_null())
+                .select((
+                    validators::consensus_address.assume_not_null(),
+                    validators::id,
+                )),
+        )
+        .into_columns((
+            validator_consensus_addresses::consensus_address,
+            validator_consensus_addresses::validator_id,
+        ))
+        .on_conflict_do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to update validator consensus addresses in db")?;
+
     Ok(())
 }
+
+pub fn upsert_slashes(
+    transaction_conn: &mut PgConnection,
+    slashes: Slashes,
+) -> anyhow::Result<()> {
+    let addresses = slashes
+        .iter()
+        .map(|slash| slash.validator.to_string())
+        .collect::<HashSet<_>>();
+
+    let validator_ids: HashMap<String, i32> = validators::table
+        .filter(validators::namada_address.eq_any(addresses))
+        .select((validators::namada_address, validators::id))
+        .load::<(String, i32)>(transaction_conn)
+        .context("Failed to query validators by address")?
+        .into_iter()
+        .collect();
+
+    let slashes_db = slashes
+        .into_iter()
+        .filter_map(|slash| {
+            let validator_id =
+                *validator_ids.get(&slash.validator.to_string())?;
+            Some(SlashInsertDb::from_slash(slash, validator_id))
+        })
+        .collect::<Vec<_>>();
+
+    diesel::insert_into(slashes::table)
+        .values::<&Vec<SlashInsertDb>>(&slashes_db)
+        .on_conflict((
+            slashes::columns::validator_id,
+            slashes::columns::slash_type,
+            slashes::columns::block_height,
+        ))
+        .do_update()
+        .set((
+            slashes::columns::epoch.eq(excluded(slashes::columns::epoch)),
+            slashes::columns::rate.eq(excluded(slashes::columns::rate)),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to upsert slashes in db")?;
+
+    anyhow::Ok(())
+}
@@ -1,9 +1,12 @@
 use anyhow::Context;
 use futures::{StreamExt, TryStreamExt};
 use namada_core::chain::Epoch as NamadaSdkEpoch;
+use namada_sdk::key::tm_consensus_key_raw_hash;
+use namada_sdk::queries::RPC;
 use namada_sdk::rpc;
 use shared::block::Epoch;
 use shared::id::Id;
+use shared::slash::{Slash, Slashes};
 use shared::validator::{Validator, ValidatorSet, ValidatorState};
 use tendermint_rpc::HttpClient;
 
@@ -21,6 +24,11 @@ pub async fn get_validator_set_at_epoch(
             )
         })?;
 
+    let pos_params = rpc::get_pos_params(client)
+        .await
+        .context("Failed to query Namada's PoS parameters")?;
+    let pipeline_epoch = namada_epoch + pos_params.pipeline_len;
+
     let validators = futures::stream::iter(validator_set)
         .map(|address| async move {
             let voting_power_fut = async {
@@ -45,6 +53,17 @@ pub async fn get_validator_set_at_epoch(
                     })
             };
 
+            let pipeline_commission_fut = async {
+                rpc::query_commission_rate(client, &address, Some(pipeline_epoch))
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Failed to query commission of validator \
+                             {address} at epoch {pipeline_epoch}"
+                        )
+                    })
+            };
+
             let validator_state_fut = async {
                 rpc::get_validator_state(client, &address, Some(namada_epoch))
                     .await
@@ -56,8 +75,21 @@ pub async fn get_validator_set_at_epoch(
                     })
             };
 
-            let (voting_power, commission_pair, validator_state) =
-                futures::try_join!(voting_power_fut, commission_fut, validator_state_fut)?;
+            let consensus_key_fut = async {
+                RPC.vp()
+                    .pos()
+                    .consensus_key(client, &address)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Failed to query validator {address} \
+                         consensus key"
+                        )
+                    })
+            };
+
+            let (voting_power, commission_pair, pipeline_commission_pair, validator_state, consensus_key) =
+                futures::try_join!(voting_power_fut, commission_fut, pipeline_commission_fut, validator_state_fut, consensus_key_fut)?;
             let commission = commission_pair
                 .commission_rate
                 .expect("Commission rate has to exist")
@@ -66,7 +98,14 @@ pub async fn get_validator_set_at_epoch(
                 .max_commission_change_per_epoch
                 .expect("Max commission rate change has to exist")
                 .to_string();
+            let pending_commission = pipeline_commission_pair
+                .commission_rate
+                .map(|rate| rate.to_string())
+                .filter(|rate| rate != &commission);
             let validator_state = validator_state.0.map(ValidatorState::from).unwrap_or(ValidatorState::Unknown);
+            // Same format as the proposer address we read from the blocks
+            let consensus_address = consensus_key
+                .map(|key| tm_consensus_key_raw_hash(&key).to_lowercase());
 
             anyhow::Ok(Validator {
                 address: Id::Account(address.to_string()),
@@ -79,7 +118,9 @@ pub async fn get_validator_set_at_epoch(
                 website: None,
                 discord_handler: None,
                 avatar: None,
-                state: validator_state
+                state: validator_state,
+                consensus_address,
+                pending_commission,
             })
         })
         .buffer_unordered(100)
@@ -97,6 +138,26 @@ pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
     Ok(epoch.0 as Epoch)
 }
 
+pub async fn get_slashes(client: &HttpClient) -> anyhow::Result<Slashes> {
+    let slashes = RPC
+        .vp()
+        .pos()
+        .slashes(client)
+        .await
+        .context("Failed to query Namada's slashes")?;
+
+    let slashes = slashes
+        .into_iter()
+        .flat_map(|(address, validator_slashes)| {
+            validator_slashes.into_iter().map(move |slash| {
+                Slash::from_namada(Id::Account(address.to_string()), slash)
+            })
+        })
+        .collect();
+
+    Ok(slashes)
+}
+
 fn to_epoch(epoch: u32) -> NamadaSdkEpoch {
     NamadaSdkEpoch::from(epoch as u64)
 }
@@ -26,6 +26,15 @@ pub struct AppConfig {
     #[clap(long, env)]
     pub database_url: String,
 
+    /// Maximum number of reward queries in flight at once
+    #[clap(long, env, default_value_t = 20)]
+    pub concurrency: usize,
+
+    /// Share of the epoch duration the reward queries are spread across, 0
+    /// queries them as fast as possible
+    #[clap(long, env, default_value_t = 0.5)]
+    pub epoch_spread: f64,
+
     #[command(flatten)]
     pub verbosity: Verbosity<InfoLevel>,
 }
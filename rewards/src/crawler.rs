@@ -0,0 +1,269 @@
+use std::collections::{HashMap, HashSet};
+use std::convert::identity;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::NaiveDateTime;
+use deadpool_diesel::postgres::{Object, Pool};
+use namada_sdk::time::{DateTimeUtc, Utc};
+use shared::crawler;
+use shared::crawler_state::{CrawlerName, EpochCrawlerState};
+use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
+use shared::utils::DelegationPair;
+use tendermint_rpc::HttpClient;
+use tokio::time::sleep;
+
+use crate::repository;
+use crate::repository::pos_rewards::DelegationPairState;
+use crate::services::namada as namada_service;
+
+/// Crawls epochs once the chain reached the second one, the database behind
+/// `pool` must be migrated
+pub async fn run(
+    client: Arc<HttpClient>,
+    pool: Pool,
+    sleep_for: u64,
+    concurrency: usize,
+    epoch_spread: f64,
+) -> Result<(), MainError> {
+    tracing::info!("Query epoch...");
+
+    let mut epoch;
+    loop {
+        epoch = namada_service::get_current_epoch(&client)
+            .await
+            .into_rpc_error()?;
+
+        if epoch < 2 {
+            tracing::info!("Waiting for first epoch to happen...");
+            sleep(Duration::from_secs(sleep_for)).await;
+        } else {
+            break;
+        }
+    }
+
+    // Delegation pairs refreshed so far, with the state they were in. Starting
+    // empty means every pair gets refreshed once after a restart.
+    let refreshed_pairs = Arc::new(Mutex::new(HashMap::new()));
+
+    crawler::crawl(
+        move |epoch| {
+            crawling_fn(
+                pool.clone(),
+                client.clone(),
+                refreshed_pairs.clone(),
+                concurrency,
+                epoch_spread,
+                epoch,
+            )
+        },
+        epoch,
+        None,
+    )
+    .await
+}
+
+async fn crawling_fn(
+    pool: Pool,
+    client: Arc<HttpClient>,
+    refreshed_pairs: Arc<Mutex<HashMap<DelegationPair, DelegationPairState>>>,
+    concurrency: usize,
+    epoch_spread: f64,
+    epoch_to_process: u32,
+) -> Result<(), MainError> {
+    let should_process = can_process(epoch_to_process, client.clone()).await?;
+
+    if !should_process {
+        let timestamp = Utc::now().naive_utc();
+        update_crawler_timestamp(&pool, timestamp).await?;
+
+        tracing::warn!(
+            "Epoch {} was not processed, retry...",
+            epoch_to_process
+        );
+
+        return Err(MainError::NoAction);
+    }
+
+    tracing::info!("Starting to update proposals...");
+
+    let (delegation_pairs, min_epoch_duration) = get_db_connection(&pool)
+        .await?
+        .interact(|conn| {
+            let delegation_pairs =
+                repository::pos_rewards::query_delegation_pairs(conn)?;
+            let min_epoch_duration =
+                repository::parameters::get_min_epoch_duration(conn)?;
+
+            anyhow::Ok((delegation_pairs, min_epoch_duration))
+        })
+        .await
+        .context_db_interact_error()
+        .and_then(identity)
+        .into_db_error()?;
+
+    // Validators in the consensus set earn rewards every epoch, the other
+    // pairs only need a refresh when their stake or validator changed
+    let pairs_to_refresh = {
+        let refreshed_pairs = refreshed_pairs.lock().unwrap();
+        delegation_pairs
+            .iter()
+            .filter(|(pair, state)| {
+                state.validator_in_consensus
+                    || refreshed_pairs.get(*pair) != Some(*state)
+            })
+            .map(|(pair, _)| pair.clone())
+            .collect::<HashSet<_>>()
+    };
+
+    tracing::info!(
+        "Refreshing rewards of {} out of {} delegation pairs...",
+        pairs_to_refresh.len(),
+        delegation_pairs.len()
+    );
+
+    let spread = min_epoch_duration
+        .map(|duration| {
+            Duration::from_secs_f64(
+                duration.max(0) as f64 * epoch_spread.clamp(0.0, 1.0),
+            )
+        })
+        .unwrap_or_default();
+
+    // Every pair is queried at the same height, so the rewards recorded for
+    // the epoch don't depend on when in the spread they were queried
+    let block_height =
+        namada_service::get_first_block_in_epoch(&client, epoch_to_process)
+            .await
+            .into_rpc_error()?;
+
+    let rewards = namada_service::query_rewards(
+        &client,
+        pairs_to_refresh.clone(),
+        block_height,
+        concurrency,
+        spread,
+    )
+    .await
+    .into_rpc_error()?;
+
+    // Pairs whose query failed are retried at the next epoch
+    let fetched_pairs = rewards
+        .iter()
+        .map(|reward| reward.delegation_pair.clone())
+        .collect::<HashSet<_>>();
+    let failed_pairs = pairs_to_refresh
+        .difference(&fetched_pairs)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // The other pairs keep the rewards they had at the previous epoch
+    let unchanged_pairs = delegation_pairs
+        .keys()
+        .filter(|pair| !pairs_to_refresh.contains(*pair))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let non_zero_rewards: Vec<_> = rewards
+        .into_iter()
+        .filter(|reward| !reward.amount.is_zero())
+        .collect();
+
+    let timestamp = DateTimeUtc::now().0.timestamp();
+    let crawler_state = EpochCrawlerState {
+        last_processed_epoch: epoch_to_process,
+        timestamp,
+    };
+
+    let conn = get_db_connection(&pool).await?;
+
+    conn.interact(move |conn| {
+        conn.build_transaction().read_write().run(
+            |transaction_conn: &mut diesel::prelude::PgConnection| {
+                repository::pos_rewards::upsert_rewards(
+                    transaction_conn,
+                    non_zero_rewards.clone(),
+                )?;
+
+                repository::pos_rewards::upsert_rewards_history(
+                    transaction_conn,
+                    non_zero_rewards,
+                    epoch_to_process,
+                )?;
+
+                repository::pos_rewards::carry_forward_rewards_history(
+                    transaction_conn,
+                    unchanged_pairs,
+                    epoch_to_process,
+                )?;
+
+                repository::crawler_state::upsert_crawler_state(
+                    transaction_conn,
+                    (CrawlerName::Rewards, crawler_state).into(),
+                )?;
+
+                anyhow::Ok(())
+            },
+        )
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()?;
+
+    let mut refreshed_pairs = refreshed_pairs.lock().unwrap();
+    *refreshed_pairs = delegation_pairs;
+    for pair in failed_pairs {
+        refreshed_pairs.remove(&pair);
+    }
+
+    Ok(())
+}
+
+async fn can_process(
+    epoch: u32,
+    client: Arc<HttpClient>,
+) -> Result<bool, MainError> {
+    tracing::info!("Attempting to process epoch: {}...", epoch);
+    let current_epoch = namada_service::get_current_epoch(&client.clone())
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to query Namada's last committed block: {}",
+                e
+            );
+            MainError::RpcError
+        })?;
+
+    Ok(current_epoch >= epoch)
+}
+
+async fn update_crawler_timestamp(
+    pool: &Pool,
+    timestamp: NaiveDateTime,
+) -> Result<(), MainError> {
+    let conn = get_db_connection(pool).await?;
+
+    conn.interact(move |transaction_conn| {
+        repository::crawler_state::update_timestamp(
+            transaction_conn,
+            timestamp,
+        )?;
+
+        anyhow::Ok(())
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()
+}
+
+/// Connections are only held for a read or a write, never across the rewards
+/// queries which can be spread over most of the epoch
+async fn get_db_connection(pool: &Pool) -> Result<Object, MainError> {
+    pool.get()
+        .await
+        .context("Failed to get db connection handle from deadpool")
+        .into_db_error()
+}
@@ -1,4 +1,5 @@
 pub mod config;
+pub mod crawler;
 pub mod repository;
 pub mod services;
 pub mod state;
@@ -2,19 +2,23 @@ use anyhow::Context;
 use chrono::NaiveDateTime;
 use diesel::upsert::excluded;
 use diesel::{ExpressionMethods, PgConnection, RunQueryDsl};
-use orm::crawler_state::{CrawlerNameDb, IntervalStateInsertDb};
+use orm::crawler_state::{CrawlerNameDb, EpochStateInsertDb};
 use orm::schema::crawler_state;
 use shared::crawler_state::CrawlerName;
 
 pub fn upsert_crawler_state(
     transaction_conn: &mut PgConnection,
-    status: IntervalStateInsertDb,
+    status: EpochStateInsertDb,
 ) -> anyhow::Result<()> {
     diesel::insert_into(crawler_state::table)
-        .values::<&IntervalStateInsertDb>(&status)
+        .values::<&EpochStateInsertDb>(&status)
         .on_conflict(crawler_state::name)
         .do_update()
-        .set((crawler_state::timestamp.eq(excluded(crawler_state::timestamp)),))
+        .set((
+            crawler_state::timestamp.eq(excluded(crawler_state::timestamp)),
+            crawler_state::last_processed_epoch
+                .eq(excluded(crawler_state::last_processed_epoch)),
+        ))
         .execute(transaction_conn)
         .context("Failed to update crawler state in db")?;
 
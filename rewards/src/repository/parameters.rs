@@ -0,0 +1,15 @@
+use anyhow::Context;
+use diesel::{OptionalExtension, PgConnection, QueryDsl, RunQueryDsl};
+use orm::schema::chain_parameters;
+
+/// Returns the minimum duration of an epoch in seconds, if the parameters
+/// crawler already indexed the chain parameters
+pub fn get_min_epoch_duration(
+    transaction_conn: &mut PgConnection,
+) -> anyhow::Result<Option<i32>> {
+    chain_parameters::table
+        .select(chain_parameters::min_duration)
+        .first(transaction_conn)
+        .optional()
+        .context("Failed to query chain parameters")
+}
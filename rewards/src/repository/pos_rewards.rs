@@ -1,8 +1,92 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use bigdecimal::BigDecimal;
+use diesel::dsl::sum;
+use diesel::sql_types::Integer;
 use diesel::upsert::excluded;
-use diesel::{ExpressionMethods, PgConnection, QueryDsl, RunQueryDsl};
-use orm::pos_rewards::PosRewardInsertDb;
-use orm::schema::{pos_rewards, validators};
+use diesel::{ExpressionMethods, IntoSql, PgConnection, QueryDsl, RunQueryDsl};
+use orm::pos_rewards::{PosRewardHistoryInsertDb, PosRewardInsertDb};
+use orm::schema::{
+    bonds, pos_rewards, pos_rewards_history, unbonds, validators,
+};
+use orm::validators::ValidatorStateDb;
+use shared::block::Epoch;
+use shared::id::Id;
 use shared::rewards::Reward;
+use shared::utils::DelegationPair;
+
+/// Bonded and unbonded amounts of a delegation pair and state of its
+/// validator, used to tell which pairs might have accrued rewards since they
+/// were last refreshed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DelegationPairState {
+    pub bonds_raw_amount: String,
+    pub unbonds_raw_amount: String,
+    pub validator_voting_power: i32,
+    pub validator_in_consensus: bool,
+}
+
+type DelegationPairRow =
+    (String, String, i32, ValidatorStateDb, Option<BigDecimal>);
+
+/// Pairs with either bonds or unbonds, as both can hold unclaimed rewards
+pub fn query_delegation_pairs(
+    transaction_conn: &mut PgConnection,
+) -> anyhow::Result<HashMap<DelegationPair, DelegationPairState>> {
+    let bond_pairs: Vec<DelegationPairRow> = validators::table
+        .inner_join(bonds::table)
+        .group_by((bonds::address, validators::id))
+        .select((
+            bonds::address,
+            validators::namada_address,
+            validators::voting_power,
+            validators::state,
+            sum(bonds::raw_amount),
+        ))
+        .load(transaction_conn)
+        .context("Failed to query bond delegation pairs")?;
+
+    let unbond_pairs: Vec<DelegationPairRow> = validators::table
+        .inner_join(unbonds::table)
+        .group_by((unbonds::address, validators::id))
+        .select((
+            unbonds::address,
+            validators::namada_address,
+            validators::voting_power,
+            validators::state,
+            sum(unbonds::raw_amount),
+        ))
+        .load(transaction_conn)
+        .context("Failed to query unbond delegation pairs")?;
+
+    let mut pairs = HashMap::new();
+
+    for (is_bond, rows) in [(true, bond_pairs), (false, unbond_pairs)] {
+        for (delegator, validator, voting_power, state, raw_amount) in rows {
+            let pair = DelegationPair {
+                validator_address: Id::Account(validator),
+                delegator_address: Id::Account(delegator),
+            };
+            let pair_state: &mut DelegationPairState =
+                pairs.entry(pair).or_default();
+            pair_state.validator_voting_power = voting_power;
+            pair_state.validator_in_consensus =
+                matches!(state, ValidatorStateDb::Consensus);
+
+            let raw_amount = raw_amount
+                .map(|amount| amount.to_string())
+                .unwrap_or_default();
+            if is_bond {
+                pair_state.bonds_raw_amount = raw_amount;
+            } else {
+                pair_state.unbonds_raw_amount = raw_amount;
+            }
+        }
+    }
+
+    anyhow::Ok(pairs)
+}
 
 pub fn upsert_rewards(
     transaction_conn: &mut PgConnection,
@@ -41,3 +125,158 @@ pub fn upsert_rewards(
 
     Ok(())
 }
+
+/// Records the claimable amount of each delegation pair at the given epoch
+pub fn upsert_rewards_history(
+    transaction_conn: &mut PgConnection,
+    rewards: Vec<Reward>,
+    epoch: Epoch,
+) -> anyhow::Result<()> {
+    diesel::insert_into(pos_rewards_history::table)
+        .values::<Vec<PosRewardHistoryInsertDb>>(
+            rewards
+                .into_iter()
+                .map(|reward| {
+                    let validator_id: i32 = validators::table
+                        .filter(
+                            validators::namada_address.eq(&reward
+                                .delegation_pair
+                                .validator_address
+                                .to_string()),
+                        )
+                        .select(validators::id)
+                        .first(transaction_conn)
+                        .expect("Failed to get validator");
+
+                    PosRewardHistoryInsertDb::from_reward(
+                        reward,
+                        validator_id,
+                        epoch,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+        .on_conflict((
+            pos_rewards_history::columns::owner,
+            pos_rewards_history::columns::validator_id,
+            pos_rewards_history::columns::epoch,
+        ))
+        .do_update()
+        .set(
+            pos_rewards_history::columns::raw_amount
+                .eq(excluded(pos_rewards_history::columns::raw_amount)),
+        )
+        .execute(transaction_conn)
+        .context("Failed to update rewards history in db")?;
+
+    Ok(())
+}
+
+/// Copies the current rewards of pairs that weren't refreshed, as they
+/// couldn't have changed, into the history at the given epoch
+pub fn carry_forward_rewards_history(
+    transaction_conn: &mut PgConnection,
+    delegation_pairs: Vec<DelegationPair>,
+    epoch: Epoch,
+) -> anyhow::Result<()> {
+    let mut delegators_by_validator: HashMap<String, Vec<String>> =
+        HashMap::new();
+    for pair in delegation_pairs {
+        delegators_by_validator
+            .entry(pair.validator_address.to_string())
+            .or_default()
+            .push(pair.delegator_address.to_string());
+    }
+
+    for (validator, delegators) in delegators_by_validator {
+        diesel::insert_into(pos_rewards_history::table)
+            .values(
+                pos_rewards::table
+                    .inner_join(validators::table)
+                    .filter(validators::namada_address.eq(validator))
+                    .filter(pos_rewards::owner.eq_any(delegators))
+                    .select((
+                        pos_rewards::owner,
+                        pos_rewards::validator_id,
+                        pos_rewards::raw_amount,
+                        (epoch as i32).into_sql::<Integer>(),
+                    )),
+            )
+            .into_columns((
+                pos_rewards_history::owner,
+                pos_rewards_history::validator_id,
+                pos_rewards_history::raw_amount,
+                pos_rewards_history::epoch,
+            ))
+            .on_conflict_do_nothing()
+            .execute(transaction_conn)
+            .context("Failed to carry forward rewards history in db")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use diesel::SelectableHelper;
+    use orm::pos_rewards::PoSRewardHistoryDb;
+    use orm::validators::{ValidatorDb, ValidatorInsertDb};
+    use shared::balance::Amount;
+    use shared::validator::Validator;
+    use test_helpers::db::TestDb;
+
+    use super::*;
+
+    /// Test that only the pairs that weren't refreshed get their current
+    /// rewards copied into the history.
+    #[tokio::test]
+    async fn test_carry_forward_rewards_history() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let validator: ValidatorDb = diesel::insert_into(validators::table)
+                .values(ValidatorInsertDb::from_validator(Validator::fake()))
+                .get_result(conn)?;
+
+            let pairs = ["tnam1refreshed", "tnam1unchanged"].map(|owner| {
+                DelegationPair {
+                    validator_address: Id::Account(
+                        validator.namada_address.clone(),
+                    ),
+                    delegator_address: Id::Account(owner.to_string()),
+                }
+            });
+            let rewards = pairs
+                .iter()
+                .map(|pair| Reward {
+                    delegation_pair: pair.clone(),
+                    amount: Amount::from(BigDecimal::from(10)),
+                })
+                .collect::<Vec<_>>();
+            upsert_rewards(conn, rewards.clone())?;
+            upsert_rewards_history(conn, rewards[..1].to_vec(), 5)?;
+
+            carry_forward_rewards_history(conn, pairs[1..].to_vec(), 5)?;
+
+            let history: Vec<PoSRewardHistoryDb> = pos_rewards_history::table
+                .select(PoSRewardHistoryDb::as_select())
+                .order(pos_rewards_history::owner)
+                .load(conn)?;
+
+            assert_eq!(
+                history
+                    .iter()
+                    .map(|row| (row.owner.as_str(), row.epoch))
+                    .collect::<Vec<_>>(),
+                vec![("tnam1refreshed", 5), ("tnam1unchanged", 5)]
+            );
+            assert!(history
+                .iter()
+                .all(|row| row.raw_amount == BigDecimal::from(10)));
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+}
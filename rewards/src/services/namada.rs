@@ -1,75 +1,138 @@
 use std::collections::HashSet;
+use std::time::{Duration, Instant};
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use futures::StreamExt;
-use namada_sdk::queries::RPC;
-use namada_sdk::rpc;
+use namada_core::chain::BlockHeight as NamadaSdkBlockHeight;
+use namada_sdk::queries::{Client as _, RPC};
+use namada_sdk::token::Amount as NamadaSdkAmount;
+use namada_sdk::{borsh, rpc};
 use shared::balance::Amount;
-use shared::block::Epoch;
-use shared::id::Id;
+use shared::block::{BlockHeight, Epoch};
 use shared::rewards::Reward;
 use shared::utils::DelegationPair;
 use tendermint_rpc::HttpClient;
+use tokio::time::sleep;
 
-pub async fn query_delegation_pairs(
-    client: &HttpClient,
-) -> anyhow::Result<HashSet<DelegationPair>> {
-    let data = rpc::bonds_and_unbonds(client, &None, &None)
-        .await
-        .with_context(|| {
-            "Failed to query Namada's bonds and unbonds".to_string()
-        })?;
-
-    let pairs =
-        data.into_iter()
-            .fold(HashSet::new(), |mut acc, (bond_id, _)| {
-                acc.insert(DelegationPair {
-                    validator_address: Id::from(bond_id.validator),
-                    delegator_address: Id::from(bond_id.source),
-                });
-                acc
-            });
-
-    anyhow::Ok(pairs)
-}
-
+/// Queries the rewards of the given delegation pairs at `block_height`,
+/// `concurrency` at a time. The batches of queries are evenly spread over
+/// `spread`, to avoid loading the node with all of them at once.
 pub async fn query_rewards(
     client: &HttpClient,
     delegation_pairs: HashSet<DelegationPair>,
+    block_height: BlockHeight,
+    concurrency: usize,
+    spread: Duration,
 ) -> anyhow::Result<Vec<Reward>> {
-    Ok(futures::stream::iter(delegation_pairs)
-        .filter_map(|delegation| async move {
-            tracing::info!(
-                "Fetching rewards {} -> {} ...",
-                delegation.validator_address,
-                delegation.delegator_address
-            );
-            let reward = RPC
-                .vp()
-                .pos()
-                .rewards(
-                    client,
+    let delegation_pairs = delegation_pairs.into_iter().collect::<Vec<_>>();
+    let batches = delegation_pairs.chunks(concurrency.max(1));
+    let batch_interval =
+        spread.checked_div(batches.len() as u32).unwrap_or_default();
+
+    let mut rewards = Vec::with_capacity(delegation_pairs.len());
+    for batch in batches {
+        let batch_start = Instant::now();
+
+        let batch_rewards = futures::stream::iter(batch.iter().cloned())
+            .map(|delegation| async move {
+                tracing::info!(
+                    "Fetching rewards {} -> {} ...",
+                    delegation.validator_address,
+                    delegation.delegator_address
+                );
+                let path = RPC.vp().pos().rewards_path(
                     &delegation.validator_address.clone().into(),
                     &Some(delegation.delegator_address.clone().into()),
-                )
-                .await
-                .ok()?;
-
-            tracing::info!(
-                "Done fetching reward for {} -> {}!",
-                delegation.validator_address,
-                delegation.delegator_address
-            );
-
-            Some(Reward {
-                delegation_pair: delegation,
-                amount: Amount::from(reward),
+                );
+                let query_result = client
+                    .request(
+                        path,
+                        None,
+                        Some(to_block_height(block_height)),
+                        false,
+                    )
+                    .await
+                    .ok()?;
+                let reward: NamadaSdkAmount =
+                    borsh::BorshDeserialize::try_from_slice(&query_result.data)
+                        .ok()?;
+
+                tracing::info!(
+                    "Done fetching reward for {} -> {}!",
+                    delegation.validator_address,
+                    delegation.delegator_address
+                );
+
+                Some(Reward {
+                    delegation_pair: delegation,
+                    amount: Amount::from(reward),
+                })
             })
-        })
-        .map(futures::future::ready)
-        .buffer_unordered(20)
-        .collect::<Vec<_>>()
-        .await)
+            .buffer_unordered(concurrency.max(1))
+            .filter_map(futures::future::ready)
+            .collect::<Vec<_>>()
+            .await;
+        rewards.extend(batch_rewards);
+
+        if let Some(remaining) =
+            batch_interval.checked_sub(batch_start.elapsed())
+        {
+            sleep(remaining).await;
+        }
+    }
+
+    Ok(rewards)
+}
+
+/// First block of `epoch`, found by bisecting the blocks before the current
+/// epoch when the crawler is catching up
+pub async fn get_first_block_in_epoch(
+    client: &HttpClient,
+    epoch: Epoch,
+) -> anyhow::Result<BlockHeight> {
+    let current_first_block = RPC
+        .shell()
+        .first_block_height_of_current_epoch(client)
+        .await
+        .context("Failed to query the first block of the current epoch")?
+        .0 as BlockHeight;
+
+    let current_epoch =
+        get_epoch_at_block_height(client, current_first_block).await?;
+    if current_epoch <= epoch {
+        return Ok(current_first_block);
+    }
+
+    let (mut low, mut high) = (1, current_first_block);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        if get_epoch_at_block_height(client, middle).await? < epoch {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+
+    Ok(low)
+}
+
+async fn get_epoch_at_block_height(
+    client: &HttpClient,
+    block_height: BlockHeight,
+) -> anyhow::Result<Epoch> {
+    let epoch =
+        rpc::query_epoch_at_height(client, to_block_height(block_height))
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to query Namada's epoch at height {block_height}"
+                )
+            })?
+            .ok_or_else(|| {
+                anyhow!("No Namada epoch found for height {block_height}")
+            })?;
+
+    Ok(epoch.0 as Epoch)
 }
 
 pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
@@ -79,3 +142,7 @@ pub async fn get_current_epoch(client: &HttpClient) -> anyhow::Result<Epoch> {
 
     Ok(epoch.0 as Epoch)
 }
+
+fn to_block_height(block_height: BlockHeight) -> NamadaSdkBlockHeight {
+    NamadaSdkBlockHeight::from(block_height as u64)
+}
@@ -8,8 +8,10 @@ use namada_ibc::IbcMessage;
 use namada_sdk::borsh::BorshDeserialize;
 use namada_sdk::token::Transfer;
 use subtle_encoding::hex;
+use tendermint::block::CommitSig;
 use tendermint_rpc::endpoint::block::Response as TendermintBlockResponse;
 
+use crate::balance::Amount;
 use crate::block_result::BlockResult;
 use crate::bond::BondAddresses;
 use crate::checksums::Checksums;
@@ -17,14 +19,17 @@ use crate::header::BlockHeader;
 use crate::id::Id;
 use crate::proposal::{GovernanceProposal, GovernanceProposalKind};
 use crate::public_key::PublicKey;
+use crate::redelegation::Redelegation;
 use crate::token::{IbcToken, Token};
 use crate::transaction::{
     InnerTransaction, Transaction, TransactionExitStatus, TransactionKind,
     WrapperTransaction,
 };
 use crate::unbond::UnbondAddresses;
-use crate::utils::BalanceChange;
-use crate::validator::ValidatorMetadataChange;
+use crate::utils::{BalanceChange, DelegationPair};
+use crate::validator::{
+    ValidatorBlock, ValidatorBlockKind, ValidatorMetadataChange,
+};
 use crate::vote::GovernanceVote;
 
 pub type Epoch = u32;
@@ -97,12 +102,21 @@ impl TxAttributes {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct LastCommit {
+    pub height: BlockHeight,
+    /// Whether each validator signed the previous block, in the same order
+    /// as the validator set at `height`
+    pub signed: Vec<bool>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Block {
     pub hash: Id,
     pub header: BlockHeader,
     pub transactions: Vec<(WrapperTransaction, Vec<InnerTransaction>)>,
     pub epoch: Epoch,
+    pub last_commit: Option<LastCommit>,
 }
 
 impl Block {
@@ -133,6 +147,23 @@ impl Block {
             })
             .collect::<Vec<(WrapperTransaction, Vec<InnerTransaction>)>>();
 
+        // The first block carries an empty commit at height 0
+        let last_commit = block_response
+            .block
+            .last_commit
+            .as_ref()
+            .filter(|commit| commit.height.value() > 0)
+            .map(|commit| LastCommit {
+                height: commit.height.value() as BlockHeight,
+                signed: commit
+                    .signatures
+                    .iter()
+                    .map(|signature| {
+                        !matches!(signature, CommitSig::BlockIdFlagAbsent)
+                    })
+                    .collect(),
+            });
+
         Block {
             hash: Id::from(block_response.block_id.hash),
             header: BlockHeader {
@@ -149,9 +180,40 @@ impl Block {
             },
             transactions,
             epoch,
+            last_commit,
         }
     }
 
+    /// Returns the block proposer and the signed and missed blocks of the
+    /// last commit. `commit_validator_set` must contain the consensus
+    /// addresses of the validator set at the height of the last commit.
+    pub fn validator_blocks(
+        &self,
+        commit_validator_set: &[String],
+    ) -> Vec<ValidatorBlock> {
+        let proposer = ValidatorBlock {
+            consensus_address: self.header.proposer_address.clone(),
+            height: self.header.height,
+            kind: ValidatorBlockKind::Proposed,
+        };
+
+        let signatures = self.last_commit.iter().flat_map(|commit| {
+            commit_validator_set.iter().zip(&commit.signed).map(
+                |(consensus_address, signed)| ValidatorBlock {
+                    consensus_address: consensus_address.clone(),
+                    height: commit.height,
+                    kind: if *signed {
+                        ValidatorBlockKind::Signed
+                    } else {
+                        ValidatorBlockKind::Missed
+                    },
+                },
+            )
+        });
+
+        std::iter::once(proposer).chain(signatures).collect()
+    }
+
     pub fn inner_txs(&self) -> Vec<InnerTransaction> {
         self.transactions
             .iter()
@@ -166,6 +228,30 @@ impl Block {
             .collect()
     }
 
+    /// Inner transactions along with the addresses they involve: the fee
+    /// payer of their wrapper and the owners of the balances they change
+    pub fn inner_txs_with_addresses(
+        &self,
+        native_token: &Id,
+    ) -> Vec<(InnerTransaction, HashSet<Id>)> {
+        self.transactions
+            .iter()
+            .flat_map(|(wrapper_tx, inner_txs)| {
+                inner_txs.iter().map(|tx| {
+                    let mut addresses = self
+                        .process_inner_tx_for_balance(tx, native_token)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|balance_change| balance_change.address)
+                        .collect::<HashSet<_>>();
+                    addresses.insert(wrapper_tx.fee.gas_payer.clone());
+
+                    (tx.clone(), addresses)
+                })
+            })
+            .collect()
+    }
+
     pub fn governance_proposal(
         &self,
         mut next_proposal_id: u64,
@@ -249,7 +335,7 @@ impl Block {
             .collect()
     }
 
-    pub fn pos_rewards(&self) -> HashSet<Id> {
+    pub fn pos_rewards(&self) -> HashSet<DelegationPair> {
         self.transactions
             .iter()
             .flat_map(|(_, txs)| txs)
@@ -260,9 +346,12 @@ impl Block {
             .filter_map(|tx| match &tx.kind {
                 TransactionKind::ClaimRewards(data) => {
                     let data = data.clone().unwrap();
-                    let source = data.source.unwrap_or(data.validator);
+                    let source = data.source.unwrap_or(data.validator.clone());
 
-                    Some(Id::from(source))
+                    Some(DelegationPair {
+                        validator_address: Id::from(data.validator),
+                        delegator_address: Id::from(source),
+                    })
                 }
                 _ => None,
             })
@@ -573,6 +662,36 @@ impl Block {
             .collect()
     }
 
+    pub fn redelegations(&self) -> Vec<Redelegation> {
+        self.transactions
+            .iter()
+            .flat_map(|(_, txs)| txs)
+            .filter(|tx| {
+                tx.data.is_some()
+                    && tx.exit_code == TransactionExitStatus::Applied
+            })
+            .filter_map(|tx| match &tx.kind {
+                TransactionKind::Redelegation(data) => {
+                    let redelegation_data = data.clone().unwrap();
+
+                    Some(Redelegation {
+                        tx_id: tx.tx_id.clone(),
+                        delegator: Id::from(redelegation_data.owner),
+                        src_validator: Id::from(
+                            redelegation_data.src_validator,
+                        ),
+                        dest_validator: Id::from(
+                            redelegation_data.dest_validator,
+                        ),
+                        amount: Amount::from(redelegation_data.amount),
+                        epoch: self.epoch,
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn withdraw_addresses(&self) -> HashSet<UnbondAddresses> {
         self.transactions
             .iter()
@@ -12,9 +12,10 @@ use tokio_retry::RetryIf;
 
 use crate::error::MainError;
 
+/// Yields `from..=to`, up to `u32::MAX` without a `to`
 fn indexes(from: u32, to: Option<u32>) -> impl Stream<Item = u32> {
     stream! {
-        for i in from..to.unwrap_or(u32::MAX) {
+        for i in from..=to.unwrap_or(u32::MAX) {
             yield i;
         }
     }
@@ -25,12 +26,26 @@ pub async fn crawl<F, Fut>(
     first_index: u32,
     interval: Option<u64>,
 ) -> Result<(), MainError>
+where
+    F: Fn(u32) -> Fut,
+    Fut: Future<Output = Result<(), MainError>>,
+{
+    crawl_until(f, first_index, None, interval).await
+}
+
+/// Like `crawl`, returning once `last_index` has been processed
+pub async fn crawl_until<F, Fut>(
+    f: F,
+    first_index: u32,
+    last_index: Option<u32>,
+    interval: Option<u64>,
+) -> Result<(), MainError>
 where
     F: Fn(u32) -> Fut,
     Fut: Future<Output = Result<(), MainError>>,
 {
     let interval = interval.unwrap_or(5000);
-    let s = indexes(first_index, None);
+    let s = indexes(first_index, last_index);
     pin_mut!(s);
     let retry_strategy = FixedInterval::from_millis(interval).map(jitter);
     let must_exit = must_exit_handle();
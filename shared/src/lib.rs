@@ -13,8 +13,10 @@ pub mod id;
 pub mod parameters;
 pub mod proposal;
 pub mod public_key;
+pub mod redelegation;
 pub mod rewards;
 pub mod ser;
+pub mod slash;
 pub mod token;
 pub mod transaction;
 pub mod unbond;
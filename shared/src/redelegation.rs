@@ -0,0 +1,15 @@
+use crate::balance::Amount;
+use crate::block::Epoch;
+use crate::id::Id;
+
+#[derive(Debug, Clone)]
+pub struct Redelegation {
+    pub tx_id: Id,
+    pub delegator: Id,
+    pub src_validator: Id,
+    pub dest_validator: Id,
+    pub amount: Amount,
+    pub epoch: Epoch,
+}
+
+pub type Redelegations = Vec<Redelegation>;
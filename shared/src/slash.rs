@@ -0,0 +1,44 @@
+use namada_proof_of_stake::types::{
+    Slash as NamadaSlash, SlashType as NamadaSlashType,
+};
+
+use crate::block::{BlockHeight, Epoch};
+use crate::id::Id;
+
+#[derive(Debug, Clone)]
+pub enum SlashType {
+    DuplicateVote,
+    LightClientAttack,
+}
+
+impl From<NamadaSlashType> for SlashType {
+    fn from(value: NamadaSlashType) -> Self {
+        match value {
+            NamadaSlashType::DuplicateVote => SlashType::DuplicateVote,
+            NamadaSlashType::LightClientAttack => SlashType::LightClientAttack,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Slash {
+    pub validator: Id,
+    pub slash_type: SlashType,
+    pub block_height: BlockHeight,
+    pub epoch: Epoch,
+    pub rate: String,
+}
+
+impl Slash {
+    pub fn from_namada(validator: Id, slash: NamadaSlash) -> Self {
+        Self {
+            validator,
+            slash_type: slash.r#type.into(),
+            block_height: slash.block_height as BlockHeight,
+            epoch: slash.epoch.0 as Epoch,
+            rate: slash.rate.to_string(),
+        }
+    }
+}
+
+pub type Slashes = Vec<Slash>;
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::balance::{Amount, Denomination};
+use crate::block::{BlockHeight, Epoch};
 use crate::id::Id;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -22,3 +24,73 @@ impl Display for Token {
         }
     }
 }
+
+impl Token {
+    pub fn address(&self) -> Id {
+        match self {
+            Token::Ibc(token) => token.address.clone(),
+            Token::Native(token) => token.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenSupply {
+    pub address: Id,
+    pub epoch: Epoch,
+    pub total: Amount,
+    pub effective: Amount,
+    pub bonded: Amount,
+    pub pgf: Amount,
+    pub masp: Amount,
+}
+
+/// The components of an IBC token trace, e.g. `transfer/channel-2/uatom`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IbcTokenTrace {
+    /// Port of the first hop, on the Namada side
+    pub port: String,
+    /// Channel of the first hop, on the Namada side
+    pub channel: String,
+    pub base_denom: String,
+    /// Number of port/channel pairs the token went through
+    pub hops: usize,
+}
+
+impl IbcTokenTrace {
+    pub fn parse(trace: &str) -> Option<Self> {
+        let segments = trace.split('/').collect::<Vec<_>>();
+
+        // The trace is a list of port/channel pairs followed by the base
+        // denom, which can contain slashes itself
+        let mut hops = 0;
+        let mut rest = segments.as_slice();
+        while rest.len() > 2 && rest[1].starts_with("channel-") {
+            hops += 1;
+            rest = &rest[2..];
+        }
+
+        if hops == 0 {
+            return None;
+        }
+
+        Some(Self {
+            port: segments[0].to_string(),
+            channel: segments[1].to_string(),
+            base_denom: rest.join("/"),
+            hops,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TokenMetadata {
+    pub address: Id,
+    pub denomination: Denomination,
+    pub symbol: Option<String>,
+    pub ibc_trace: Option<IbcTokenTrace>,
+    /// Chain id of the counterparty of the first hop. Only known when the
+    /// token was received directly from the chain it originates from.
+    pub origin_chain: Option<String>,
+    pub first_seen_height: BlockHeight,
+}
@@ -16,6 +16,8 @@ pub struct Unbond {
     pub target: Id,
     pub amount: Amount,
     pub withdraw_at: Epoch,
+    /// Earliest start epoch of the bonds unbonded together
+    pub start: Epoch,
 }
 
 impl Unbond {
@@ -28,6 +30,7 @@ impl Unbond {
             target: validator_address,
             amount: Amount::fake(),
             withdraw_at: (3..10).fake::<u32>(),
+            start: (0..3).fake::<u32>(),
         }
     }
 }
@@ -3,8 +3,9 @@ use fake::faker::internet::en::{DomainSuffix, SafeEmail, Username};
 use fake::Fake;
 use namada_proof_of_stake::types::ValidatorState as NamadaValidatorState;
 use rand::distributions::{Distribution, Standard};
+use subtle_encoding::hex;
 
-use crate::block::Epoch;
+use crate::block::{BlockHeight, Epoch};
 use crate::id::Id;
 
 pub type VotingPower = String;
@@ -54,6 +55,10 @@ pub struct Validator {
     pub discord_handler: Option<String>,
     pub avatar: Option<String>,
     pub state: ValidatorState,
+    pub consensus_address: Option<String>,
+    /// Commission rate that takes effect at the end of the pipeline, if it
+    /// differs from the current one
+    pub pending_commission: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -68,6 +73,20 @@ pub struct ValidatorMetadataChange {
     pub avatar: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValidatorBlockKind {
+    Proposed,
+    Signed,
+    Missed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidatorBlock {
+    pub consensus_address: String,
+    pub height: BlockHeight,
+    pub kind: ValidatorBlockKind,
+}
+
 impl Validator {
     pub fn fake() -> Self {
         let address =
@@ -85,6 +104,9 @@ impl Validator {
             DomainSuffix().fake::<String>()
         ));
         let discord_handler: Option<String> = Username().fake();
+        let consensus_address =
+            String::from_utf8(hex::encode(rand::random::<[u8; 20]>()))
+                .expect("Hex encoding should be valid utf8");
 
         Self {
             address: Id::Account(address.to_string()),
@@ -98,6 +120,8 @@ impl Validator {
             discord_handler,
             avatar: Some("https://picsum.photos/200/300".to_string()),
             state: rand::random(),
+            consensus_address: Some(consensus_address),
+            pending_commission: None,
         }
     }
 }
@@ -55,6 +55,12 @@ impl TestDb {
         }
     }
 
+    /// Pool of the test database, for code taking a pool rather than a
+    /// connection
+    pub fn pool(&self) -> Pool {
+        self.pool.clone()
+    }
+
     pub async fn run_test(
         &self,
         test: impl Fn(&mut PgConnection) -> anyhow::Result<()> + Send + 'static,
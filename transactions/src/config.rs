@@ -23,6 +23,12 @@ pub struct AppConfig {
     #[clap(long, env, default_value_t = 1)]
     pub from_block_height: u32,
 
+    /// Last block to index. Bounded instances track their own progress, so
+    /// several can backfill disjoint ranges next to the live one. An instance
+    /// whose range overlaps another one refuses to start.
+    #[clap(long, env)]
+    pub to_block_height: Option<u32>,
+
     #[clap(long, env)]
     pub database_url: String,
 
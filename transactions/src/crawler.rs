@@ -0,0 +1,359 @@
+use std::convert::identity;
+use std::sync::Arc;
+
+use anyhow::Context;
+use chrono::{NaiveDateTime, Utc};
+use deadpool_diesel::postgres::{Object, Pool};
+use orm::crawler_state::TransactionsRangeStateInsertDb;
+use orm::notifications::{self, IndexerEvent};
+use shared::block::Block;
+use shared::block_result::BlockResult;
+use shared::checksums::Checksums;
+use shared::crawler::crawl_until;
+use shared::crawler_state::BlockCrawlerState;
+use shared::error::{AsDbError, AsRpcError, ContextDbInteractError, MainError};
+use shared::id::Id;
+use tendermint_rpc::HttpClient;
+
+use crate::repository::transactions as transaction_repo;
+use crate::services::{
+    db as db_service, namada as namada_service,
+    tendermint as tendermint_service,
+};
+
+/// Where processing a block records the crawler progress
+#[derive(Clone, Copy, Debug)]
+enum Mode {
+    /// Advances the crawler state and notifies the listeners
+    Live,
+    /// Advances the progress of the instance indexing `from..=to`
+    Range { from: u32, to: u32 },
+    /// Leaves any progress untouched
+    Reprocess,
+}
+
+/// Crawls blocks from the last one processed, or `from_block_height` if
+/// later, the database behind `pool` must be migrated. With a `to_block_height`
+/// the crawler returns once the range is indexed.
+pub async fn run(
+    client: Arc<HttpClient>,
+    pool: Pool,
+    from_block_height: u32,
+    to_block_height: Option<u32>,
+) -> Result<(), MainError> {
+    let checksums = query_checksums(&client).await;
+    let native_token = namada_service::get_native_token(&client)
+        .await
+        .into_rpc_error()?;
+
+    let conn = get_db_connection(&pool).await?;
+    let (next_block, mode) = match to_block_height {
+        Some(to_block_height) => {
+            let claimed = conn
+                .interact(move |conn| {
+                    conn.build_transaction().read_write().run(
+                        |transaction_conn| {
+                            transaction_repo::claim_range(
+                                transaction_conn,
+                                from_block_height,
+                                to_block_height,
+                                Utc::now().naive_utc(),
+                            )
+                        },
+                    )
+                })
+                .await
+                .context_db_interact_error()
+                .and_then(identity);
+            if let Err(e) = claimed {
+                tracing::error!("Failed to claim the range: {:#}", e);
+                return Err(MainError::NoAction);
+            }
+
+            let range_state = db_service::get_range_crawler_state(
+                &conn,
+                from_block_height,
+                to_block_height,
+            )
+            .await
+            .into_db_error()?;
+
+            (
+                range_state
+                    .map(|cs| cs.last_processed_block + 1)
+                    .unwrap_or(from_block_height),
+                Mode::Range {
+                    from: from_block_height,
+                    to: to_block_height,
+                },
+            )
+        }
+        None => {
+            let crawler_state = db_service::get_crawler_state(&conn).await;
+
+            (
+                std::cmp::max(
+                    crawler_state
+                        .map(|cs| cs.last_processed_block + 1)
+                        .unwrap_or(1),
+                    from_block_height,
+                ),
+                Mode::Live,
+            )
+        }
+    };
+    drop(conn);
+
+    crawl_until(
+        move |block_height| {
+            crawling_fn(
+                block_height,
+                client.clone(),
+                pool.clone(),
+                checksums.clone(),
+                native_token.clone(),
+                mode,
+            )
+        },
+        next_block,
+        to_block_height,
+        None,
+    )
+    .await
+}
+
+async fn crawling_fn(
+    block_height: u32,
+    client: Arc<HttpClient>,
+    pool: Pool,
+    checksums: Checksums,
+    native_token: Id,
+    mode: Mode,
+) -> Result<(), MainError> {
+    let conn = get_db_connection(&pool).await?;
+
+    let should_process = can_process(block_height, client.clone()).await?;
+
+    if !should_process {
+        if let Mode::Live = mode {
+            let timestamp = Utc::now().naive_utc();
+            update_crawler_timestamp(&conn, timestamp).await?;
+        }
+
+        tracing::warn!("Block {} was not processed, retry...", block_height);
+
+        return Err(MainError::NoAction);
+    }
+
+    process_block(block_height, &client, &conn, checksums, &native_token, mode)
+        .await
+}
+
+/// Reindexes the blocks of `from..=to`, leaving the crawler state untouched.
+/// Their transactions must have been deleted beforehand.
+pub async fn reprocess(
+    client: Arc<HttpClient>,
+    pool: Pool,
+    from: u32,
+    to: u32,
+) -> Result<(), MainError> {
+    if !can_process(to, client.clone()).await? {
+        tracing::error!("Block {} was not produced yet", to);
+        return Err(MainError::NoAction);
+    }
+
+    let checksums = query_checksums(&client).await;
+    let native_token = namada_service::get_native_token(&client)
+        .await
+        .into_rpc_error()?;
+
+    for block_height in from..=to {
+        let conn = get_db_connection(&pool).await?;
+
+        process_block(
+            block_height,
+            &client,
+            &conn,
+            checksums.clone(),
+            &native_token,
+            Mode::Reprocess,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn query_checksums(client: &HttpClient) -> Checksums {
+    let mut checksums = Checksums::default();
+    for code_path in Checksums::code_paths() {
+        let code = namada_service::query_tx_code_hash(client, &code_path)
+            .await
+            .unwrap_or_else(|| {
+                panic!("{} must be defined in namada storage.", code_path)
+            });
+        checksums.add(code_path, code.to_lowercase());
+    }
+
+    checksums
+}
+
+async fn process_block(
+    block_height: u32,
+    client: &HttpClient,
+    conn: &Object,
+    checksums: Checksums,
+    native_token: &Id,
+    mode: Mode,
+) -> Result<(), MainError> {
+    tracing::info!("Query block...");
+    let tm_block_response =
+        tendermint_service::query_raw_block_at_height(client, block_height)
+            .await
+            .into_rpc_error()?;
+    tracing::info!(
+        "Raw block contains {} txs...",
+        tm_block_response.block.data.len()
+    );
+
+    tracing::info!("Query block results...");
+    let tm_block_results_response =
+        tendermint_service::query_raw_block_results_at_height(
+            client,
+            block_height,
+        )
+        .await
+        .into_rpc_error()?;
+    let block_results = BlockResult::from(tm_block_results_response);
+
+    let block = Block::from(
+        tm_block_response.clone(),
+        &block_results,
+        checksums,
+        1_u32,
+        block_height,
+    );
+
+    let inner_txs = block.inner_txs();
+    let wrapper_txs = block.wrapper_txs();
+
+    let inner_txs_with_addresses = block.inner_txs_with_addresses(native_token);
+    let events = inner_txs
+        .iter()
+        .map(|tx| IndexerEvent::Transaction {
+            id: tx.tx_id.to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    tracing::info!(
+        "Deserialized {} txs...",
+        wrapper_txs.len() + inner_txs.len()
+    );
+
+    // Because transaction crawler starts from block 1 we read timestamp from
+    // the block
+    let timestamp = tm_block_response.block.header.time.unix_timestamp();
+    let crawler_state = BlockCrawlerState {
+        timestamp,
+        last_processed_block: block_height,
+    };
+
+    conn.interact(move |conn| {
+        conn.build_transaction()
+            .read_write()
+            .run(|transaction_conn| {
+                transaction_repo::insert_wrapper_transactions(
+                    transaction_conn,
+                    wrapper_txs,
+                )?;
+                transaction_repo::insert_inner_transactions(
+                    transaction_conn,
+                    inner_txs,
+                )?;
+                transaction_repo::insert_inner_transaction_addresses(
+                    transaction_conn,
+                    &inner_txs_with_addresses,
+                    block_height,
+                )?;
+                transaction_repo::insert_block(
+                    transaction_conn,
+                    block_height,
+                    timestamp,
+                )?;
+                match mode {
+                    Mode::Live => {
+                        transaction_repo::insert_crawler_state(
+                            transaction_conn,
+                            crawler_state,
+                        )?;
+
+                        notifications::notify(transaction_conn, events);
+                    }
+                    Mode::Range { from, to } => {
+                        transaction_repo::upsert_range_state(
+                            transaction_conn,
+                            TransactionsRangeStateInsertDb::from(
+                                from,
+                                to,
+                                crawler_state,
+                            ),
+                        )?;
+                    }
+                    Mode::Reprocess => {}
+                }
+
+                anyhow::Ok(())
+            })
+    })
+    .await
+    .context_db_interact_error()
+    .and_then(identity)
+    .into_db_error()?;
+
+    Ok(())
+}
+
+async fn can_process(
+    block_height: u32,
+    client: Arc<HttpClient>,
+) -> Result<bool, MainError> {
+    tracing::info!("Attempting to process block: {}...", block_height);
+
+    let last_block_height =
+        namada_service::get_last_block(&client).await.map_err(|e| {
+            tracing::error!(
+                "Failed to query Namada's last committed block: {}",
+                e
+            );
+            MainError::RpcError
+        })?;
+
+    Ok(last_block_height >= block_height)
+}
+
+async fn update_crawler_timestamp(
+    conn: &Object,
+    timestamp: NaiveDateTime,
+) -> Result<(), MainError> {
+    conn.interact(move |transaction_conn| {
+        transaction_repo::update_crawler_timestamp(
+            transaction_conn,
+            timestamp,
+        )?;
+
+        anyhow::Ok(())
+    })
+    .await
+    .context_db_interact_error()
+    .into_db_error()?
+    .context("Insert crawler state error")
+    .into_db_error()
+}
+
+/// Connections are only held for the block being processed
+async fn get_db_connection(pool: &Pool) -> Result<Object, MainError> {
+    pool.get()
+        .await
+        .context("Failed to get db connection handle from deadpool")
+        .into_db_error()
+}
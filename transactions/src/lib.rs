@@ -1,4 +1,5 @@
 pub mod app_state;
 pub mod config;
+pub mod crawler;
 pub mod repository;
 pub mod services;
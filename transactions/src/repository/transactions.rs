@@ -1,11 +1,30 @@
-use anyhow::Context;
+use std::collections::HashSet;
+
+use anyhow::{bail, Context};
 use chrono::NaiveDateTime;
+use diesel::dsl::sql;
+use diesel::sql_types::{Integer, Nullable};
 use diesel::upsert::excluded;
-use diesel::{ExpressionMethods, PgConnection, RunQueryDsl};
-use orm::crawler_state::{BlockStateInsertDb, CrawlerNameDb};
-use orm::schema::{crawler_state, inner_transactions, wrapper_transactions};
-use orm::transactions::{InnerTransactionInsertDb, WrapperTransactionInsertDb};
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension, PgConnection,
+    QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use orm::blocks::BlockInsertDb;
+use orm::crawler_state::{
+    BlockStateInsertDb, CrawlerNameDb, TransactionsRangeStateDb,
+    TransactionsRangeStateInsertDb,
+};
+use orm::schema::{
+    blocks, crawler_state, inner_transaction_addresses, inner_transactions,
+    transactions_crawler_ranges, wrapper_transactions,
+};
+use orm::transactions::{
+    InnerTransactionAddressInsertDb, InnerTransactionInsertDb,
+    WrapperTransactionInsertDb,
+};
+use shared::block::BlockHeight;
 use shared::crawler_state::{BlockCrawlerState, CrawlerName};
+use shared::id::Id;
 use shared::transaction::{InnerTransaction, WrapperTransaction};
 
 pub fn insert_inner_transactions(
@@ -18,12 +37,47 @@ pub fn insert_inner_transactions(
                 .map(InnerTransactionInsertDb::from)
                 .collect::<Vec<_>>(),
         )
+        .on_conflict(inner_transactions::id)
+        .do_update()
+        .set((
+            inner_transactions::wrapper_id
+                .eq(excluded(inner_transactions::wrapper_id)),
+            inner_transactions::kind.eq(excluded(inner_transactions::kind)),
+            inner_transactions::data.eq(excluded(inner_transactions::data)),
+            inner_transactions::memo.eq(excluded(inner_transactions::memo)),
+            inner_transactions::exit_code
+                .eq(excluded(inner_transactions::exit_code)),
+        ))
         .execute(transaction_conn)
         .context("Failed to insert inner transactions in db")?;
 
     anyhow::Ok(())
 }
 
+pub fn insert_inner_transaction_addresses(
+    transaction_conn: &mut PgConnection,
+    txs: &[(InnerTransaction, HashSet<Id>)],
+    block_height: BlockHeight,
+) -> anyhow::Result<()> {
+    diesel::insert_into(inner_transaction_addresses::table)
+        .values::<&Vec<InnerTransactionAddressInsertDb>>(
+            &txs.iter()
+                .flat_map(|(tx, addresses)| {
+                    InnerTransactionAddressInsertDb::from(
+                        tx,
+                        addresses,
+                        block_height,
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+        .on_conflict_do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to insert inner transaction addresses in db")?;
+
+    anyhow::Ok(())
+}
+
 pub fn insert_wrapper_transactions(
     transaction_conn: &mut PgConnection,
     txs: Vec<WrapperTransaction>,
@@ -34,26 +88,74 @@ pub fn insert_wrapper_transactions(
                 .map(WrapperTransactionInsertDb::from)
                 .collect::<Vec<_>>(),
         )
+        .on_conflict(wrapper_transactions::id)
+        .do_update()
+        .set((
+            wrapper_transactions::fee_payer
+                .eq(excluded(wrapper_transactions::fee_payer)),
+            wrapper_transactions::fee_token
+                .eq(excluded(wrapper_transactions::fee_token)),
+            wrapper_transactions::gas_limit
+                .eq(excluded(wrapper_transactions::gas_limit)),
+            wrapper_transactions::block_height
+                .eq(excluded(wrapper_transactions::block_height)),
+            wrapper_transactions::exit_code
+                .eq(excluded(wrapper_transactions::exit_code)),
+            wrapper_transactions::atomic
+                .eq(excluded(wrapper_transactions::atomic)),
+        ))
         .execute(transaction_conn)
         .context("Failed to insert wrapper transactions in db")?;
 
     anyhow::Ok(())
 }
 
+pub fn insert_block(
+    transaction_conn: &mut PgConnection,
+    block_height: BlockHeight,
+    timestamp: i64,
+) -> anyhow::Result<()> {
+    diesel::insert_into(blocks::table)
+        .values::<&BlockInsertDb>(&BlockInsertDb::from(
+            block_height,
+            timestamp,
+        )?)
+        .on_conflict(blocks::height)
+        .do_update()
+        .set(blocks::timestamp.eq(excluded(blocks::timestamp)))
+        .execute(transaction_conn)
+        .context("Failed to insert block in db")?;
+
+    anyhow::Ok(())
+}
+
 pub fn insert_crawler_state(
     transaction_conn: &mut PgConnection,
     crawler_state: BlockCrawlerState,
 ) -> anyhow::Result<()> {
+    let first_processed_block = crawler_state.last_processed_block as i32;
+
+    // The first processed block only moves back, when the crawler is rewound
+    // below it
     diesel::insert_into(crawler_state::table)
-        .values::<&BlockStateInsertDb>(
-            &(CrawlerName::Transactions, crawler_state).into(),
-        )
+        .values((
+            &BlockStateInsertDb::from((
+                CrawlerName::Transactions,
+                crawler_state,
+            )),
+            crawler_state::first_processed_block.eq(first_processed_block),
+        ))
         .on_conflict(crawler_state::name)
         .do_update()
         .set((
             crawler_state::timestamp.eq(excluded(crawler_state::timestamp)),
             crawler_state::last_processed_block
                 .eq(excluded(crawler_state::last_processed_block)),
+            crawler_state::first_processed_block.eq(sql::<Nullable<Integer>>(
+                "LEAST(COALESCE(crawler_state.first_processed_block, \
+                 excluded.last_processed_block), \
+                 excluded.last_processed_block)",
+            )),
         ))
         .execute(transaction_conn)
         .context("Failed to update crawler state in db")?;
@@ -61,6 +163,109 @@ pub fn insert_crawler_state(
     anyhow::Ok(())
 }
 
+pub fn upsert_range_state(
+    transaction_conn: &mut PgConnection,
+    range_state: TransactionsRangeStateInsertDb,
+) -> anyhow::Result<()> {
+    diesel::insert_into(transactions_crawler_ranges::table)
+        .values::<&TransactionsRangeStateInsertDb>(&range_state)
+        .on_conflict((
+            transactions_crawler_ranges::from_block_height,
+            transactions_crawler_ranges::to_block_height,
+        ))
+        .do_update()
+        .set((
+            transactions_crawler_ranges::last_processed_block.eq(excluded(
+                transactions_crawler_ranges::last_processed_block,
+            )),
+            transactions_crawler_ranges::timestamp
+                .eq(excluded(transactions_crawler_ranges::timestamp)),
+        ))
+        .execute(transaction_conn)
+        .context("Failed to update range crawler state in db")?;
+
+    anyhow::Ok(())
+}
+
+/// Records the instance indexing `from..=to`, refusing if another range or
+/// the live crawler holds some of its blocks. The exclusion constraint of
+/// the ranges table rejects a concurrent claim of overlapping blocks.
+pub fn claim_range(
+    transaction_conn: &mut PgConnection,
+    from: BlockHeight,
+    to: BlockHeight,
+    timestamp: NaiveDateTime,
+) -> anyhow::Result<()> {
+    let (from, to) = (from as i32, to as i32);
+
+    // Locked so the live crawler can't record blocks of the range while it is
+    // claimed
+    let live: Option<(Option<i32>, Option<i32>)> = crawler_state::table
+        .filter(crawler_state::name.eq(CrawlerNameDb::Transactions))
+        .select((
+            crawler_state::first_processed_block,
+            crawler_state::last_processed_block,
+        ))
+        .for_update()
+        .first(transaction_conn)
+        .optional()
+        .context("Failed to lock transactions crawler state in db")?;
+    if let Some((Some(first), Some(last))) = live {
+        if first <= to && last >= from {
+            bail!(
+                "Blocks {} to {} overlap the blocks {}-{} indexed by the live \
+                 crawler",
+                from,
+                to,
+                first,
+                last
+            );
+        }
+    }
+
+    let overlapping: Option<TransactionsRangeStateDb> =
+        transactions_crawler_ranges::table
+            .filter(transactions_crawler_ranges::from_block_height.le(to))
+            .filter(transactions_crawler_ranges::to_block_height.ge(from))
+            .filter(
+                transactions_crawler_ranges::from_block_height
+                    .ne(from)
+                    .or(transactions_crawler_ranges::to_block_height.ne(to)),
+            )
+            .select(TransactionsRangeStateDb::as_select())
+            .first(transaction_conn)
+            .optional()
+            .context("Failed to read range crawlers state from the db")?;
+    if let Some(range) = overlapping {
+        bail!(
+            "Blocks {} to {} overlap the range {}-{} indexed by another \
+             instance",
+            from,
+            to,
+            range.from_block_height,
+            range.to_block_height
+        );
+    }
+
+    // A resumed range keeps its progress
+    diesel::insert_into(transactions_crawler_ranges::table)
+        .values(TransactionsRangeStateInsertDb {
+            from_block_height: from,
+            to_block_height: to,
+            last_processed_block: (from - 1).max(0),
+            timestamp,
+        })
+        .on_conflict((
+            transactions_crawler_ranges::from_block_height,
+            transactions_crawler_ranges::to_block_height,
+        ))
+        .do_nothing()
+        .execute(transaction_conn)
+        .context("Failed to claim range in db")?;
+
+    anyhow::Ok(())
+}
+
 pub fn update_crawler_timestamp(
     transaction_conn: &mut PgConnection,
     timestamp: NaiveDateTime,
@@ -76,3 +281,200 @@ pub fn update_crawler_timestamp(
 
     anyhow::Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+
+    use diesel::Connection;
+    use shared::transaction::{Fee, TransactionExitStatus, TransactionKind};
+    use test_helpers::db::TestDb;
+
+    use super::*;
+
+    // Test case for reindexing a block that was already indexed, as a
+    // restarted crawler or an overlapping range would
+    #[tokio::test]
+    async fn test_insert_block_twice_success() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            insert_block_transactions(conn, 1)?;
+            insert_block_transactions(conn, 1)?;
+
+            let wrappers: i64 =
+                wrapper_transactions::table.count().get_result(conn)?;
+            let inners: i64 =
+                inner_transactions::table.count().get_result(conn)?;
+            let addresses: i64 = inner_transaction_addresses::table
+                .count()
+                .get_result(conn)?;
+            let blocks: i64 = blocks::table.count().get_result(conn)?;
+
+            assert_eq!(wrappers, 1);
+            assert_eq!(inners, 2);
+            assert_eq!(addresses, 4);
+            assert_eq!(blocks, 1);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that a range is claimed only when neither another range nor the
+    /// live crawler holds some of its blocks.
+    #[tokio::test]
+    async fn test_claim_range() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let now =
+                chrono::DateTime::from_timestamp(0, 0).unwrap().naive_utc();
+            for last_processed_block in [10, 20] {
+                insert_crawler_state(
+                    conn,
+                    BlockCrawlerState {
+                        last_processed_block,
+                        timestamp: 0,
+                    },
+                )?;
+            }
+
+            assert!(claim_range(conn, 5, 15, now).is_err());
+            assert!(claim_range(conn, 1, 9, now).is_ok());
+            assert!(claim_range(conn, 21, 30, now).is_ok());
+            assert!(claim_range(conn, 25, 40, now).is_err());
+
+            upsert_range_state(
+                conn,
+                TransactionsRangeStateInsertDb {
+                    from_block_height: 21,
+                    to_block_height: 30,
+                    last_processed_block: 25,
+                    timestamp: now,
+                },
+            )?;
+            assert!(claim_range(conn, 21, 30, now).is_ok());
+
+            let ranges: Vec<(i32, i32, i32)> =
+                transactions_crawler_ranges::table
+                    .select((
+                        transactions_crawler_ranges::from_block_height,
+                        transactions_crawler_ranges::to_block_height,
+                        transactions_crawler_ranges::last_processed_block,
+                    ))
+                    .order(transactions_crawler_ranges::from_block_height)
+                    .load(conn)?;
+            assert_eq!(ranges, vec![(1, 9, 0), (21, 30, 25)]);
+
+            // Claimed without the checks, as a concurrent instance could, in
+            // a savepoint so the test transaction survives the failure
+            let overlapping = conn.transaction(|conn| {
+                upsert_range_state(
+                    conn,
+                    TransactionsRangeStateInsertDb {
+                        from_block_height: 28,
+                        to_block_height: 35,
+                        last_processed_block: 27,
+                        timestamp: now,
+                    },
+                )
+            });
+            assert!(overlapping.is_err());
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that the first block of the live crawler moves back only when it
+    /// is rewound below it.
+    #[tokio::test]
+    async fn test_insert_crawler_state_first_processed_block() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let first_processed_block = |conn: &mut PgConnection| {
+                crawler_state::table
+                    .filter(crawler_state::name.eq(CrawlerNameDb::Transactions))
+                    .select(crawler_state::first_processed_block)
+                    .first::<Option<i32>>(conn)
+            };
+
+            for (last_processed_block, expected) in
+                [(10, 10), (20, 10), (5, 5), (8, 5)]
+            {
+                insert_crawler_state(
+                    conn,
+                    BlockCrawlerState {
+                        last_processed_block,
+                        timestamp: 0,
+                    },
+                )?;
+                assert_eq!(first_processed_block(conn)?, Some(expected));
+            }
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    fn insert_block_transactions(
+        conn: &mut PgConnection,
+        block_height: BlockHeight,
+    ) -> anyhow::Result<()> {
+        let wrapper_id = Id::Hash(format!("wrapper{}", block_height));
+        let wrapper_tx = WrapperTransaction {
+            tx_id: wrapper_id.clone(),
+            index: 0,
+            fee: Fee {
+                gas: "10000".to_string(),
+                amount_per_gas_unit: "1".to_string(),
+                gas_payer: Id::Account("tnam1payer".to_string()),
+                gas_token: Id::Account("tnam1token".to_string()),
+            },
+            atomic: true,
+            block_height,
+            exit_code: TransactionExitStatus::Applied,
+        };
+        let inner_txs = (0..2)
+            .map(|index| InnerTransaction {
+                tx_id: Id::Hash(format!("inner{}-{}", block_height, index)),
+                index,
+                wrapper_id: wrapper_id.clone(),
+                kind: TransactionKind::Unknown,
+                memo: None,
+                data: None,
+                extra_sections: HashMap::new(),
+                exit_code: TransactionExitStatus::Applied,
+            })
+            .collect::<Vec<_>>();
+        let inner_txs_with_addresses = inner_txs
+            .iter()
+            .map(|tx| {
+                (
+                    tx.clone(),
+                    HashSet::from([
+                        Id::Account("tnam1source".to_string()),
+                        Id::Account("tnam1target".to_string()),
+                    ]),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        insert_wrapper_transactions(conn, vec![wrapper_tx])?;
+        insert_inner_transactions(conn, inner_txs)?;
+        insert_inner_transaction_addresses(
+            conn,
+            &inner_txs_with_addresses,
+            block_height,
+        )?;
+        insert_block(conn, block_height, 1_700_000_000)?;
+
+        anyhow::Ok(())
+    }
+}
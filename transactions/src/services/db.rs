@@ -1,8 +1,13 @@
 use anyhow::Context;
 use deadpool_diesel::postgres::Object;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
-use orm::crawler_state::{BlockCrawlerStateDb, CrawlerNameDb};
-use orm::schema::crawler_state;
+use diesel::{
+    ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl,
+    SelectableHelper,
+};
+use orm::crawler_state::{
+    BlockCrawlerStateDb, CrawlerNameDb, TransactionsRangeStateDb,
+};
+use orm::schema::{crawler_state, transactions_crawler_ranges};
 use shared::block::BlockHeight;
 use shared::crawler_state::BlockCrawlerState;
 use shared::error::ContextDbInteractError;
@@ -29,3 +34,33 @@ pub async fn get_crawler_state(
         timestamp: crawler_state.timestamp.and_utc().timestamp(),
     })
 }
+
+/// Progress of the instance indexing `from..=to`, if it started already
+pub async fn get_range_crawler_state(
+    conn: &Object,
+    from: BlockHeight,
+    to: BlockHeight,
+) -> anyhow::Result<Option<BlockCrawlerState>> {
+    let range_state: Option<TransactionsRangeStateDb> = conn
+        .interact(move |conn| {
+            transactions_crawler_ranges::table
+                .filter(
+                    transactions_crawler_ranges::from_block_height
+                        .eq(from as i32),
+                )
+                .filter(
+                    transactions_crawler_ranges::to_block_height.eq(to as i32),
+                )
+                .select(TransactionsRangeStateDb::as_select())
+                .first(conn)
+                .optional()
+        })
+        .await
+        .context_db_interact_error()?
+        .context("Failed to read range crawler state from the db")?;
+
+    Ok(range_state.map(|range_state| BlockCrawlerState {
+        last_processed_block: range_state.last_processed_block as BlockHeight,
+        timestamp: range_state.timestamp.and_utc().timestamp(),
+    }))
+}
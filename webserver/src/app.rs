@@ -1,33 +1,49 @@
 use std::net::{Ipv4Addr, SocketAddr};
 use std::time::Duration;
 
+use async_graphql_axum::GraphQL;
 use axum::error_handling::HandleErrorLayer;
-use axum::http::{HeaderValue, StatusCode};
+use axum::extract::DefaultBodyLimit;
+use axum::http::{header, HeaderName, HeaderValue, StatusCode};
+use axum::middleware::from_fn_with_state;
 use axum::response::IntoResponse;
-use axum::routing::get;
+use axum::routing::{get, get_service, post};
 use axum::{BoxError, Json, Router};
 use lazy_static::lazy_static;
 use namada_sdk::tendermint_rpc::HttpClient;
+use orm::crawler_state::CrawlerNameDb;
 use serde_json::json;
 use tower::buffer::BufferLayer;
-use tower::limit::RateLimitLayer;
 use tower::ServiceBuilder;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 use crate::appstate::AppState;
+use crate::cache::{cache_response, CacheLayerState, ResponseCache};
 use crate::config::AppConfig;
+use crate::constant::MAX_BATCH_REQUEST_BODY_SIZE;
+use crate::event_bus::EventBus;
+use crate::freshness::{
+    conditional_get, FreshnessLayerState, INDEXED_BLOCK_HEIGHT, INDEXED_EPOCH,
+};
+use crate::graphql::schema::build_schema;
+use crate::handler::export::HISTORY_FROM_HEIGHT;
 use crate::handler::{
     balance as balance_handlers, chain as chain_handlers,
-    crawler_state as crawler_state_handlers, gas as gas_handlers,
-    governance as gov_handlers, pk as pk_handlers, pos as pos_handlers,
+    crawler_state as crawler_state_handlers, event as event_handlers,
+    export as export_handlers, gas as gas_handlers, governance as gov_handlers,
+    pk as pk_handlers, pos as pos_handlers,
     transaction as transaction_handlers,
 };
+use crate::openapi::ApiDoc;
+use crate::rate_limit::{rate_limit, RateLimiter};
+use crate::service::transaction::TransactionService;
 use crate::state::common::CommonState;
 
 lazy_static! {
     static ref HTTP_TIMEOUT: u64 = 60;
-    static ref REQ_PER_SEC: u64 = u64::MAX;
 }
 
 pub struct ApplicationServer;
@@ -40,104 +56,397 @@ impl ApplicationServer {
         let app_state = AppState::new(db_url, cache_url);
         let client = HttpClient::new(config.tendermint_url.as_str()).unwrap();
 
+        Self::serve_with(config, app_state, client).await
+    }
+
+    /// Serves the API on top of an existing database pool and RPC client
+    pub async fn serve_with(
+        config: AppConfig,
+        app_state: AppState,
+        client: HttpClient,
+    ) -> anyhow::Result<()> {
+        let response_cache = ResponseCache::new(
+            app_state.clone(),
+            config.cache_ttl,
+            config.cache_lru_capacity,
+        );
+        let cached = |crawlers: &[CrawlerNameDb]| {
+            from_fn_with_state(
+                CacheLayerState::new(response_cache.clone(), crawlers),
+                cache_response,
+            )
+        };
+
+        let fresh = |crawlers: &[CrawlerNameDb]| {
+            from_fn_with_state(
+                FreshnessLayerState::new(
+                    app_state.clone(),
+                    crawlers,
+                    config.cache_control_max_age,
+                ),
+                conditional_get,
+            )
+        };
+
+        let rate_limiter = RateLimiter::from_config(&config);
+        let event_bus = EventBus::new(
+            &config.database_url,
+            TransactionService::new(app_state.clone()),
+        )
+        .await?;
+
         let routes = {
-            let common_state =
-                CommonState::new(client, config.clone(), app_state.clone());
+            let common_state = CommonState::new(
+                client,
+                config.clone(),
+                app_state.clone(),
+                event_bus,
+            );
+            let graphql_schema = build_schema(common_state.clone());
 
-            Router::new()
-                .route("/pos/validator", get(pos_handlers::get_validators))
+            let routes = Router::new()
+                .route(
+                    "/pos/validator",
+                    get(pos_handlers::get_validators)
+                        .layer(cached(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Pos,
+                        ]))
+                        .layer(fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Pos,
+                        ])),
+                )
                 .route(
                     "/pos/validator/all",
-                    get(pos_handlers::get_all_validators),
+                    get(pos_handlers::get_all_validators)
+                        .layer(cached(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Pos,
+                        ]))
+                        .layer(fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Pos,
+                        ])),
+                )
+                .route(
+                    "/pos/validator/:address",
+                    get(pos_handlers::get_validator).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Pos,
+                    ])),
+                )
+                .route(
+                    "/pos/validator/:address/delegations",
+                    get(pos_handlers::get_validator_delegations).layer(fresh(
+                        &[CrawlerNameDb::Chain, CrawlerNameDb::Pos],
+                    )),
+                )
+                .route(
+                    "/pos/validator/:address/estimated-rewards",
+                    get(pos_handlers::get_estimated_rewards).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Parameters,
+                        CrawlerNameDb::Pos,
+                    ])),
+                )
+                .route(
+                    "/pos/validator/:address/blocks",
+                    get(pos_handlers::get_validator_blocks)
+                        .layer(fresh(&[CrawlerNameDb::Chain])),
+                )
+                .route(
+                    "/pos/validator/:address/slashes",
+                    get(pos_handlers::get_validator_slashes)
+                        .layer(fresh(&[CrawlerNameDb::Pos])),
+                )
+                .route(
+                    "/pos/my-validator",
+                    get(pos_handlers::get_my_validators).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Pos,
+                    ])),
+                )
+                .route(
+                    "/pos/bond/:address",
+                    get(pos_handlers::get_bonds).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Pos,
+                    ])),
                 )
-                .route("/pos/bond/:address", get(pos_handlers::get_bonds))
                 .route(
                     "/pos/merged-bonds/:address",
-                    get(pos_handlers::get_merged_bonds),
+                    get(pos_handlers::get_merged_bonds).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Pos,
+                    ])),
+                )
+                .route(
+                    "/pos/unbond/:address",
+                    get(pos_handlers::get_unbonds).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Pos,
+                    ])),
                 )
-                .route("/pos/unbond/:address", get(pos_handlers::get_unbonds))
                 .route(
                     "/pos/merged-unbonds/:address",
-                    get(pos_handlers::get_merged_unbonds),
+                    get(pos_handlers::get_merged_unbonds).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Pos,
+                    ])),
                 )
                 .route(
                     "/pos/withdraw/:address",
-                    get(pos_handlers::get_withdraws),
+                    get(pos_handlers::get_withdraws).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Pos,
+                    ])),
+                )
+                .route(
+                    "/pos/redelegation/:address",
+                    get(pos_handlers::get_redelegations).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Pos,
+                    ])),
+                )
+                .route(
+                    "/pos/reward/:address",
+                    get(pos_handlers::get_rewards).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Rewards,
+                    ])),
+                )
+                .route(
+                    "/pos/reward/:address/history",
+                    get(pos_handlers::get_rewards_history).layer(fresh(&[
+                        CrawlerNameDb::Chain,
+                        CrawlerNameDb::Rewards,
+                    ])),
                 )
-                .route("/pos/reward/:address", get(pos_handlers::get_rewards))
                 .route(
                     "/pos/voting-power",
-                    get(pos_handlers::get_total_voting_power),
+                    get(pos_handlers::get_total_voting_power)
+                        .layer(cached(&[CrawlerNameDb::Pos]))
+                        .layer(fresh(&[CrawlerNameDb::Pos])),
                 )
                 .route(
                     "/gov/proposal",
-                    get(gov_handlers::get_governance_proposals),
+                    get(gov_handlers::get_governance_proposals)
+                        .layer(cached(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                        ]))
+                        .layer(fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                        ])),
                 )
                 .route(
                     "/gov/proposal/all",
-                    get(gov_handlers::get_all_governance_proposals),
+                    get(gov_handlers::get_all_governance_proposals)
+                        .layer(cached(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                        ]))
+                        .layer(fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                        ])),
                 )
                 .route(
                     "/gov/proposal/:id",
-                    get(gov_handlers::get_governance_proposal_by_id),
+                    get(gov_handlers::get_governance_proposal_by_id).layer(
+                        fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                        ]),
+                    ),
                 )
                 .route(
                     "/gov/proposal/:id/votes",
-                    get(gov_handlers::get_governance_proposal_votes),
+                    get(gov_handlers::get_governance_proposal_votes).layer(
+                        fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                        ]),
+                    ),
                 )
                 .route(
                     "/gov/proposal/:id/votes/:address",
-                    get(gov_handlers::get_governance_proposal_votes_by_address),
+                    get(gov_handlers::get_governance_proposal_votes_by_address)
+                        .layer(fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                        ])),
                 )
                 .route(
                     "/gov/voter/:address/votes",
-                    get(gov_handlers::get_governance_proposal_votes_by_voter),
+                    get(gov_handlers::get_governance_proposal_votes_by_voter)
+                        .layer(fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                        ])),
+                )
+                .route(
+                    "/account/balances",
+                    post(balance_handlers::get_addresses_balances).layer(
+                        DefaultBodyLimit::max(MAX_BATCH_REQUEST_BODY_SIZE),
+                    ),
                 )
                 .route(
                     "/account/:address",
-                    get(balance_handlers::get_address_balance),
+                    get(balance_handlers::get_address_balance)
+                        .layer(fresh(&[CrawlerNameDb::Chain])),
+                )
+                .route(
+                    "/account/:address/export/transactions",
+                    get(export_handlers::export_transactions),
+                )
+                .route(
+                    "/account/:address/export/balances",
+                    get(export_handlers::export_balance_changes),
+                )
+                .route(
+                    "/account/:address/export/bonds",
+                    get(export_handlers::export_bonds),
+                )
+                .route(
+                    "/account/:address/export/unbonds",
+                    get(export_handlers::export_unbonds),
+                )
+                .route(
+                    "/account/:address/export/rewards",
+                    get(export_handlers::export_reward_claims),
                 )
                 .route(
                     "/revealed-public-key/:address",
-                    get(pk_handlers::get_revealed_pk),
+                    get(pk_handlers::get_revealed_pk)
+                        .layer(fresh(&[CrawlerNameDb::Chain])),
+                )
+                .route(
+                    "/gas/:token",
+                    get(gas_handlers::get_gas_by_token)
+                        .layer(fresh(&[CrawlerNameDb::Parameters])),
                 )
-                .route("/gas/:token", get(gas_handlers::get_gas_by_token))
                 .route(
                     "/gas-price/:token",
-                    get(gas_handlers::get_gas_price_by_token),
+                    get(gas_handlers::get_gas_price_by_token)
+                        .layer(fresh(&[CrawlerNameDb::Parameters])),
                 )
                 .route(
                     "/chain/wrapper/:id",
-                    get(transaction_handlers::get_wrapper_tx),
+                    get(transaction_handlers::get_wrapper_tx)
+                        .layer(fresh(&[CrawlerNameDb::Transactions])),
                 )
                 .route(
                     "/chain/inner/:id",
-                    get(transaction_handlers::get_inner_tx),
+                    get(transaction_handlers::get_inner_tx)
+                        .layer(fresh(&[CrawlerNameDb::Transactions])),
+                )
+                .route(
+                    "/chain/history/:address",
+                    get(transaction_handlers::get_inner_txs_by_address)
+                        .layer(fresh(&[CrawlerNameDb::Transactions])),
+                )
+                .route(
+                    "/chain/parameters",
+                    get(chain_handlers::get_parameters)
+                        .layer(cached(&[CrawlerNameDb::Parameters]))
+                        .layer(fresh(&[CrawlerNameDb::Parameters])),
+                )
+                .route(
+                    "/chain/rpc-url",
+                    get(chain_handlers::get_rpc_url).layer(fresh(&[])),
+                )
+                .route(
+                    "/chain/token",
+                    get(chain_handlers::get_tokens)
+                        .layer(cached(&[CrawlerNameDb::Chain]))
+                        .layer(fresh(&[CrawlerNameDb::Chain])),
+                )
+                .route(
+                    "/chain/token/:address/supply",
+                    get(chain_handlers::get_token_supply)
+                        .layer(fresh(&[CrawlerNameDb::Parameters])),
+                )
+                .route(
+                    "/chain/token/:address/holders",
+                    get(chain_handlers::get_token_holders)
+                        .layer(fresh(&[CrawlerNameDb::Chain])),
+                )
+                .route(
+                    "/chain/token/:address/distribution",
+                    get(chain_handlers::get_token_distribution)
+                        .layer(fresh(&[CrawlerNameDb::Chain])),
                 )
-                .route("/chain/parameters", get(chain_handlers::get_parameters))
-                .route("/chain/rpc-url", get(chain_handlers::get_rpc_url))
-                .route("/chain/token", get(chain_handlers::get_tokens))
                 .route(
                     "/chain/block/latest",
-                    get(chain_handlers::get_last_processed_block),
+                    get(chain_handlers::get_last_processed_block)
+                        .layer(fresh(&[CrawlerNameDb::Chain])),
                 )
                 .route(
                     "/chain/epoch/latest",
-                    get(chain_handlers::get_last_processed_epoch),
+                    get(chain_handlers::get_last_processed_epoch)
+                        .layer(fresh(&[CrawlerNameDb::Chain])),
                 )
                 .route(
                     "/crawlers/timestamps",
-                    get(crawler_state_handlers::get_crawlers_timestamps),
+                    get(crawler_state_handlers::get_crawlers_timestamps).layer(
+                        fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                            CrawlerNameDb::Parameters,
+                            CrawlerNameDb::Pos,
+                            CrawlerNameDb::Rewards,
+                            CrawlerNameDb::Transactions,
+                        ]),
+                    ),
                 )
                 // Server sent events endpoints
                 .route("/chain/status", get(chain_handlers::chain_status))
-                .with_state(common_state)
+                .route("/events", get(event_handlers::subscribe_events))
+                .route(
+                    "/graphql",
+                    get_service(GraphQL::new(graphql_schema.clone()))
+                        .post_service(GraphQL::new(graphql_schema))
+                        .layer(cached(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                            CrawlerNameDb::Parameters,
+                            CrawlerNameDb::Pos,
+                            CrawlerNameDb::Rewards,
+                            CrawlerNameDb::Transactions,
+                        ]))
+                        .layer(fresh(&[
+                            CrawlerNameDb::Chain,
+                            CrawlerNameDb::Governance,
+                            CrawlerNameDb::Parameters,
+                            CrawlerNameDb::Pos,
+                            CrawlerNameDb::Rewards,
+                            CrawlerNameDb::Transactions,
+                        ])),
+                );
+
+            let routes = match rate_limiter {
+                Some(rate_limiter) => routes
+                    .route_layer(from_fn_with_state(rate_limiter, rate_limit)),
+                None => routes,
+            };
+
+            routes.with_state(common_state)
         };
 
         let cors = CorsLayer::new()
             .allow_origin("*".parse::<HeaderValue>().unwrap())
             .allow_methods(Any)
-            .allow_headers(Any);
+            .allow_headers(Any)
+            .expose_headers([
+                header::ETAG,
+                header::LAST_MODIFIED,
+                HeaderName::from_static(INDEXED_BLOCK_HEIGHT),
+                HeaderName::from_static(INDEXED_EPOCH),
+                HeaderName::from_static(HISTORY_FROM_HEIGHT),
+            ]);
 
         let router = Router::new()
             .nest("/api/v1", routes)
@@ -145,17 +454,17 @@ impl ApplicationServer {
                 "/health",
                 get(|| async { env!("VERGEN_GIT_SHA").to_string() }),
             ))
+            .merge(
+                SwaggerUi::new("/api/v1/docs")
+                    .url("/api/v1/openapi.json", ApiDoc::openapi()),
+            )
             .layer(
                 ServiceBuilder::new()
                     .layer(TraceLayer::new_for_http())
                     .layer(HandleErrorLayer::new(Self::handle_timeout_error))
                     .timeout(Duration::from_secs(*HTTP_TIMEOUT))
                     .layer(cors)
-                    .layer(BufferLayer::new(4096))
-                    .layer(RateLimitLayer::new(
-                        *REQ_PER_SEC,
-                        Duration::from_secs(1),
-                    )),
+                    .layer(BufferLayer::new(4096)),
             );
 
         let router = router.fallback(Self::handle_404);
@@ -167,10 +476,13 @@ impl ApplicationServer {
 
         let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
 
-        axum::serve(listener, router.into_make_service())
-            .with_graceful_shutdown(Self::shutdown_signal())
-            .await
-            .unwrap_or_else(|e| panic!("Server error: {}", e));
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(Self::shutdown_signal())
+        .await
+        .unwrap_or_else(|e| panic!("Server error: {}", e));
 
         Ok(())
     }
@@ -7,7 +7,7 @@ use deadpool_redis::{Config, Connection, Pool as CachePool};
 #[derive(Clone)]
 pub struct AppState {
     db: DbPool,
-    cache: CachePool,
+    cache: Option<CachePool>,
 }
 
 impl AppState {
@@ -35,18 +35,27 @@ impl AppState {
             }
         };
 
+        Self::with_pool(pool, cache_url)
+    }
+
+    /// Shares `db` with other tasks of the process, e.g. the crawlers
+    pub fn with_pool(db: DbPool, cache_url: String) -> Self {
         let cache_pool = Config::from_url(cache_url)
             .create_pool(Some(deadpool_redis::Runtime::Tokio1));
         let cache_pool = match cache_pool {
-            Ok(pool) => pool,
+            Ok(pool) => Some(pool),
             Err(e) => {
-                tracing::info!("Error building redis pool: {}", e.to_string());
-                exit(1);
+                tracing::warn!(
+                    "Error building redis pool, falling back to in-memory \
+                     cache: {}",
+                    e.to_string()
+                );
+                None
             }
         };
 
         Self {
-            db: pool,
+            db,
             cache: cache_pool,
         }
     }
@@ -55,7 +64,15 @@ impl AppState {
         self.db.get().await.unwrap()
     }
 
-    pub async fn get_cache_connection(&self) -> Connection {
-        self.cache.get().await.unwrap()
+    pub async fn get_cache_connection(&self) -> Option<Connection> {
+        let cache = self.cache.as_ref()?;
+
+        match cache.get().await {
+            Ok(connection) => Some(connection),
+            Err(e) => {
+                tracing::warn!("Error getting redis connection: {}", e);
+                None
+            }
+        }
     }
 }
@@ -0,0 +1,28 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use utoipa::OpenApi;
+use webserver::openapi::ApiDoc;
+
+/// Writes the OpenAPI document derived from the handlers, the one the client
+/// is generated from
+#[derive(Parser)]
+struct Args {
+    #[clap(long, default_value = "swagger.yml")]
+    output: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let document = ApiDoc::openapi()
+        .to_yaml()
+        .context("Failed to serialize the OpenAPI document")?;
+
+    std::fs::write(&args.output, document).with_context(|| {
+        format!("Failed to write {}", args.output.display())
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,434 @@
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::body::{to_bytes, Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::response::Parts;
+use axum::http::{HeaderName, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use deadpool_redis::redis;
+use lru::LruCache;
+use orm::crawler_state::{CrawlerNameDb, CrawlerStateDb};
+use serde::{Deserialize, Serialize};
+
+use crate::appstate::AppState;
+use crate::service::crawler_state::CrawlerStateService;
+
+const CACHE_KEY_PREFIX: &str = "namada-indexer:response";
+
+/// How long the crawler states keys are built from are reused, so that cache
+/// hits don't query the database
+const VERSION_TTL: Duration = Duration::from_secs(1);
+
+/// Caches serialized responses in redis, or in memory when redis is not
+/// reachable. Keys embed the state of the crawlers the response depends on,
+/// so entries become unreachable as soon as one of those crawlers advances.
+#[derive(Clone)]
+pub struct ResponseCache {
+    app_state: AppState,
+    crawler_state_service: CrawlerStateService,
+    versions: Arc<Mutex<HashMap<String, (Instant, String)>>>,
+    version_ttl: Duration,
+    fallback: Arc<Mutex<LruCache<String, Bytes>>>,
+    ttl: u64,
+}
+
+/// Status and headers of a cached response, stored in front of its body
+#[derive(Serialize, Deserialize)]
+struct CachedHead {
+    status: u16,
+    headers: Vec<(String, Vec<u8>)>,
+}
+
+impl ResponseCache {
+    pub fn new(app_state: AppState, ttl: u64, lru_capacity: usize) -> Self {
+        let lru_capacity =
+            NonZeroUsize::new(lru_capacity).unwrap_or(NonZeroUsize::MIN);
+
+        Self {
+            crawler_state_service: CrawlerStateService::new(app_state.clone()),
+            app_state,
+            versions: Arc::default(),
+            version_ttl: VERSION_TTL,
+            fallback: Arc::new(Mutex::new(LruCache::new(lru_capacity))),
+            ttl,
+        }
+    }
+
+    async fn key(
+        &self,
+        crawlers: &[CrawlerNameDb],
+        uri: &str,
+    ) -> Option<String> {
+        let version = self.version(crawlers).await?;
+
+        Some(format!("{}:{}:{}", CACHE_KEY_PREFIX, version, uri))
+    }
+
+    async fn version(&self, crawlers: &[CrawlerNameDb]) -> Option<String> {
+        let crawlers_key = crawlers
+            .iter()
+            .map(|crawler| crawler.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        if let Some((fetched_at, version)) =
+            self.versions.lock().unwrap().get(&crawlers_key)
+        {
+            if fetched_at.elapsed() < self.version_ttl {
+                return Some(version.clone());
+            }
+        }
+
+        let states = self
+            .crawler_state_service
+            .get_states(crawlers.to_vec())
+            .await
+            .map_err(|e| tracing::warn!("Error building cache key: {}", e))
+            .ok()?;
+        let version = Self::crawlers_version(states);
+
+        self.versions
+            .lock()
+            .unwrap()
+            .insert(crawlers_key, (Instant::now(), version.clone()));
+
+        Some(version)
+    }
+
+    fn crawlers_version(mut states: Vec<CrawlerStateDb>) -> String {
+        states.sort_by_key(|state| state.name.to_string());
+
+        states
+            .iter()
+            .map(Self::crawler_version)
+            .collect::<Vec<_>>()
+            .join(":")
+    }
+
+    fn crawler_version(state: &CrawlerStateDb) -> String {
+        format!(
+            "{}-{}-{}-{}",
+            state.name,
+            state.last_processed_block.unwrap_or_default(),
+            state.last_processed_epoch.unwrap_or_default(),
+            state.timestamp.and_utc().timestamp_millis()
+        )
+    }
+
+    async fn get(&self, key: &str) -> Option<Bytes> {
+        if let Some(mut conn) = self.app_state.get_cache_connection().await {
+            let value = redis::cmd("GET")
+                .arg(key)
+                .query_async::<_, Option<Vec<u8>>>(&mut conn)
+                .await;
+
+            match value {
+                Ok(value) => return value.map(Bytes::from),
+                Err(e) => tracing::warn!("Error reading from redis: {}", e),
+            }
+        }
+
+        self.fallback.lock().unwrap().get(key).cloned()
+    }
+
+    async fn set(&self, key: String, value: Bytes) {
+        if let Some(mut conn) = self.app_state.get_cache_connection().await {
+            let result = redis::cmd("SET")
+                .arg(&key)
+                .arg(value.as_ref())
+                .arg("EX")
+                .arg(self.ttl)
+                .query_async::<_, ()>(&mut conn)
+                .await;
+
+            match result {
+                Ok(()) => return,
+                Err(e) => tracing::warn!("Error writing to redis: {}", e),
+            }
+        }
+
+        self.fallback.lock().unwrap().put(key, value);
+    }
+}
+
+/// Stores the length of the serialized head, the head then the body
+fn encode(parts: &Parts, body: &Bytes) -> Option<Bytes> {
+    let head = CachedHead {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+            .collect(),
+    };
+    let head = serde_json::to_vec(&head)
+        .map_err(|e| tracing::warn!("Error serializing response head: {}", e))
+        .ok()?;
+
+    let mut value = Vec::with_capacity(4 + head.len() + body.len());
+    value.extend_from_slice(&(head.len() as u32).to_be_bytes());
+    value.extend_from_slice(&head);
+    value.extend_from_slice(body);
+
+    Some(Bytes::from(value))
+}
+
+fn decode(value: Bytes) -> Option<Response> {
+    let head_len =
+        u32::from_be_bytes(value.get(..4)?.try_into().ok()?) as usize;
+    let head: CachedHead =
+        serde_json::from_slice(value.get(4..4 + head_len)?).ok()?;
+
+    let mut response = Response::new(Body::from(value.slice(4 + head_len..)));
+    *response.status_mut() = StatusCode::from_u16(head.status).ok()?;
+    for (name, value) in head.headers {
+        response.headers_mut().append(
+            HeaderName::try_from(name).ok()?,
+            HeaderValue::from_bytes(&value).ok()?,
+        );
+    }
+
+    Some(response)
+}
+
+#[derive(Clone)]
+pub struct CacheLayerState {
+    cache: ResponseCache,
+    crawlers: Vec<CrawlerNameDb>,
+}
+
+impl CacheLayerState {
+    pub fn new(cache: ResponseCache, crawlers: &[CrawlerNameDb]) -> Self {
+        Self {
+            cache,
+            crawlers: crawlers.to_vec(),
+        }
+    }
+}
+
+/// Serves successful GET responses from the cache, storing them on a miss.
+pub async fn cache_response(
+    State(state): State<CacheLayerState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+
+    let uri = request.uri().to_string();
+    let Some(key) = state.cache.key(&state.crawlers, &uri).await else {
+        return next.run(request).await;
+    };
+
+    if let Some(response) = state.cache.get(&key).await.and_then(decode) {
+        return response;
+    }
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!("Error buffering response body: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    if let Some(value) = encode(&parts, &body) {
+        state.cache.set(key, value).await;
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::middleware::from_fn_with_state;
+    use axum::routing::get;
+    use axum::Router;
+    use diesel::{ExpressionMethods, RunQueryDsl};
+    use orm::crawler_state::BlockStateInsertDb;
+    use orm::schema::crawler_state;
+    use shared::crawler_state::{BlockCrawlerState, CrawlerName};
+    use test_helpers::db::TestDb;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    /// Routes counting how many times their handler ran, behind the cache
+    fn cached_router(cache: ResponseCache, calls: Arc<AtomicUsize>) -> Router {
+        let ok_calls = calls.clone();
+        let not_found_calls = calls;
+
+        Router::new()
+            .route(
+                "/ok",
+                get(move || {
+                    ok_calls.fetch_add(1, Ordering::SeqCst);
+                    async { ([("x-total-count", "1")], "{}") }
+                })
+                .post(|| async { "{}" }),
+            )
+            .route(
+                "/missing",
+                get(move || {
+                    not_found_calls.fetch_add(1, Ordering::SeqCst);
+                    async { StatusCode::NOT_FOUND }
+                }),
+            )
+            .layer(from_fn_with_state(
+                CacheLayerState::new(cache, &[CrawlerNameDb::Transactions]),
+                cache_response,
+            ))
+    }
+
+    fn request(method: Method, uri: &str) -> Request {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    async fn set_transactions_state(db: &TestDb, last_processed_block: u32) {
+        db.run_test(move |conn| {
+            diesel::insert_into(crawler_state::table)
+                .values(BlockStateInsertDb::from((
+                    CrawlerName::Transactions,
+                    BlockCrawlerState {
+                        last_processed_block,
+                        timestamp: 1_700_000_000,
+                    },
+                )))
+                .on_conflict(crawler_state::name)
+                .do_update()
+                .set(
+                    crawler_state::last_processed_block
+                        .eq(last_processed_block as i32),
+                )
+                .execute(conn)?;
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    /// Test that a cache hit keeps the status and headers of the response
+    /// and is invalidated once the crawler advances.
+    #[tokio::test]
+    async fn test_cache_response_hit_and_invalidation() {
+        let db = TestDb::new();
+        set_transactions_state(&db, 1).await;
+
+        let app_state =
+            AppState::with_pool(db.pool(), "redis://127.0.0.1:1".to_string());
+        let cache = ResponseCache {
+            version_ttl: Duration::ZERO,
+            ..ResponseCache::new(app_state, 60, 16)
+        };
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = cached_router(cache, calls.clone());
+
+        for _ in 0..2 {
+            let response = router
+                .clone()
+                .oneshot(request(Method::GET, "/ok"))
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()["x-total-count"], "1");
+            assert_eq!(
+                response.headers()["content-type"],
+                "text/plain; charset=utf-8"
+            );
+            assert_eq!(
+                to_bytes(response.into_body(), usize::MAX).await.unwrap(),
+                "{}"
+            );
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        set_transactions_state(&db, 2).await;
+        router
+            .clone()
+            .oneshot(request(Method::GET, "/ok"))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Test that only successful GET responses are cached.
+    #[tokio::test]
+    async fn test_cache_response_skips_non_get_and_non_ok() {
+        let db = TestDb::new();
+        set_transactions_state(&db, 1).await;
+
+        let app_state =
+            AppState::with_pool(db.pool(), "redis://127.0.0.1:1".to_string());
+        let cache = ResponseCache::new(app_state, 60, 16);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = cached_router(cache.clone(), calls.clone());
+
+        for _ in 0..2 {
+            let response = router
+                .clone()
+                .oneshot(request(Method::GET, "/missing"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let response = router
+            .clone()
+            .oneshot(request(Method::POST, "/ok"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let post_key = cache
+            .key(&[CrawlerNameDb::Transactions], "/ok")
+            .await
+            .unwrap();
+        assert!(cache.get(&post_key).await.is_none());
+    }
+
+    /// Test that the version doesn't depend on the order of the crawlers.
+    #[test]
+    fn test_crawlers_version_is_sorted() {
+        let state = |name, last_processed_block| CrawlerStateDb {
+            name,
+            last_processed_block: Some(last_processed_block),
+            first_block_in_epoch: None,
+            last_processed_epoch: None,
+            timestamp: chrono::DateTime::from_timestamp(0, 0)
+                .unwrap()
+                .naive_utc(),
+            first_processed_block: None,
+        };
+
+        assert_eq!(
+            ResponseCache::crawlers_version(vec![
+                state(CrawlerNameDb::Transactions, 2),
+                state(CrawlerNameDb::Chain, 1),
+            ]),
+            ResponseCache::crawlers_version(vec![
+                state(CrawlerNameDb::Chain, 1),
+                state(CrawlerNameDb::Transactions, 2),
+            ])
+        );
+    }
+}
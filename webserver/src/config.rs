@@ -1,9 +1,39 @@
+use std::net::IpAddr;
+use std::str::FromStr;
+
 #[derive(clap::ValueEnum, Clone, Debug, Copy)]
 pub enum CargoEnv {
     Development,
     Production,
 }
 
+/// Number of tokens a request to a given route consumes from the client
+/// budget, parsed from `<route>=<cost>`
+#[derive(Clone, Debug)]
+pub struct RouteCost {
+    pub route: String,
+    pub cost: u64,
+}
+
+impl FromStr for RouteCost {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (route, cost) = value
+            .split_once('=')
+            .ok_or(format!("Invalid route cost: {}", value))?;
+        let cost = cost
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| format!("Invalid route cost {}: {}", value, e))?;
+
+        Ok(Self {
+            route: route.trim().to_string(),
+            cost,
+        })
+    }
+}
+
 #[derive(clap::Parser, Clone)]
 pub struct AppConfig {
     #[clap(long, env, default_value = "5001")]
@@ -12,12 +42,54 @@ pub struct AppConfig {
     #[clap(long, env)]
     pub cache_url: String,
 
+    /// Seconds a cached response is kept in redis
+    #[clap(long, env, default_value = "3600")]
+    pub cache_ttl: u64,
+
+    /// Number of responses kept in memory when redis is unavailable
+    #[clap(long, env, default_value = "1024")]
+    pub cache_lru_capacity: usize,
+
+    /// Seconds clients and CDNs may reuse a response without revalidating it
+    #[clap(long, env, default_value = "5")]
+    pub cache_control_max_age: u64,
+
     #[clap(long, env)]
     pub database_url: String,
 
+    /// Requests per second allowed for each client, unlimited if unset
     #[clap(long, env)]
     pub rps: Option<u64>,
 
+    /// Per route request costs, e.g. `/api/v1/pos/validator/all=5`
+    #[clap(long, env, value_delimiter = ',')]
+    pub rate_limit_costs: Vec<RouteCost>,
+
+    /// API keys, sent in the `x-api-key` header, allowed a higher quota
+    #[clap(long, env, value_delimiter = ',')]
+    pub api_keys: Vec<String>,
+
+    /// Requests per second allowed for allow-listed API keys
+    #[clap(long, env)]
+    pub api_key_rps: Option<u64>,
+
+    /// IPs of the reverse proxies whose `x-forwarded-for` and `x-real-ip`
+    /// headers are trusted to identify rate limited clients
+    #[clap(long, env, value_delimiter = ',')]
+    pub trusted_proxies: Vec<IpAddr>,
+
     #[clap(long, env)]
     pub tendermint_url: String,
+
+    /// Number of blocks over which validators uptime is computed
+    #[clap(long, env, value_delimiter = ',', default_value = "100,1000,10000")]
+    pub uptime_windows: Vec<u64>,
+
+    /// Maximum nesting depth of a GraphQL query
+    #[clap(long, env, default_value = "10")]
+    pub graphql_max_depth: usize,
+
+    /// Maximum complexity, i.e. number of resolved fields, of a GraphQL query
+    #[clap(long, env, default_value = "500")]
+    pub graphql_max_complexity: usize,
 }
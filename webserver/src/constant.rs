@@ -1 +1,4 @@
 pub const ITEM_PER_PAGE: u64 = 30;
+pub const MAX_ITEM_PER_PAGE: u64 = 100;
+pub const MAX_BATCH_REQUEST_BODY_SIZE: usize = 64 * 1024;
+pub const EXPORT_CHUNK_SIZE: i64 = 1000;
@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Clone, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BalancesDto {
+    #[validate(length(min = 1, max = 100))]
+    pub addresses: Vec<String>,
+    #[validate(length(min = 1, max = 50))]
+    pub tokens: Option<Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::extract::DefaultBodyLimit;
+    use axum::http::{header, Request, StatusCode};
+    use axum::routing::post;
+    use axum::{Json, Router};
+    use tower::ServiceExt;
+
+    use super::*;
+    use crate::constant::MAX_BATCH_REQUEST_BODY_SIZE;
+
+    fn addresses(count: usize) -> Vec<String> {
+        (0..count)
+            .map(|index| format!("tnam1qq{:0>38}", index))
+            .collect()
+    }
+
+    fn balances_dto(
+        addresses_count: usize,
+        tokens_count: usize,
+    ) -> BalancesDto {
+        BalancesDto {
+            addresses: addresses(addresses_count),
+            tokens: Some(addresses(tokens_count)),
+        }
+    }
+
+    /// Route limiting the body size as the batch balances endpoint does
+    fn router() -> Router {
+        Router::new().route(
+            "/account/balances",
+            post(|Json(body): Json<BalancesDto>| async move {
+                match body.validate() {
+                    Ok(()) => StatusCode::OK,
+                    Err(_) => StatusCode::BAD_REQUEST,
+                }
+            })
+            .layer(DefaultBodyLimit::max(MAX_BATCH_REQUEST_BODY_SIZE)),
+        )
+    }
+
+    async fn post_balances(body: String) -> StatusCode {
+        router()
+            .oneshot(
+                Request::post("/account/balances")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    /// Test that the number of addresses and tokens is bounded.
+    #[test]
+    fn test_balances_dto_limits() {
+        assert!(balances_dto(1, 1).validate().is_ok());
+        assert!(balances_dto(100, 50).validate().is_ok());
+        assert!(balances_dto(0, 1).validate().is_err());
+        assert!(balances_dto(101, 1).validate().is_err());
+        assert!(balances_dto(1, 0).validate().is_err());
+        assert!(balances_dto(1, 51).validate().is_err());
+
+        let without_tokens = BalancesDto {
+            addresses: addresses(1),
+            tokens: None,
+        };
+        assert!(without_tokens.validate().is_ok());
+    }
+
+    /// Test that the largest valid request fits the body limit while a larger
+    /// body is refused before being parsed.
+    #[tokio::test]
+    async fn test_balances_body_limit() {
+        let largest = serde_json::to_string(&balances_dto(100, 50)).unwrap();
+        assert!(largest.len() < MAX_BATCH_REQUEST_BODY_SIZE);
+        assert_eq!(post_balances(largest).await, StatusCode::OK);
+
+        let too_many = serde_json::to_string(&balances_dto(101, 1)).unwrap();
+        assert_eq!(post_balances(too_many).await, StatusCode::BAD_REQUEST);
+
+        // Within the count limits, only the size is refused
+        let oversized = serde_json::to_string(&BalancesDto {
+            addresses: vec!["a".repeat(MAX_BATCH_REQUEST_BODY_SIZE)],
+            tokens: None,
+        })
+        .unwrap();
+        assert_eq!(
+            post_balances(oversized).await,
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+use validator::Validate;
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct TokenSupplyDto {
+    pub from_epoch: Option<u64>,
+    pub to_epoch: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct TokenHoldersDto {
+    #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
+    pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
+    pub exclude_internal: Option<bool>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct TokenDistributionDto {
+    pub exclude_internal: Option<bool>,
+}
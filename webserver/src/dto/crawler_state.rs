@@ -1,12 +1,21 @@
 use serde::{Deserialize, Serialize};
 use strum::{Display, VariantArray};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
 #[derive(
-    Clone, Debug, Serialize, Deserialize, Display, VariantArray, PartialEq,
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    Display,
+    VariantArray,
+    PartialEq,
+    ToSchema,
 )]
 #[serde(rename_all = "camelCase")]
 #[strum(serialize_all = "camelCase")]
+#[schema(as = CrawlerName)]
 pub enum CrawlerNameDto {
     Chain,
     Governance,
@@ -16,7 +25,8 @@ pub enum CrawlerNameDto {
     Transactions,
 }
 
-#[derive(Clone, Serialize, Deserialize, Validate)]
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct CrawlerStateQueryParams {
     pub crawler_names: Option<Vec<CrawlerNameDto>>,
 }
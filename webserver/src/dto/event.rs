@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(as = EventKind)]
+pub enum EventKindDto {
+    Block,
+    Transaction,
+    ProposalStatus,
+    ValidatorSet,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EventsQueryParams {
+    pub kind: Option<Vec<EventKindDto>>,
+    pub address: Option<String>,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[schema(as = ExportFormat)]
+pub enum ExportFormatDto {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ExportQueryParams {
+    pub format: Option<ExportFormatDto>,
+    pub from_height: Option<u64>,
+    pub to_height: Option<u64>,
+    /// Unix timestamp in seconds, resolved to the first block at or after it
+    pub from_timestamp: Option<i64>,
+    /// Unix timestamp in seconds, resolved to the last block at or before it
+    pub to_timestamp: Option<i64>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct EpochExportQueryParams {
+    pub format: Option<ExportFormatDto>,
+    pub from_epoch: Option<u64>,
+    pub to_epoch: Option<u64>,
+}
@@ -1,8 +1,14 @@
+use async_graphql::Enum;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
+#[graphql(name = "ProposalStatusFilter")]
+#[schema(as = ProposalStatusFilter)]
 pub enum ProposalStatus {
     Pending,
     VotingPeriod,
@@ -10,7 +16,9 @@ pub enum ProposalStatus {
     Rejected,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum ProposalKind {
     Default,
@@ -19,17 +27,33 @@ pub enum ProposalKind {
     PgfFunding,
 }
 
-#[derive(Clone, Serialize, Deserialize, Validate)]
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
 pub struct ProposalQueryParams {
     #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
     pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
     pub status: Option<ProposalStatus>,
     pub kind: Option<ProposalKind>,
     pub pattern: Option<String>,
 }
 
-#[derive(Clone, Serialize, Deserialize, Validate)]
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
 pub struct ProposalVotesQueryparams {
     #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
     pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
+    /// Switches to keyset pagination, empty for the first page
+    pub cursor: Option<String>,
+    /// Counts the total items in keyset pagination mode
+    pub with_count: Option<bool>,
 }
@@ -1,3 +1,9 @@
+pub mod balance;
+pub mod chain;
 pub mod crawler_state;
+pub mod event;
+pub mod export;
 pub mod governance;
 pub mod pos;
+pub mod transaction;
+pub mod utils;
@@ -1,8 +1,14 @@
+use async_graphql::Enum;
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
+#[graphql(name = "ValidatorStateFilter")]
+#[schema(as = ValidatorStateFilter)]
 pub enum ValidatorStateDto {
     Consensus,
     BelowCapacity,
@@ -26,59 +32,166 @@ impl ValidatorStateDto {
     }
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
+#[graphql(name = "SortOrder")]
+#[schema(as = SortOrder)]
 pub enum OrderByDto {
     Asc,
     Desc,
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
+#[graphql(name = "ValidatorSortField")]
+#[schema(as = ValidatorSortField)]
 pub enum ValidatorSortFieldDto {
     VotingPower,
     Commission,
     Rank,
 }
 
-#[derive(Clone, Serialize, Deserialize, Validate)]
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
 #[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
 pub struct ValidatorQueryParams {
     #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
     pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
     pub state: Option<Vec<ValidatorStateDto>>,
     pub sort_field: Option<ValidatorSortFieldDto>,
     pub sort_order: Option<OrderByDto>,
 }
 
-#[derive(Clone, Serialize, Deserialize, Validate)]
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct AllValidatorsQueryParams {
     pub state: Option<Vec<ValidatorStateDto>>,
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
+#[schema(as = MyValidatorKind)]
 pub enum MyValidatorKindDto {
     WithBonds,
     WithUnbonds,
 }
 
-#[derive(Clone, Serialize, Deserialize, Validate)]
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct MyValidatorQueryParams {
+    #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
+    pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
+    #[validate(length(min = 1, max = 100))]
+    pub address: Vec<String>,
+    pub kind: MyValidatorKindDto,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
 pub struct BondsDto {
     #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
     pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
 }
 
-#[derive(Clone, Serialize, Deserialize, Validate)]
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
 pub struct UnbondsDto {
     #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
     pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
 }
 
-#[derive(Clone, Serialize, Deserialize, Validate)]
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ValidatorBlocksDto {
+    #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
+    pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
+    /// Switches to keyset pagination, empty for the first page
+    pub cursor: Option<String>,
+    /// Counts the total items in keyset pagination mode
+    pub with_count: Option<bool>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ValidatorDelegationsDto {
+    #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
+    pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
+    pub sort_order: Option<OrderByDto>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EstimatedRewardsDto {
+    pub amount: String,
+    #[validate(range(min = 1, max = 100000))]
+    #[param(minimum = 1, maximum = 100000)]
+    pub epochs: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
 pub struct WithdrawsDto {
     #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
     pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
     #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
     pub epoch: Option<u64>,
 }
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct RedelegationsDto {
+    #[validate(range(min = 1, max = 10000))]
+    #[param(minimum = 1, maximum = 10000)]
+    pub page: Option<u64>,
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct RewardHistoryDto {
+    pub from_epoch: Option<u64>,
+    pub to_epoch: Option<u64>,
+}
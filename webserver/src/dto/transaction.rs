@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+use validator::Validate;
+
+#[derive(Clone, Serialize, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct TransactionHistoryQueryParams {
+    #[validate(range(min = 1, max = 100))]
+    #[param(minimum = 1, maximum = 100)]
+    pub per_page: Option<u64>,
+    /// Keyset cursor, empty or omitted for the first page
+    pub cursor: Option<String>,
+    /// Counts the total items
+    pub with_count: Option<bool>,
+}
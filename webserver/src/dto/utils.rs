@@ -0,0 +1,38 @@
+use crate::constant::{ITEM_PER_PAGE, MAX_ITEM_PER_PAGE};
+
+/// Page size requested by the client, capped to `MAX_ITEM_PER_PAGE`
+pub fn per_page(per_page: Option<u64>) -> u64 {
+    per_page
+        .unwrap_or(ITEM_PER_PAGE)
+        .clamp(1, MAX_ITEM_PER_PAGE)
+}
+
+/// Keyset cursor sent by the client, an empty cursor asks for the first page.
+/// Returns the invalid cursor as error.
+pub fn cursor(cursor: &str) -> Result<Option<i32>, String> {
+    if cursor.is_empty() {
+        return Ok(None);
+    }
+
+    cursor
+        .parse::<i32>()
+        .map(Some)
+        .map_err(|_| cursor.to_string())
+}
+
+/// Keyset cursor made of two positions, `<first>_<second>`, for keys that
+/// aren't unique on their own. An empty cursor asks for the first page.
+/// Returns the invalid cursor as error.
+pub fn composite_cursor(cursor: &str) -> Result<Option<(i32, i32)>, String> {
+    if cursor.is_empty() {
+        return Ok(None);
+    }
+
+    cursor
+        .split_once('_')
+        .and_then(|(first, second)| {
+            Some((first.parse::<i32>().ok()?, second.parse::<i32>().ok()?))
+        })
+        .map(Some)
+        .ok_or_else(|| cursor.to_string())
+}
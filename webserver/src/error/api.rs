@@ -4,6 +4,7 @@ use thiserror::Error;
 use super::balance::BalanceError;
 use super::chain::ChainError;
 use super::crawler_state::CrawlerStateError;
+use super::export::ExportError;
 use super::gas::GasError;
 use super::governance::GovernanceError;
 use super::pos::PoSError;
@@ -28,6 +29,8 @@ pub enum ApiError {
     GasError(#[from] GasError),
     #[error(transparent)]
     CrawlerStateError(#[from] CrawlerStateError),
+    #[error(transparent)]
+    ExportError(#[from] ExportError),
 }
 
 impl IntoResponse for ApiError {
@@ -41,6 +44,7 @@ impl IntoResponse for ApiError {
             ApiError::RevealedPkError(error) => error.into_response(),
             ApiError::GasError(error) => error.into_response(),
             ApiError::CrawlerStateError(error) => error.into_response(),
+            ApiError::ExportError(error) => error.into_response(),
         }
     }
 }
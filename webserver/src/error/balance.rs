@@ -8,6 +8,8 @@ use crate::response::api::ApiErrorResponse;
 pub enum BalanceError {
     #[error("Proposal {0} not found")]
     NotFound(u64),
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Unknown error: {0}")]
@@ -18,6 +20,7 @@ impl IntoResponse for BalanceError {
     fn into_response(self) -> Response {
         let status_code = match self {
             BalanceError::NotFound(_) => StatusCode::NOT_FOUND,
+            BalanceError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
             BalanceError::Unknown(_) | BalanceError::Database(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
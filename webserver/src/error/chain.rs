@@ -6,6 +6,8 @@ use crate::response::api::ApiErrorResponse;
 
 #[derive(Error, Debug)]
 pub enum ChainError {
+    #[error("Token {0} not found")]
+    TokenNotFound(String),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Unknown error: {0}")]
@@ -15,6 +17,7 @@ pub enum ChainError {
 impl IntoResponse for ChainError {
     fn into_response(self) -> Response {
         let status_code = match self {
+            ChainError::TokenNotFound(_) => StatusCode::NOT_FOUND,
             ChainError::Unknown(_) | ChainError::Database(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
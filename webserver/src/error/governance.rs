@@ -10,6 +10,8 @@ pub enum GovernanceError {
     TooShortPattern(usize),
     #[error("Proposal {0} not found")]
     NotFound(u64),
+    #[error("Invalid cursor {0}")]
+    InvalidCursor(String),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Unknown error: {0}")]
@@ -19,7 +21,8 @@ pub enum GovernanceError {
 impl IntoResponse for GovernanceError {
     fn into_response(self) -> Response {
         let status_code = match self {
-            GovernanceError::TooShortPattern(_) => StatusCode::BAD_REQUEST,
+            GovernanceError::TooShortPattern(_)
+            | GovernanceError::InvalidCursor(_) => StatusCode::BAD_REQUEST,
             GovernanceError::NotFound(_) => StatusCode::NOT_FOUND,
             GovernanceError::Unknown(_) | GovernanceError::Database(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
@@ -2,6 +2,7 @@ pub mod api;
 pub mod balance;
 pub mod chain;
 pub mod crawler_state;
+pub mod export;
 pub mod gas;
 pub mod governance;
 pub mod pos;
@@ -6,6 +6,14 @@ use crate::response::api::ApiErrorResponse;
 
 #[derive(Error, Debug)]
 pub enum PoSError {
+    #[error("Validator {0} not found")]
+    ValidatorNotFound(String),
+    #[error("Invalid amount {0}")]
+    InvalidAmount(String),
+    #[error("Invalid cursor {0}")]
+    InvalidCursor(String),
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Unknown error: {0}")]
@@ -15,6 +23,10 @@ pub enum PoSError {
 impl IntoResponse for PoSError {
     fn into_response(self) -> Response {
         let status_code = match self {
+            PoSError::ValidatorNotFound(_) => StatusCode::NOT_FOUND,
+            PoSError::InvalidAmount(_)
+            | PoSError::InvalidCursor(_)
+            | PoSError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
             PoSError::Unknown(_) | PoSError::Database(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
@@ -10,6 +10,8 @@ pub enum TransactionError {
     InvalidTxId,
     #[error("The tx id {0} does not exist")]
     TxIdNotFound(String),
+    #[error("Invalid cursor {0}")]
+    InvalidCursor(String),
     #[error("Database error: {0}")]
     Database(String),
     #[error("Rpc error: {0}")]
@@ -21,7 +23,8 @@ pub enum TransactionError {
 impl IntoResponse for TransactionError {
     fn into_response(self) -> Response {
         let status_code = match self {
-            TransactionError::InvalidTxId => StatusCode::BAD_REQUEST,
+            TransactionError::InvalidTxId
+            | TransactionError::InvalidCursor(_) => StatusCode::BAD_REQUEST,
             TransactionError::TxIdNotFound(_) => StatusCode::NOT_FOUND,
             TransactionError::Unknown(_)
             | TransactionError::Database(_)
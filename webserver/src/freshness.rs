@@ -0,0 +1,298 @@
+use axum::body::{to_bytes, Body};
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use orm::crawler_state::{CrawlerNameDb, CrawlerStateDb};
+use sha2::{Digest, Sha256};
+
+use crate::appstate::AppState;
+use crate::service::crawler_state::CrawlerStateService;
+
+pub const INDEXED_BLOCK_HEIGHT: &str = "x-indexed-block-height";
+pub const INDEXED_EPOCH: &str = "x-indexed-epoch";
+
+#[derive(Clone)]
+pub struct FreshnessLayerState {
+    crawler_state_service: CrawlerStateService,
+    crawlers: Vec<CrawlerNameDb>,
+    max_age: u64,
+}
+
+impl FreshnessLayerState {
+    pub fn new(
+        app_state: AppState,
+        crawlers: &[CrawlerNameDb],
+        max_age: u64,
+    ) -> Self {
+        Self {
+            crawler_state_service: CrawlerStateService::new(app_state),
+            crawlers: crawlers.to_vec(),
+            max_age,
+        }
+    }
+}
+
+/// Progress of the crawlers a response depends on. Heights and epochs are
+/// the lowest ones processed by those crawlers, so the response reflects at
+/// least that state of the chain.
+struct Freshness {
+    version: String,
+    block_height: Option<i32>,
+    epoch: Option<i32>,
+    last_modified: Option<chrono::NaiveDateTime>,
+}
+
+impl Freshness {
+    fn from(mut states: Vec<CrawlerStateDb>) -> Self {
+        states.sort_by_key(|state| state.name.to_string());
+
+        let version = states
+            .iter()
+            .map(|state| {
+                format!(
+                    "{}-{}-{}",
+                    state.name,
+                    state.last_processed_block.unwrap_or_default(),
+                    state.last_processed_epoch.unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(":");
+
+        Self {
+            version,
+            block_height: states
+                .iter()
+                .filter_map(|state| state.last_processed_block)
+                .min(),
+            epoch: states
+                .iter()
+                .filter_map(|state| state.last_processed_epoch)
+                .min(),
+            last_modified: states.iter().map(|state| state.timestamp).max(),
+        }
+    }
+
+    fn etag(&self, body: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.version.as_bytes());
+        hasher.update(body);
+
+        format!("\"{:x}\"", hasher.finalize())
+    }
+}
+
+/// Tags successful GET responses with an `ETag` covering the crawlers state
+/// and the body, answering `304 Not Modified` when the client already holds
+/// that version.
+pub async fn conditional_get(
+    State(state): State<FreshnessLayerState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::GET {
+        return next.run(request).await;
+    }
+
+    let freshness = match state
+        .crawler_state_service
+        .get_states(state.crawlers.clone())
+        .await
+    {
+        Ok(states) => Freshness::from(states),
+        Err(e) => {
+            tracing::warn!("Error reading crawlers state: {}", e);
+            return next.run(request).await;
+        }
+    };
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+
+    let response = next.run(request).await;
+    if response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!("Error buffering response body: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let etag = freshness.etag(&body);
+    let headers = freshness_headers(&freshness, &etag, state.max_age);
+
+    if if_none_match.is_some_and(|value| matches_etag(&value, &etag)) {
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        response.headers_mut().extend(headers);
+
+        return response;
+    }
+
+    parts.headers.extend(headers);
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn freshness_headers(
+    freshness: &Freshness,
+    etag: &str,
+    max_age: u64,
+) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+
+    if let Ok(etag) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, etag);
+    }
+    if let Ok(cache_control) =
+        HeaderValue::from_str(&format!("public, max-age={}", max_age))
+    {
+        headers.insert(header::CACHE_CONTROL, cache_control);
+    }
+    if let Some(last_modified) = freshness.last_modified.and_then(|timestamp| {
+        HeaderValue::from_str(
+            &timestamp
+                .and_utc()
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string(),
+        )
+        .ok()
+    }) {
+        headers.insert(header::LAST_MODIFIED, last_modified);
+    }
+    if let Some(block_height) = freshness.block_height {
+        headers.insert(INDEXED_BLOCK_HEIGHT, HeaderValue::from(block_height));
+    }
+    if let Some(epoch) = freshness.epoch {
+        headers.insert(INDEXED_EPOCH, HeaderValue::from(epoch));
+    }
+
+    headers
+}
+
+/// `If-None-Match` holds either `*` or a list of, possibly weak, entity tags
+fn matches_etag(if_none_match: &HeaderValue, etag: &str) -> bool {
+    let Ok(if_none_match) = if_none_match.to_str() else {
+        return false;
+    };
+
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.trim_start_matches("W/") == etag
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::routing::get;
+    use axum::Router;
+    use diesel::{ExpressionMethods, RunQueryDsl};
+    use orm::crawler_state::BlockStateInsertDb;
+    use orm::schema::crawler_state;
+    use shared::crawler_state::{BlockCrawlerState, CrawlerName};
+    use test_helpers::db::TestDb;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn set_transactions_state(db: &TestDb, last_processed_block: u32) {
+        db.run_test(move |conn| {
+            diesel::insert_into(crawler_state::table)
+                .values(BlockStateInsertDb::from((
+                    CrawlerName::Transactions,
+                    BlockCrawlerState {
+                        last_processed_block,
+                        timestamp: 1_700_000_000,
+                    },
+                )))
+                .on_conflict(crawler_state::name)
+                .do_update()
+                .set(
+                    crawler_state::last_processed_block
+                        .eq(last_processed_block as i32),
+                )
+                .execute(conn)?;
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    fn get_request(if_none_match: Option<&HeaderValue>) -> Request {
+        let mut request = Request::builder().uri("/ok");
+        if let Some(if_none_match) = if_none_match {
+            request = request.header(header::IF_NONE_MATCH, if_none_match);
+        }
+
+        request.body(Body::empty()).unwrap()
+    }
+
+    /// Test that a client holding the current version gets `304 Not
+    /// Modified`, until the crawler advances.
+    #[tokio::test]
+    async fn test_conditional_get() {
+        let db = TestDb::new();
+        set_transactions_state(&db, 1).await;
+
+        let app_state =
+            AppState::with_pool(db.pool(), "redis://127.0.0.1:1".to_string());
+        let router = Router::new().route("/ok", get(|| async { "{}" })).layer(
+            axum::middleware::from_fn_with_state(
+                FreshnessLayerState::new(
+                    app_state,
+                    &[CrawlerNameDb::Transactions],
+                    5,
+                ),
+                conditional_get,
+            ),
+        );
+
+        let response = router.clone().oneshot(get_request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "public, max-age=5"
+        );
+        assert_eq!(response.headers()[INDEXED_BLOCK_HEIGHT], "1");
+        let etag = response.headers()[header::ETAG].clone();
+
+        let response = router
+            .clone()
+            .oneshot(get_request(Some(&etag)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers()[header::ETAG], etag);
+        assert!(to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .is_empty());
+
+        set_transactions_state(&db, 2).await;
+        let response = router
+            .clone()
+            .oneshot(get_request(Some(&etag)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers()[header::ETAG], etag);
+        assert_eq!(response.headers()[INDEXED_BLOCK_HEIGHT], "2");
+    }
+
+    /// Test that `If-None-Match` matches any listed, possibly weak, tag.
+    #[test]
+    fn test_matches_etag() {
+        let etag = "\"abc\"";
+
+        assert!(matches_etag(&HeaderValue::from_static("*"), etag));
+        assert!(matches_etag(&HeaderValue::from_static("\"abc\""), etag));
+        assert!(matches_etag(
+            &HeaderValue::from_static("\"xyz\", W/\"abc\""),
+            etag
+        ));
+        assert!(!matches_etag(&HeaderValue::from_static("\"xyz\""), etag));
+    }
+}
@@ -0,0 +1,114 @@
+use async_graphql::{Context, Object, Result};
+
+use super::page::{Page, UNPAGINATED_LIST_SIZE};
+use crate::dto::utils;
+use crate::response::balance::AddressBalance;
+use crate::response::governance::ProposalVote;
+use crate::response::pos::{Bond, MergedBond, Reward, Unbond};
+use crate::state::common::CommonState;
+
+/// Everything the indexer knows about an address, so that a single query can
+/// replace the balances, bonds, rewards and votes REST calls
+pub struct Account {
+    pub address: String,
+}
+
+#[Object]
+impl Account {
+    async fn address(&self) -> &str {
+        &self.address
+    }
+
+    #[graphql(complexity = "UNPAGINATED_LIST_SIZE * child_complexity")]
+    async fn balances(&self, ctx: &Context<'_>) -> Result<Vec<AddressBalance>> {
+        let state = ctx.data::<CommonState>()?;
+
+        Ok(state
+            .balance_service
+            .get_address_balances(self.address.clone())
+            .await?)
+    }
+
+    #[graphql(
+        complexity = "utils::per_page(per_page) as usize * child_complexity"
+    )]
+    async fn bonds(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 1, validator(minimum = 1, maximum = 10000))]
+        page: u64,
+        #[graphql(validator(minimum = 1, maximum = 100))] per_page: Option<u64>,
+    ) -> Result<Page<Bond>> {
+        let state = ctx.data::<CommonState>()?;
+        let per_page = utils::per_page(per_page);
+
+        let bonds = state
+            .pos_service
+            .get_bonds_by_address(self.address.clone(), page, per_page)
+            .await?;
+
+        Ok(Page::new(bonds, page, per_page))
+    }
+
+    #[graphql(
+        complexity = "utils::per_page(per_page) as usize * child_complexity"
+    )]
+    async fn merged_bonds(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 1, validator(minimum = 1, maximum = 10000))]
+        page: u64,
+        #[graphql(validator(minimum = 1, maximum = 100))] per_page: Option<u64>,
+    ) -> Result<Page<MergedBond>> {
+        let state = ctx.data::<CommonState>()?;
+        let per_page = utils::per_page(per_page);
+
+        let bonds = state
+            .pos_service
+            .get_merged_bonds_by_address(self.address.clone(), page, per_page)
+            .await?;
+
+        Ok(Page::new(bonds, page, per_page))
+    }
+
+    #[graphql(
+        complexity = "utils::per_page(per_page) as usize * child_complexity"
+    )]
+    async fn unbonds(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 1, validator(minimum = 1, maximum = 10000))]
+        page: u64,
+        #[graphql(validator(minimum = 1, maximum = 100))] per_page: Option<u64>,
+    ) -> Result<Page<Unbond>> {
+        let state = ctx.data::<CommonState>()?;
+        let per_page = utils::per_page(per_page);
+
+        let unbonds = state
+            .pos_service
+            .get_unbonds_by_address(self.address.clone(), page, per_page)
+            .await?;
+
+        Ok(Page::new(unbonds, page, per_page))
+    }
+
+    #[graphql(complexity = "UNPAGINATED_LIST_SIZE * child_complexity")]
+    async fn rewards(&self, ctx: &Context<'_>) -> Result<Vec<Reward>> {
+        let state = ctx.data::<CommonState>()?;
+
+        Ok(state
+            .pos_service
+            .get_rewards_by_address(self.address.clone())
+            .await?)
+    }
+
+    #[graphql(complexity = "UNPAGINATED_LIST_SIZE * child_complexity")]
+    async fn votes(&self, ctx: &Context<'_>) -> Result<Vec<ProposalVote>> {
+        let state = ctx.data::<CommonState>()?;
+
+        Ok(state
+            .gov_service
+            .find_governance_proposal_votes_by_voter(self.address.clone())
+            .await?)
+    }
+}
@@ -0,0 +1,5 @@
+pub mod account;
+pub mod page;
+pub mod query;
+pub mod relations;
+pub mod schema;
@@ -0,0 +1,42 @@
+use async_graphql::{OutputType, SimpleObject};
+
+use crate::response::governance::{Proposal, ProposalVote};
+use crate::response::pos::{
+    Bond, Delegation, MergedBond, Unbond, ValidatorWithId,
+};
+use crate::response::utils::Pagination;
+
+/// Number of items assumed when computing the complexity of lists that are
+/// not paginated, paginated lists count `per_page` items
+pub const UNPAGINATED_LIST_SIZE: usize = 10;
+
+#[derive(Clone, Debug, SimpleObject)]
+#[graphql(concrete(name = "ValidatorPage", params(ValidatorWithId)))]
+#[graphql(concrete(name = "DelegationPage", params(Delegation)))]
+#[graphql(concrete(name = "BondPage", params(Bond)))]
+#[graphql(concrete(name = "MergedBondPage", params(MergedBond)))]
+#[graphql(concrete(name = "UnbondPage", params(Unbond)))]
+#[graphql(concrete(name = "ProposalPage", params(Proposal)))]
+#[graphql(concrete(name = "ProposalVotePage", params(ProposalVote)))]
+pub struct Page<T: OutputType> {
+    pub results: Vec<T>,
+    pub pagination: Pagination,
+}
+
+impl<T: OutputType> Page<T> {
+    pub fn new(
+        (results, total_pages, total_items): (Vec<T>, u64, u64),
+        page: u64,
+        per_page: u64,
+    ) -> Self {
+        Self {
+            results,
+            pagination: Pagination {
+                page,
+                per_page,
+                total_pages,
+                total_items,
+            },
+        }
+    }
+}
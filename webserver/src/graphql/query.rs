@@ -0,0 +1,188 @@
+use async_graphql::{Context, Object, Result};
+
+use super::account::Account;
+use super::page::{Page, UNPAGINATED_LIST_SIZE};
+use crate::dto::governance::{ProposalKind, ProposalStatus};
+use crate::dto::pos::{OrderByDto, ValidatorSortFieldDto, ValidatorStateDto};
+use crate::dto::utils;
+use crate::response::balance::AddressBalance;
+use crate::response::chain::{Parameters, Token};
+use crate::response::governance::{Proposal, ProposalVote};
+use crate::response::pos::{ValidatorDetail, ValidatorWithId};
+use crate::response::transaction::{InnerTransaction, WrapperTransaction};
+use crate::state::common::CommonState;
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    #[graphql(
+        complexity = "utils::per_page(per_page) as usize * child_complexity"
+    )]
+    async fn validators(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 1, validator(minimum = 1, maximum = 10000))]
+        page: u64,
+        #[graphql(validator(minimum = 1, maximum = 100))] per_page: Option<u64>,
+        states: Option<Vec<ValidatorStateDto>>,
+        sort_field: Option<ValidatorSortFieldDto>,
+        sort_order: Option<OrderByDto>,
+    ) -> Result<Page<ValidatorWithId>> {
+        let state = ctx.data::<CommonState>()?;
+        let per_page = utils::per_page(per_page);
+        let states = states.unwrap_or_else(ValidatorStateDto::all);
+
+        let validators = state
+            .pos_service
+            .get_validators(
+                page,
+                per_page,
+                states,
+                sort_field,
+                sort_order,
+                &state.config.uptime_windows,
+            )
+            .await?;
+
+        Ok(Page::new(validators, page, per_page))
+    }
+
+    async fn validator(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+    ) -> Result<ValidatorDetail> {
+        let state = ctx.data::<CommonState>()?;
+
+        Ok(state
+            .pos_service
+            .get_validator(address, &state.config.uptime_windows)
+            .await?)
+    }
+
+    async fn total_voting_power(&self, ctx: &Context<'_>) -> Result<String> {
+        let state = ctx.data::<CommonState>()?;
+        let total_voting_power =
+            state.pos_service.get_total_voting_power().await?;
+
+        Ok(total_voting_power.to_string())
+    }
+
+    async fn account(&self, address: String) -> Account {
+        Account { address }
+    }
+
+    #[graphql(
+        complexity = "utils::per_page(per_page) as usize * child_complexity"
+    )]
+    async fn proposals(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 1, validator(minimum = 1, maximum = 10000))]
+        page: u64,
+        #[graphql(validator(minimum = 1, maximum = 100))] per_page: Option<u64>,
+        status: Option<ProposalStatus>,
+        kind: Option<ProposalKind>,
+        pattern: Option<String>,
+    ) -> Result<Page<Proposal>> {
+        let state = ctx.data::<CommonState>()?;
+        let per_page = utils::per_page(per_page);
+
+        let proposals = state
+            .gov_service
+            .find_governance_proposals(status, kind, pattern, page, per_page)
+            .await?;
+
+        Ok(Page::new(proposals, page, per_page))
+    }
+
+    async fn proposal(
+        &self,
+        ctx: &Context<'_>,
+        id: u64,
+    ) -> Result<Option<Proposal>> {
+        let state = ctx.data::<CommonState>()?;
+
+        Ok(state.gov_service.find_governance_proposal_by_id(id).await?)
+    }
+
+    #[graphql(
+        complexity = "utils::per_page(per_page) as usize * child_complexity"
+    )]
+    async fn proposal_votes(
+        &self,
+        ctx: &Context<'_>,
+        id: u64,
+        #[graphql(default = 1, validator(minimum = 1, maximum = 10000))]
+        page: u64,
+        #[graphql(validator(minimum = 1, maximum = 100))] per_page: Option<u64>,
+    ) -> Result<Page<ProposalVote>> {
+        let state = ctx.data::<CommonState>()?;
+        let per_page = utils::per_page(per_page);
+
+        let votes = state
+            .gov_service
+            .find_governance_proposal_votes(id, page, per_page)
+            .await?;
+
+        Ok(Page::new(votes, page, per_page))
+    }
+
+    #[graphql(complexity = "UNPAGINATED_LIST_SIZE * child_complexity")]
+    async fn balances(
+        &self,
+        ctx: &Context<'_>,
+        address: String,
+    ) -> Result<Vec<AddressBalance>> {
+        let state = ctx.data::<CommonState>()?;
+
+        Ok(state.balance_service.get_address_balances(address).await?)
+    }
+
+    async fn wrapper_transaction(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<WrapperTransaction>> {
+        let state = ctx.data::<CommonState>()?;
+
+        let wrapper_tx = state.transaction_service.get_wrapper_tx(id).await?;
+        let Some(mut wrapper_tx) = wrapper_tx else {
+            return Ok(None);
+        };
+
+        let inner_txs = state
+            .transaction_service
+            .get_inner_tx_by_wrapper_id(wrapper_tx.tx_id.clone())
+            .await?;
+        wrapper_tx.inner_transactions =
+            inner_txs.iter().map(InnerTransaction::to_short).collect();
+
+        Ok(Some(wrapper_tx))
+    }
+
+    async fn inner_transaction(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<Option<InnerTransaction>> {
+        let state = ctx.data::<CommonState>()?;
+
+        Ok(state.transaction_service.get_inner_tx(id).await?)
+    }
+
+    #[graphql(complexity = "UNPAGINATED_LIST_SIZE * child_complexity")]
+    async fn tokens(&self, ctx: &Context<'_>) -> Result<Vec<Token>> {
+        let state = ctx.data::<CommonState>()?;
+        let tokens = state.chain_service.find_tokens().await?;
+
+        Ok(tokens.into_iter().map(Token::from).collect())
+    }
+
+    async fn parameters(&self, ctx: &Context<'_>) -> Result<Parameters> {
+        let state = ctx.data::<CommonState>()?;
+
+        Ok(state.chain_service.find_latest_parameters().await?)
+    }
+}
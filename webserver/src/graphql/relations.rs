@@ -0,0 +1,77 @@
+use async_graphql::{ComplexObject, Context, Result};
+
+use super::page::{Page, UNPAGINATED_LIST_SIZE};
+use crate::dto::pos::OrderByDto;
+use crate::dto::utils;
+use crate::response::balance::AddressBalance;
+use crate::response::governance::{Proposal, ProposalVote};
+use crate::response::pos::{Delegation, ValidatorWithId};
+use crate::state::common::CommonState;
+
+#[ComplexObject]
+impl ValidatorWithId {
+    #[graphql(
+        complexity = "utils::per_page(per_page) as usize * child_complexity"
+    )]
+    async fn delegations(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 1, validator(minimum = 1, maximum = 10000))]
+        page: u64,
+        #[graphql(validator(minimum = 1, maximum = 100))] per_page: Option<u64>,
+        sort_order: Option<OrderByDto>,
+    ) -> Result<Page<Delegation>> {
+        let state = ctx.data::<CommonState>()?;
+        let per_page = utils::per_page(per_page);
+
+        let delegations = state
+            .pos_service
+            .get_validator_delegations(
+                self.validator.address.clone(),
+                sort_order,
+                page,
+                per_page,
+            )
+            .await?;
+
+        Ok(Page::new(delegations, page, per_page))
+    }
+}
+
+#[ComplexObject]
+impl Delegation {
+    #[graphql(complexity = "UNPAGINATED_LIST_SIZE * child_complexity")]
+    async fn balances(&self, ctx: &Context<'_>) -> Result<Vec<AddressBalance>> {
+        let state = ctx.data::<CommonState>()?;
+
+        Ok(state
+            .balance_service
+            .get_address_balances(self.address.clone())
+            .await?)
+    }
+}
+
+#[ComplexObject]
+impl Proposal {
+    #[graphql(
+        complexity = "utils::per_page(per_page) as usize * child_complexity"
+    )]
+    async fn votes(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 1, validator(minimum = 1, maximum = 10000))]
+        page: u64,
+        #[graphql(validator(minimum = 1, maximum = 100))] per_page: Option<u64>,
+    ) -> Result<Page<ProposalVote>> {
+        let state = ctx.data::<CommonState>()?;
+        let per_page = utils::per_page(per_page);
+        let proposal_id = self.id.parse::<u64>()?;
+
+        let votes = state
+            .gov_service
+            .find_governance_proposal_votes(proposal_id, page, per_page)
+            .await?;
+
+        Ok(Page::new(votes, page, per_page))
+    }
+}
@@ -0,0 +1,83 @@
+use async_graphql::{EmptyMutation, EmptySubscription, Schema, SchemaBuilder};
+
+use super::query::QueryRoot;
+use crate::state::common::CommonState;
+
+pub type IndexerSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub fn build_schema(state: CommonState) -> IndexerSchema {
+    let max_depth = state.config.graphql_max_depth;
+    let max_complexity = state.config.graphql_max_complexity;
+
+    limited_schema(max_depth, max_complexity)
+        .data(state)
+        .finish()
+}
+
+fn limited_schema(
+    max_depth: usize,
+    max_complexity: usize,
+) -> SchemaBuilder<QueryRoot, EmptyMutation, EmptySubscription> {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(max_depth)
+        .limit_complexity(max_complexity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NESTED_QUERY: &str = "{ validators(perPage: 100) { results { \
+                                delegations(perPage: 100) { results { address \
+                                } } } } }";
+
+    async fn errors(schema: &IndexerSchema, query: &str) -> Vec<String> {
+        schema
+            .execute(query)
+            .await
+            .errors
+            .into_iter()
+            .map(|error| error.message)
+            .collect()
+    }
+
+    /// Test that a query within the limits is executed.
+    #[tokio::test]
+    async fn test_query_within_limits() {
+        let schema = limited_schema(10, 500).finish();
+
+        let response = schema
+            .execute("{ account(address: \"tnam1\") { address } }")
+            .await;
+
+        assert!(response.errors.is_empty());
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({ "account": { "address": "tnam1" } })
+        );
+    }
+
+    /// Test that nested paginated lists count `per_page` items each, so that
+    /// the query is refused before being resolved.
+    #[tokio::test]
+    async fn test_query_too_complex() {
+        let schema = limited_schema(10, 500).finish();
+
+        assert_eq!(
+            errors(&schema, NESTED_QUERY).await,
+            vec!["Query is too complex.".to_string()]
+        );
+    }
+
+    /// Test that a query nested deeper than the limit is refused before being
+    /// resolved.
+    #[tokio::test]
+    async fn test_query_too_deep() {
+        let schema = limited_schema(3, usize::MAX).finish();
+
+        assert_eq!(
+            errors(&schema, NESTED_QUERY).await,
+            vec!["Query is nested too deep.".to_string()]
+        );
+    }
+}
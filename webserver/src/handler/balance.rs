@@ -2,11 +2,31 @@ use axum::extract::{Path, State};
 use axum::http::HeaderMap;
 use axum::Json;
 use axum_macros::debug_handler;
+use validator::Validate;
 
+use crate::dto::balance::BalancesDto;
 use crate::error::api::ApiError;
-use crate::response::balance::AddressBalance;
+use crate::error::balance::BalanceError;
+use crate::response::api::ApiErrorResponse;
+use crate::response::balance::{AccountBalances, AddressBalance};
 use crate::state::common::CommonState;
 
+/// Get all the token balances of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/account/{address}",
+    tag = "account",
+    params(
+        ("address" = String, Path, description = "The account address"),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A list of balances.",
+            body = Vec<AddressBalance>
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_address_balance(
     _headers: HeaderMap,
@@ -17,3 +37,40 @@ pub async fn get_address_balance(
 
     Ok(Json(balances))
 }
+
+/// Get the balances of multiple addresses at once
+#[utoipa::path(
+    post,
+    path = "/api/v1/account/balances",
+    tag = "account",
+    request_body = BalancesDto,
+    responses(
+        (
+            status = 200,
+            description = "Balances of each address, in order.",
+            body = Vec<AccountBalances>
+        ),
+        (
+            status = 400,
+            description = "Too many or no addresses or tokens.",
+            body = ApiErrorResponse
+        ),
+        (status = 413, description = "Request body too large."),
+    )
+)]
+#[debug_handler]
+pub async fn get_addresses_balances(
+    _headers: HeaderMap,
+    State(state): State<CommonState>,
+    Json(body): Json<BalancesDto>,
+) -> Result<Json<Vec<AccountBalances>>, ApiError> {
+    body.validate()
+        .map_err(|e| BalanceError::InvalidRequest(e.to_string()))?;
+
+    let balances = state
+        .balance_service
+        .get_addresses_balances(body.addresses, body.tokens)
+        .await?;
+
+    Ok(Json(balances))
+}
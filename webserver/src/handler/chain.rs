@@ -1,33 +1,46 @@
 use std::convert::Infallible;
-use std::time::Duration;
 
-use axum::extract::State;
+use axum::extract::{Path, State};
 use axum::http::HeaderMap;
 use axum::response::sse::{Event, KeepAlive};
 use axum::response::Sse;
 use axum::Json;
+use axum_extra::extract::Query;
 use futures::Stream;
 use tokio_stream::StreamExt;
 
+use crate::dto::chain::{
+    TokenDistributionDto, TokenHoldersDto, TokenSupplyDto,
+};
+use crate::dto::utils::per_page;
 use crate::error::api::ApiError;
+use crate::response::api::ApiErrorResponse;
 use crate::response::chain::{
-    LastProcessedBlock, LastProcessedEpoch, Parameters, RpcUrl, Token,
+    ChainStatus, LastProcessedBlock, LastProcessedEpoch, Parameters, RpcUrl,
+    Token, TokenDistribution, TokenHolder, TokenSupply,
 };
+use crate::response::event::IndexerEvent;
+use crate::response::utils::{PaginatedResponse, TokenHolderPage};
 use crate::state::common::CommonState;
 
-#[derive(serde::Serialize)]
-struct ChainStatusEvent {
-    pub height: i32,
-    pub epoch: i32,
-}
-
+/// Stream the latest block and epoch processed by the chain crawler
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/status",
+    tag = "chain",
+    responses(
+        (
+            status = 200,
+            description = "Stream of ChainStatus messages.",
+            body = ChainStatus,
+            content_type = "text/event-stream"
+        ),
+    )
+)]
 pub async fn chain_status(
     State(state): State<CommonState>,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
-    let stream = tokio_stream::wrappers::IntervalStream::new(
-        tokio::time::interval(Duration::from_secs(3)),
-    )
-    .then(move |_| {
+    let current_status = {
         let state = state.clone();
 
         async move {
@@ -43,17 +56,39 @@ pub async fn chain_status(
                 .await
                 .expect("Failed to get last processed epoch");
 
-            let event =
-                serde_json::to_string(&ChainStatusEvent { height, epoch })
-                    .expect("Failed to serialize event");
+            ChainStatus { height, epoch }
+        }
+    };
+
+    let status_updates =
+        state.event_bus.subscribe().filter_map(|event| match event {
+            IndexerEvent::Block { height, epoch } => {
+                Some(ChainStatus { height, epoch })
+            }
+            _ => None,
+        });
+
+    let stream = futures::stream::once(current_status)
+        .chain(status_updates)
+        .map(|status| {
+            let event = serde_json::to_string(&status)
+                .expect("Failed to serialize event");
 
             Ok(Event::default().data(event))
-        }
-    });
+        });
 
     Sse::new(stream).keep_alive(KeepAlive::default())
 }
 
+/// Get the chain parameters
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/parameters",
+    tag = "chain",
+    responses(
+        (status = 200, description = "Chain parameters.", body = Parameters),
+    )
+)]
 pub async fn get_parameters(
     _headers: HeaderMap,
     State(state): State<CommonState>,
@@ -63,12 +98,30 @@ pub async fn get_parameters(
     Ok(Json(parameters))
 }
 
+/// Get the rpc url the indexer connects to
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/rpc-url",
+    tag = "chain",
+    responses(
+        (status = 200, description = "Rpc url.", body = RpcUrl),
+    )
+)]
 pub async fn get_rpc_url(State(state): State<CommonState>) -> Json<RpcUrl> {
     Json(RpcUrl {
         url: state.config.tendermint_url,
     })
 }
 
+/// Get the chain tokens
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/token",
+    tag = "chain",
+    responses(
+        (status = 200, description = "Chain tokens.", body = Vec<Token>),
+    )
+)]
 pub async fn get_tokens(
     State(state): State<CommonState>,
 ) -> Result<Json<Vec<Token>>, ApiError> {
@@ -78,6 +131,135 @@ pub async fn get_tokens(
     Ok(Json(res))
 }
 
+/// Get the supply of a token, current and per epoch
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/token/{address}/supply",
+    tag = "chain",
+    params(
+        ("address" = String, Path, description = "The token address"),
+        TokenSupplyDto,
+    ),
+    responses(
+        (status = 200, description = "Token supply.", body = TokenSupply),
+        (
+            status = 404,
+            description = "Token not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+pub async fn get_token_supply(
+    Query(query): Query<TokenSupplyDto>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<TokenSupply>, ApiError> {
+    let supply = state
+        .chain_service
+        .find_token_supply(address, query.from_epoch, query.to_epoch)
+        .await?;
+
+    Ok(Json(supply))
+}
+
+/// Get the holders of a token, sorted by balance
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/token/{address}/holders",
+    tag = "chain",
+    params(
+        ("address" = String, Path, description = "The token address"),
+        TokenHoldersDto,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A list of token holders.",
+            body = TokenHolderPage
+        ),
+        (
+            status = 404,
+            description = "Token not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+pub async fn get_token_holders(
+    Query(query): Query<TokenHoldersDto>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<TokenHolder>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
+    let exclude_internal = query.exclude_internal.unwrap_or(false);
+
+    let (holders, total_pages, total_holders) = state
+        .chain_service
+        .find_token_holders(address, exclude_internal, page, per_page)
+        .await?;
+
+    let response = PaginatedResponse::new(
+        holders,
+        page,
+        per_page,
+        total_pages,
+        total_holders,
+    );
+
+    Ok(Json(response))
+}
+
+/// Get the holders distribution of a token
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/token/{address}/distribution",
+    tag = "chain",
+    params(
+        ("address" = String, Path, description = "The token address"),
+        TokenDistributionDto,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Token distribution.",
+            body = TokenDistribution
+        ),
+        (
+            status = 404,
+            description = "Token not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+pub async fn get_token_distribution(
+    Query(query): Query<TokenDistributionDto>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<TokenDistribution>, ApiError> {
+    let distribution = state
+        .chain_service
+        .find_token_distribution(
+            address,
+            query.exclude_internal.unwrap_or(false),
+        )
+        .await?;
+
+    Ok(Json(distribution))
+}
+
+/// Get the latest block processed by the chain crawler
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/block/latest",
+    tag = "chain",
+    responses(
+        (
+            status = 200,
+            description = "Block height.",
+            body = LastProcessedBlock
+        ),
+    )
+)]
 pub async fn get_last_processed_block(
     State(state): State<CommonState>,
 ) -> Result<Json<LastProcessedBlock>, ApiError> {
@@ -89,6 +271,15 @@ pub async fn get_last_processed_block(
     }))
 }
 
+/// Get the latest epoch processed by the chain crawler
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/epoch/latest",
+    tag = "chain",
+    responses(
+        (status = 200, description = "Epoch.", body = LastProcessedEpoch),
+    )
+)]
 pub async fn get_last_processed_epoch(
     State(state): State<CommonState>,
 ) -> Result<Json<LastProcessedEpoch>, ApiError> {
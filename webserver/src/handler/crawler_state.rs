@@ -9,6 +9,20 @@ use crate::error::api::ApiError;
 use crate::response::crawler_state::CrawlersTimestamps;
 use crate::state::common::CommonState;
 
+/// Get the timestamps of the last activity of the crawlers
+#[utoipa::path(
+    get,
+    path = "/api/v1/crawlers/timestamps",
+    tag = "crawler",
+    params(CrawlerStateQueryParams),
+    responses(
+        (
+            status = 200,
+            description = "Crawlers timestamps.",
+            body = Vec<CrawlersTimestamps>
+        ),
+    )
+)]
 pub async fn get_crawlers_timestamps(
     _headers: HeaderMap,
     Query(query): Query<CrawlerStateQueryParams>,
@@ -0,0 +1,67 @@
+use std::convert::Infallible;
+
+use axum::extract::State;
+use axum::response::sse::{Event, KeepAlive};
+use axum::response::Sse;
+use axum_extra::extract::Query;
+use futures::Stream;
+use tokio_stream::StreamExt;
+
+use crate::dto::event::{EventKindDto, EventsQueryParams};
+use crate::response::event::IndexerEvent;
+use crate::state::common::CommonState;
+
+/// Subscribe to indexer events as they are committed by the crawlers
+#[utoipa::path(
+    get,
+    path = "/api/v1/events",
+    tag = "events",
+    params(EventsQueryParams),
+    responses(
+        (
+            status = 200,
+            description = "Stream of indexer events, tagged by `type`.",
+            content_type = "text/event-stream"
+        ),
+    )
+)]
+pub async fn subscribe_events(
+    Query(query): Query<EventsQueryParams>,
+    State(state): State<CommonState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = state
+        .event_bus
+        .subscribe()
+        .filter(move |event| matches_query(event, &query))
+        .map(|event| {
+            let event = serde_json::to_string(&event)
+                .expect("Failed to serialize event");
+
+            Ok(Event::default().data(event))
+        });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Events are kept when their kind is requested and, for events carrying
+/// addresses, when they involve the requested address
+fn matches_query(event: &IndexerEvent, query: &EventsQueryParams) -> bool {
+    let kind = match event {
+        IndexerEvent::Block { .. } => EventKindDto::Block,
+        IndexerEvent::Transaction { .. } => EventKindDto::Transaction,
+        IndexerEvent::ProposalStatus { .. } => EventKindDto::ProposalStatus,
+        IndexerEvent::ValidatorSet { .. } => EventKindDto::ValidatorSet,
+    };
+    let kind_matches = query
+        .kind
+        .as_ref()
+        .map_or(true, |kinds| kinds.contains(&kind));
+
+    let addresses = event.addresses();
+    let address_matches = match &query.address {
+        Some(address) if !addresses.is_empty() => addresses.contains(address),
+        _ => true,
+    };
+
+    kind_matches && address_matches
+}
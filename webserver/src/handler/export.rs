@@ -0,0 +1,366 @@
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use axum_extra::extract::Query;
+use axum_macros::debug_handler;
+use futures::{StreamExt, TryStreamExt};
+use serde::Serialize;
+
+use crate::dto::export::{
+    EpochExportQueryParams, ExportFormatDto, ExportQueryParams,
+};
+use crate::error::api::ApiError;
+use crate::error::export::ExportError;
+use crate::repository::export::EpochRange;
+use crate::response::api::ApiErrorResponse;
+use crate::response::export::{
+    BalanceChangeExport, BondExport, RewardClaimExport, TransactionExport,
+    UnbondExport,
+};
+use crate::service::export::ExportStream;
+use crate::state::common::CommonState;
+
+pub const HISTORY_FROM_HEIGHT: &str = "x-history-from-height";
+
+/// Export the inner transactions involving an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/account/{address}/export/transactions",
+    tag = "account",
+    params(
+        ("address" = String, Path, description = "The account address"),
+        ExportQueryParams
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Transactions, oldest first.",
+            headers(
+                (
+                    "x-history-from-height" = i32,
+                    description = "First block height with indexed history, \
+                                   earlier rows are missing from the export."
+                )
+            ),
+            content(
+                (Vec<TransactionExport> = "text/csv"),
+                (Vec<TransactionExport> = "application/x-ndjson")
+            )
+        ),
+        (
+            status = 400,
+            description = "Invalid timestamp.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn export_transactions(
+    _headers: HeaderMap,
+    Query(query): Query<ExportQueryParams>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Response, ApiError> {
+    let range = state
+        .export_service
+        .resolve_height_range(
+            query.from_height,
+            query.to_height,
+            query.from_timestamp,
+            query.to_timestamp,
+        )
+        .await?;
+    let history_start =
+        state.export_service.transactions_history_start().await?;
+    let rows = state
+        .export_service
+        .export_transactions(address.clone(), range);
+
+    export_response(&address, "transactions", query.format, rows)
+        .await
+        .map(|response| with_history_start(response, history_start))
+}
+
+/// Export the balance changes of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/account/{address}/export/balances",
+    tag = "account",
+    params(
+        ("address" = String, Path, description = "The account address"),
+        ExportQueryParams
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Balance after each change, oldest first.",
+            headers(
+                (
+                    "x-history-from-height" = i32,
+                    description = "First block height with indexed history, \
+                                   earlier rows are missing from the export."
+                )
+            ),
+            content(
+                (Vec<BalanceChangeExport> = "text/csv"),
+                (Vec<BalanceChangeExport> = "application/x-ndjson")
+            )
+        ),
+        (
+            status = 400,
+            description = "Invalid timestamp.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn export_balance_changes(
+    _headers: HeaderMap,
+    Query(query): Query<ExportQueryParams>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Response, ApiError> {
+    let range = state
+        .export_service
+        .resolve_height_range(
+            query.from_height,
+            query.to_height,
+            query.from_timestamp,
+            query.to_timestamp,
+        )
+        .await?;
+    let history_start =
+        state.export_service.balance_changes_history_start().await?;
+    let rows = state
+        .export_service
+        .export_balance_changes(address.clone(), range);
+
+    export_response(&address, "balances", query.format, rows)
+        .await
+        .map(|response| with_history_start(response, history_start))
+}
+
+/// Export the bonds of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/account/{address}/export/bonds",
+    tag = "account",
+    params(
+        ("address" = String, Path, description = "The account address"),
+        EpochExportQueryParams
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Bonds by start epoch.",
+            content(
+                (Vec<BondExport> = "text/csv"),
+                (Vec<BondExport> = "application/x-ndjson")
+            )
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn export_bonds(
+    _headers: HeaderMap,
+    Query(query): Query<EpochExportQueryParams>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Response, ApiError> {
+    let rows = state
+        .export_service
+        .export_bonds(address.clone(), epoch_range(&query));
+
+    export_response(&address, "bonds", query.format, rows).await
+}
+
+/// Export the unbonds of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/account/{address}/export/unbonds",
+    tag = "account",
+    params(
+        ("address" = String, Path, description = "The account address"),
+        EpochExportQueryParams
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Unbonds by withdraw epoch.",
+            content(
+                (Vec<UnbondExport> = "text/csv"),
+                (Vec<UnbondExport> = "application/x-ndjson")
+            )
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn export_unbonds(
+    _headers: HeaderMap,
+    Query(query): Query<EpochExportQueryParams>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Response, ApiError> {
+    let rows = state
+        .export_service
+        .export_unbonds(address.clone(), epoch_range(&query));
+
+    export_response(&address, "unbonds", query.format, rows).await
+}
+
+/// Export the reward claims of an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/account/{address}/export/rewards",
+    tag = "account",
+    params(
+        ("address" = String, Path, description = "The account address"),
+        ExportQueryParams
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Claimed rewards, oldest first.",
+            headers(
+                (
+                    "x-history-from-height" = i32,
+                    description = "First block height with indexed history, \
+                                   earlier rows are missing from the export."
+                )
+            ),
+            content(
+                (Vec<RewardClaimExport> = "text/csv"),
+                (Vec<RewardClaimExport> = "application/x-ndjson")
+            )
+        ),
+        (
+            status = 400,
+            description = "Invalid timestamp.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn export_reward_claims(
+    _headers: HeaderMap,
+    Query(query): Query<ExportQueryParams>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Response, ApiError> {
+    let range = state
+        .export_service
+        .resolve_height_range(
+            query.from_height,
+            query.to_height,
+            query.from_timestamp,
+            query.to_timestamp,
+        )
+        .await?;
+    let history_start =
+        state.export_service.reward_claims_history_start().await?;
+    let rows = state
+        .export_service
+        .export_reward_claims(address.clone(), range);
+
+    export_response(&address, "rewards", query.format, rows)
+        .await
+        .map(|response| with_history_start(response, history_start))
+}
+
+fn epoch_range(query: &EpochExportQueryParams) -> EpochRange {
+    EpochRange {
+        from: query.from_epoch.map(|epoch| epoch as i32),
+        to: query.to_epoch.map(|epoch| epoch as i32),
+    }
+}
+
+/// Streams the rows as an attachment. The first chunk is fetched upfront so
+/// that database errors still get a proper status code, later errors abort
+/// the body.
+async fn export_response<T>(
+    address: &str,
+    name: &str,
+    format: Option<ExportFormatDto>,
+    mut rows: ExportStream<T>,
+) -> Result<Response, ApiError>
+where
+    T: Serialize + Send + 'static,
+{
+    let format = format.unwrap_or_default();
+    let first_chunk = rows.try_next().await?;
+
+    let body = futures::stream::iter(first_chunk.map(Ok))
+        .chain(rows)
+        .enumerate()
+        .map(move |(index, chunk)| {
+            chunk.and_then(|chunk| encode(format, &chunk, index == 0))
+        });
+
+    let (content_type, extension) = match format {
+        ExportFormatDto::Csv => ("text/csv", "csv"),
+        ExportFormatDto::Ndjson => ("application/x-ndjson", "ndjson"),
+    };
+    let content_disposition = format!(
+        "attachment; filename=\"{}-{}.{}\"",
+        address, name, extension
+    );
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_DISPOSITION, content_disposition),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response())
+}
+
+/// Reports where the indexed history starts, absent while nothing was
+/// indexed yet
+fn with_history_start(
+    mut response: Response,
+    history_start: Option<i32>,
+) -> Response {
+    if let Some(height) = history_start {
+        response
+            .headers_mut()
+            .insert(HISTORY_FROM_HEIGHT, HeaderValue::from(height));
+    }
+
+    response
+}
+
+/// CSV headers are only written with the first chunk
+fn encode<T: Serialize>(
+    format: ExportFormatDto,
+    rows: &[T],
+    with_headers: bool,
+) -> Result<Vec<u8>, ExportError> {
+    match format {
+        ExportFormatDto::Csv => {
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(with_headers)
+                .from_writer(vec![]);
+            for row in rows {
+                writer
+                    .serialize(row)
+                    .map_err(|e| ExportError::Unknown(e.to_string()))?;
+            }
+
+            writer
+                .into_inner()
+                .map_err(|e| ExportError::Unknown(e.to_string()))
+        }
+        ExportFormatDto::Ndjson => {
+            let mut buffer = vec![];
+            for row in rows {
+                serde_json::to_writer(&mut buffer, row)
+                    .map_err(|e| ExportError::Unknown(e.to_string()))?;
+                buffer.push(b'\n');
+            }
+
+            Ok(buffer)
+        }
+    }
+}
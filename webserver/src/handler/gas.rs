@@ -7,6 +7,22 @@ use crate::error::api::ApiError;
 use crate::response::gas::{Gas, GasPrice};
 use crate::state::common::CommonState;
 
+/// Get the gas limit per transaction kind for a token
+#[utoipa::path(
+    get,
+    path = "/api/v1/gas/{token}",
+    tag = "gas",
+    params(
+        (
+            "token" = String,
+            Path,
+            description = "The gas token, `native` for the native token"
+        ),
+    ),
+    responses(
+        (status = 200, description = "Gas limit table.", body = Vec<Gas>),
+    )
+)]
 #[debug_handler]
 pub async fn get_gas_by_token(
     _headers: HeaderMap,
@@ -18,6 +34,16 @@ pub async fn get_gas_by_token(
     Ok(Json(gas))
 }
 
+/// Get the gas price of a token
+#[utoipa::path(
+    get,
+    path = "/api/v1/gas-price/{token}",
+    tag = "gas",
+    params(("token" = String, Path, description = "The gas token")),
+    responses(
+        (status = 200, description = "Gas price table.", body = Vec<GasPrice>),
+    )
+)]
 #[debug_handler]
 pub async fn get_gas_price_by_token(
     _headers: HeaderMap,
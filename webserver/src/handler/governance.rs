@@ -4,12 +4,36 @@ use axum::Json;
 use axum_macros::debug_handler;
 
 use crate::dto::governance::{ProposalQueryParams, ProposalVotesQueryparams};
+use crate::dto::utils::{self, per_page};
 use crate::error::api::ApiError;
 use crate::error::governance::GovernanceError;
+use crate::response::api::ApiErrorResponse;
 use crate::response::governance::{Proposal, ProposalVote};
-use crate::response::utils::PaginatedResponse;
+use crate::response::utils::{
+    CursorPaginatedResponse, ListResponse, PaginatedResponse, ProposalPage,
+    ProposalVoteList,
+};
 use crate::state::common::CommonState;
 
+/// Get a list of governance proposals
+#[utoipa::path(
+    get,
+    path = "/api/v1/gov/proposal",
+    tag = "governance",
+    params(ProposalQueryParams),
+    responses(
+        (
+            status = 200,
+            description = "A list of governance proposals.",
+            body = ProposalPage
+        ),
+        (
+            status = 400,
+            description = "Pattern too short.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_governance_proposals(
     _headers: HeaderMap,
@@ -17,6 +41,7 @@ pub async fn get_governance_proposals(
     State(state): State<CommonState>,
 ) -> Result<Json<PaginatedResponse<Vec<Proposal>>>, ApiError> {
     let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
     let (proposals, total_pages, total_items) = state
         .gov_service
         .find_governance_proposals(
@@ -24,14 +49,39 @@ pub async fn get_governance_proposals(
             query.kind,
             query.pattern,
             page,
+            per_page,
         )
         .await?;
 
-    let response =
-        PaginatedResponse::new(proposals, page, total_pages, total_items);
+    let response = PaginatedResponse::new(
+        proposals,
+        page,
+        per_page,
+        total_pages,
+        total_items,
+    );
     Ok(Json(response))
 }
 
+/// Get a list of all governance proposals
+#[utoipa::path(
+    get,
+    path = "/api/v1/gov/proposal/all",
+    tag = "governance",
+    params(ProposalQueryParams),
+    responses(
+        (
+            status = 200,
+            description = "A list of governance proposals.",
+            body = Vec<Proposal>
+        ),
+        (
+            status = 400,
+            description = "Pattern too short.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_all_governance_proposals(
     _headers: HeaderMap,
@@ -46,6 +96,21 @@ pub async fn get_all_governance_proposals(
     Ok(Json(proposals))
 }
 
+/// Get a governance proposal by proposal id
+#[utoipa::path(
+    get,
+    path = "/api/v1/gov/proposal/{id}",
+    tag = "governance",
+    params(("id" = u64, Path, description = "Proposal id")),
+    responses(
+        (status = 200, description = "A governance proposal.", body = Proposal),
+        (
+            status = 404,
+            description = "Proposal not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_governance_proposal_by_id(
     _headers: HeaderMap,
@@ -64,27 +129,104 @@ pub async fn get_governance_proposal_by_id(
     }
 }
 
+/// Get all the votes for a governance proposal
+#[utoipa::path(
+    get,
+    path = "/api/v1/gov/proposal/{id}/votes",
+    tag = "governance",
+    params(
+        ("id" = u64, Path, description = "Proposal id"),
+        ProposalVotesQueryparams,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A page of votes.",
+            body = ProposalVoteList
+        ),
+        (
+            status = 400,
+            description = "Invalid cursor.",
+            body = ApiErrorResponse
+        ),
+        (
+            status = 404,
+            description = "Proposal not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_governance_proposal_votes(
     _headers: HeaderMap,
     Path(proposal_id): Path<u64>,
     Query(query): Query<ProposalVotesQueryparams>,
     State(state): State<CommonState>,
-) -> Result<Json<PaginatedResponse<Vec<ProposalVote>>>, ApiError> {
+) -> Result<Json<ListResponse<Vec<ProposalVote>>>, ApiError> {
+    let per_page = per_page(query.per_page);
+
+    if let Some(cursor) = query.cursor {
+        let cursor =
+            utils::cursor(&cursor).map_err(GovernanceError::InvalidCursor)?;
+        let (proposal_votes, next_cursor, total_votes) = state
+            .gov_service
+            .find_governance_proposal_votes_after(
+                proposal_id,
+                cursor,
+                per_page,
+                query.with_count.unwrap_or(false),
+            )
+            .await?;
+
+        let response = CursorPaginatedResponse::new(
+            proposal_votes,
+            per_page,
+            next_cursor,
+            total_votes,
+        );
+
+        return Ok(Json(response.into()));
+    }
+
     let page = query.page.unwrap_or(1);
     let (proposal_votes, total_pages, total_votes) = state
         .gov_service
-        .find_governance_proposal_votes(proposal_id, page)
+        .find_governance_proposal_votes(proposal_id, page, per_page)
         .await?;
 
-    Ok(Json(PaginatedResponse::new(
+    let response = PaginatedResponse::new(
         proposal_votes,
         page,
+        per_page,
         total_pages,
         total_votes,
-    )))
+    );
+
+    Ok(Json(response.into()))
 }
 
+/// Get all the votes for a governance proposal from an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/gov/proposal/{id}/votes/{address}",
+    tag = "governance",
+    params(
+        ("id" = u64, Path, description = "Proposal id"),
+        ("address" = String, Path, description = "The voter address"),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A list of votes.",
+            body = Vec<ProposalVote>
+        ),
+        (
+            status = 404,
+            description = "Proposal not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_governance_proposal_votes_by_address(
     _headers: HeaderMap,
@@ -99,6 +241,22 @@ pub async fn get_governance_proposal_votes_by_address(
     Ok(Json(proposal_votes))
 }
 
+/// Get all the votes from a voter
+#[utoipa::path(
+    get,
+    path = "/api/v1/gov/voter/{address}/votes",
+    tag = "governance",
+    params(
+        ("address" = String, Path, description = "The voter address"),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A list of votes.",
+            body = Vec<ProposalVote>
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_governance_proposal_votes_by_voter(
     _headers: HeaderMap,
@@ -7,6 +7,18 @@ use crate::error::api::ApiError;
 use crate::response::revealed_pk::RevealedPk;
 use crate::state::common::CommonState;
 
+/// Get the revealed public key of an address, if any
+#[utoipa::path(
+    get,
+    path = "/api/v1/revealed-public-key/{address}",
+    tag = "account",
+    params(
+        ("address" = String, Path, description = "The account address"),
+    ),
+    responses(
+        (status = 200, description = "Revealed public key.", body = RevealedPk),
+    )
+)]
 #[debug_handler]
 pub async fn get_revealed_pk(
     _headers: HeaderMap,
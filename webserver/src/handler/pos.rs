@@ -3,19 +3,44 @@ use axum::http::HeaderMap;
 use axum::Json;
 use axum_extra::extract::Query;
 use axum_macros::debug_handler;
+use validator::Validate;
 
 use crate::dto::pos::{
-    AllValidatorsQueryParams, BondsDto, UnbondsDto, ValidatorQueryParams,
+    AllValidatorsQueryParams, BondsDto, EstimatedRewardsDto,
+    MyValidatorQueryParams, RedelegationsDto, RewardHistoryDto, UnbondsDto,
+    ValidatorBlocksDto, ValidatorDelegationsDto, ValidatorQueryParams,
     ValidatorStateDto, WithdrawsDto,
 };
+use crate::dto::utils::{self, per_page};
 use crate::error::api::ApiError;
+use crate::error::pos::PoSError;
+use crate::response::api::ApiErrorResponse;
 use crate::response::pos::{
-    Bond, MergedBond, Reward, TotalVotingPower, Unbond, ValidatorWithId,
-    Withdraw,
+    Bond, Delegation, EstimatedRewards, MergedBond, MyValidator, Redelegation,
+    Reward, RewardHistory, Slash, TotalVotingPower, Unbond, ValidatorBlock,
+    ValidatorDetail, ValidatorWithId, Withdraw,
+};
+use crate::response::utils::{
+    BondPage, CursorPaginatedResponse, DelegationPage, ListResponse,
+    MergedBondPage, MyValidatorPage, PaginatedResponse, RedelegationPage,
+    UnbondPage, ValidatorBlockList, ValidatorPage, WithdrawPage,
 };
-use crate::response::utils::PaginatedResponse;
 use crate::state::common::CommonState;
 
+/// Get all validators, paginated
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/validator",
+    tag = "pos",
+    params(ValidatorQueryParams),
+    responses(
+        (
+            status = 200,
+            description = "A list of validators.",
+            body = ValidatorPage
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_validators(
     _headers: HeaderMap,
@@ -23,17 +48,44 @@ pub async fn get_validators(
     State(state): State<CommonState>,
 ) -> Result<Json<PaginatedResponse<Vec<ValidatorWithId>>>, ApiError> {
     let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
     let states = query.state.unwrap_or_else(ValidatorStateDto::all);
     let (validators, total_pages, total_validators) = state
         .pos_service
-        .get_validators(page, states, query.sort_field, query.sort_order)
+        .get_validators(
+            page,
+            per_page,
+            states,
+            query.sort_field,
+            query.sort_order,
+            &state.config.uptime_windows,
+        )
         .await?;
 
-    let response =
-        PaginatedResponse::new(validators, page, total_pages, total_validators);
+    let response = PaginatedResponse::new(
+        validators,
+        page,
+        per_page,
+        total_pages,
+        total_validators,
+    );
     Ok(Json(response))
 }
 
+/// Get all validators, non paginated
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/validator/all",
+    tag = "pos",
+    params(AllValidatorsQueryParams),
+    responses(
+        (
+            status = 200,
+            description = "A list of validators.",
+            body = Vec<ValidatorWithId>
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_all_validators(
     _headers: HeaderMap,
@@ -41,11 +93,257 @@ pub async fn get_all_validators(
     State(state): State<CommonState>,
 ) -> Result<Json<Vec<ValidatorWithId>>, ApiError> {
     let states = query.state.unwrap_or_else(ValidatorStateDto::all);
-    let validators = state.pos_service.get_all_validators(states).await?;
+    let validators = state
+        .pos_service
+        .get_all_validators(states, &state.config.uptime_windows)
+        .await?;
 
     Ok(Json(validators))
 }
 
+/// Get a validator by address
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/validator/{address}",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The validator address"),
+    ),
+    responses(
+        (status = 200, description = "A validator.", body = ValidatorDetail),
+        (
+            status = 404,
+            description = "Validator not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn get_validator(
+    _headers: HeaderMap,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<ValidatorDetail>, ApiError> {
+    let validator = state
+        .pos_service
+        .get_validator(address, &state.config.uptime_windows)
+        .await?;
+
+    Ok(Json(validator))
+}
+
+/// Get the delegations to a validator, the self-bond excluded
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/validator/{address}/delegations",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The validator address"),
+        ValidatorDelegationsDto,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A list of delegations.",
+            body = DelegationPage
+        ),
+        (
+            status = 404,
+            description = "Validator not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn get_validator_delegations(
+    _headers: HeaderMap,
+    Query(query): Query<ValidatorDelegationsDto>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<Delegation>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
+
+    let (delegations, total_pages, total_delegations) = state
+        .pos_service
+        .get_validator_delegations(address, query.sort_order, page, per_page)
+        .await?;
+
+    let response = PaginatedResponse::new(
+        delegations,
+        page,
+        per_page,
+        total_pages,
+        total_delegations,
+    );
+
+    Ok(Json(response))
+}
+
+/// Estimate the rewards a new delegation to a validator would earn
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/validator/{address}/estimated-rewards",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The validator address"),
+        EstimatedRewardsDto,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "The estimated rewards.",
+            body = EstimatedRewards
+        ),
+        (
+            status = 400,
+            description = "Invalid amount.",
+            body = ApiErrorResponse
+        ),
+        (
+            status = 404,
+            description = "Validator not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn get_estimated_rewards(
+    _headers: HeaderMap,
+    Query(query): Query<EstimatedRewardsDto>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<EstimatedRewards>, ApiError> {
+    let estimated_rewards = state
+        .pos_service
+        .get_estimated_rewards(
+            address,
+            query.amount,
+            query.epochs,
+            &state.config.uptime_windows,
+        )
+        .await?;
+
+    Ok(Json(estimated_rewards))
+}
+
+/// Get the blocks proposed and missed by a validator, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/validator/{address}/blocks",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The validator address"),
+        ValidatorBlocksDto,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A page of validator blocks.",
+            body = ValidatorBlockList
+        ),
+        (
+            status = 400,
+            description = "Invalid cursor.",
+            body = ApiErrorResponse
+        ),
+        (
+            status = 404,
+            description = "Validator not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn get_validator_blocks(
+    _headers: HeaderMap,
+    Query(query): Query<ValidatorBlocksDto>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<ListResponse<Vec<ValidatorBlock>>>, ApiError> {
+    let per_page = per_page(query.per_page);
+
+    if let Some(cursor) = query.cursor {
+        let cursor = utils::composite_cursor(&cursor)
+            .map_err(PoSError::InvalidCursor)?;
+        let (blocks, next_cursor, total_blocks) = state
+            .pos_service
+            .get_validator_blocks_before(
+                address,
+                cursor,
+                per_page,
+                query.with_count.unwrap_or(false),
+            )
+            .await?;
+
+        let response = CursorPaginatedResponse::new(
+            blocks,
+            per_page,
+            next_cursor,
+            total_blocks,
+        );
+
+        return Ok(Json(response.into()));
+    }
+
+    let page = query.page.unwrap_or(1);
+    let (blocks, total_pages, total_blocks) = state
+        .pos_service
+        .get_validator_blocks(address, page, per_page)
+        .await?;
+
+    let response = PaginatedResponse::new(
+        blocks,
+        page,
+        per_page,
+        total_pages,
+        total_blocks,
+    );
+
+    Ok(Json(response.into()))
+}
+
+/// Get the slashes of a validator, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/validator/{address}/slashes",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The validator address"),
+    ),
+    responses(
+        (status = 200, description = "A list of slashes.", body = Vec<Slash>),
+        (
+            status = 404,
+            description = "Validator not found.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn get_validator_slashes(
+    _headers: HeaderMap,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<Vec<Slash>>, ApiError> {
+    let slashes = state.pos_service.get_validator_slashes(address).await?;
+
+    Ok(Json(slashes))
+}
+
+/// Get all the bonds for an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/bond/{address}",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The delegator address"),
+        BondsDto,
+    ),
+    responses(
+        (status = 200, description = "A list of bonds.", body = BondPage),
+    )
+)]
 #[debug_handler]
 pub async fn get_bonds(
     _headers: HeaderMap,
@@ -54,18 +352,76 @@ pub async fn get_bonds(
     State(state): State<CommonState>,
 ) -> Result<Json<PaginatedResponse<Vec<Bond>>>, ApiError> {
     let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
 
     let (bonds, total_pages, total_bonds) = state
         .pos_service
-        .get_bonds_by_address(address, page)
+        .get_bonds_by_address(address, page, per_page)
         .await?;
 
     let response =
-        PaginatedResponse::new(bonds, page, total_pages, total_bonds);
+        PaginatedResponse::new(bonds, page, per_page, total_pages, total_bonds);
+
+    Ok(Json(response))
+}
+
+/// Get the validators the given addresses have bonds or unbonds with
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/my-validator",
+    tag = "pos",
+    params(MyValidatorQueryParams),
+    responses(
+        (
+            status = 200,
+            description = "Validators with the amounts of all the addresses.",
+            body = MyValidatorPage
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn get_my_validators(
+    _headers: HeaderMap,
+    Query(query): Query<MyValidatorQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<MyValidator>>>, ApiError> {
+    query
+        .validate()
+        .map_err(|e| PoSError::InvalidRequest(e.to_string()))?;
+
+    let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
+
+    let (validators, total_pages, total_validators) = state
+        .pos_service
+        .get_my_validators(query.address, query.kind, page, per_page)
+        .await?;
+
+    let response = PaginatedResponse::new(
+        validators,
+        page,
+        per_page,
+        total_pages,
+        total_validators,
+    );
 
     Ok(Json(response))
 }
 
+/// Get all the bonds for an address, with merged amounts, regardless of the
+/// status
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/merged-bonds/{address}",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The delegator address"),
+        BondsDto,
+    ),
+    responses(
+        (status = 200, description = "A list of bonds.", body = MergedBondPage),
+    )
+)]
 #[debug_handler]
 pub async fn get_merged_bonds(
     _headers: HeaderMap,
@@ -74,18 +430,32 @@ pub async fn get_merged_bonds(
     State(state): State<CommonState>,
 ) -> Result<Json<PaginatedResponse<Vec<MergedBond>>>, ApiError> {
     let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
 
     let (bonds, total_pages, total_bonds) = state
         .pos_service
-        .get_merged_bonds_by_address(address, page)
+        .get_merged_bonds_by_address(address, page, per_page)
         .await?;
 
     let response =
-        PaginatedResponse::new(bonds, page, total_pages, total_bonds);
+        PaginatedResponse::new(bonds, page, per_page, total_pages, total_bonds);
 
     Ok(Json(response))
 }
 
+/// Get all the unbonds for an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/unbond/{address}",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The delegator address"),
+        UnbondsDto,
+    ),
+    responses(
+        (status = 200, description = "A list of unbonds.", body = UnbondPage),
+    )
+)]
 #[debug_handler]
 pub async fn get_unbonds(
     _headers: HeaderMap,
@@ -94,18 +464,37 @@ pub async fn get_unbonds(
     State(state): State<CommonState>,
 ) -> Result<Json<PaginatedResponse<Vec<Unbond>>>, ApiError> {
     let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
 
     let (unbonds, total_pages, total_unbonds) = state
         .pos_service
-        .get_unbonds_by_address(address, page)
+        .get_unbonds_by_address(address, page, per_page)
         .await?;
 
-    let response =
-        PaginatedResponse::new(unbonds, page, total_pages, total_unbonds);
+    let response = PaginatedResponse::new(
+        unbonds,
+        page,
+        per_page,
+        total_pages,
+        total_unbonds,
+    );
 
     Ok(Json(response))
 }
 
+/// Get all the unbonds for an address with merged withdraw amounts
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/merged-unbonds/{address}",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The delegator address"),
+        UnbondsDto,
+    ),
+    responses(
+        (status = 200, description = "A list of unbonds.", body = UnbondPage),
+    )
+)]
 #[debug_handler]
 pub async fn get_merged_unbonds(
     _headers: HeaderMap,
@@ -114,18 +503,41 @@ pub async fn get_merged_unbonds(
     State(state): State<CommonState>,
 ) -> Result<Json<PaginatedResponse<Vec<Unbond>>>, ApiError> {
     let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
 
     let (unbonds, total_pages, total_unbonds) = state
         .pos_service
-        .get_merged_unbonds_by_address(address, page)
+        .get_merged_unbonds_by_address(address, page, per_page)
         .await?;
 
-    let response =
-        PaginatedResponse::new(unbonds, page, total_pages, total_unbonds);
+    let response = PaginatedResponse::new(
+        unbonds,
+        page,
+        per_page,
+        total_pages,
+        total_unbonds,
+    );
 
     Ok(Json(response))
 }
 
+/// Get all the withdraws for an address at a specific epoch
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/withdraw/{address}",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The delegator address"),
+        WithdrawsDto,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A list of withdraws.",
+            body = WithdrawPage
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_withdraws(
     _headers: HeaderMap,
@@ -134,18 +546,79 @@ pub async fn get_withdraws(
     State(state): State<CommonState>,
 ) -> Result<Json<PaginatedResponse<Vec<Withdraw>>>, ApiError> {
     let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
 
     let (withdraws, total_pages, total_withdraws) = state
         .pos_service
-        .get_withdraws_by_address(address, query.epoch, page)
+        .get_withdraws_by_address(address, query.epoch, page, per_page)
         .await?;
 
-    let response =
-        PaginatedResponse::new(withdraws, page, total_pages, total_withdraws);
+    let response = PaginatedResponse::new(
+        withdraws,
+        page,
+        per_page,
+        total_pages,
+        total_withdraws,
+    );
+
+    Ok(Json(response))
+}
+
+/// Get all the redelegations of an address and their slashing window
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/redelegation/{address}",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The delegator address"),
+        RedelegationsDto,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A list of redelegations.",
+            body = RedelegationPage
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn get_redelegations(
+    _headers: HeaderMap,
+    query: Query<RedelegationsDto>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<PaginatedResponse<Vec<Redelegation>>>, ApiError> {
+    let page = query.page.unwrap_or(1);
+    let per_page = per_page(query.per_page);
+
+    let (redelegations, total_pages, total_redelegations) = state
+        .pos_service
+        .get_redelegations_by_address(address, page, per_page)
+        .await?;
+
+    let response = PaginatedResponse::new(
+        redelegations,
+        page,
+        per_page,
+        total_pages,
+        total_redelegations,
+    );
 
     Ok(Json(response))
 }
 
+/// Get all the rewards for an address
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/reward/{address}",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The delegator address"),
+    ),
+    responses(
+        (status = 200, description = "A list of rewards.", body = Vec<Reward>),
+    )
+)]
 #[debug_handler]
 pub async fn get_rewards(
     _headers: HeaderMap,
@@ -156,6 +629,56 @@ pub async fn get_rewards(
     Ok(Json(rewards))
 }
 
+/// Get the claimable rewards of an address at each epoch and the rewards it
+/// claimed
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/reward/{address}/history",
+    tag = "pos",
+    params(
+        ("address" = String, Path, description = "The delegator address"),
+        RewardHistoryDto,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "The reward history.",
+            body = RewardHistory
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn get_rewards_history(
+    _headers: HeaderMap,
+    Query(query): Query<RewardHistoryDto>,
+    Path(address): Path<String>,
+    State(state): State<CommonState>,
+) -> Result<Json<RewardHistory>, ApiError> {
+    let history = state
+        .pos_service
+        .get_rewards_history_by_address(
+            address,
+            query.from_epoch,
+            query.to_epoch,
+        )
+        .await?;
+
+    Ok(Json(history))
+}
+
+/// Get the total voting power
+#[utoipa::path(
+    get,
+    path = "/api/v1/pos/voting-power",
+    tag = "pos",
+    responses(
+        (
+            status = 200,
+            description = "The total voting power.",
+            body = TotalVotingPower
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_total_voting_power(
     _headers: HeaderMap,
@@ -1,13 +1,36 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::HeaderMap;
 use axum::Json;
 use axum_macros::debug_handler;
 
+use crate::dto::transaction::TransactionHistoryQueryParams;
+use crate::dto::utils::{self, per_page};
 use crate::error::api::ApiError;
 use crate::error::transaction::TransactionError;
+use crate::response::api::ApiErrorResponse;
 use crate::response::transaction::{InnerTransaction, WrapperTransaction};
+use crate::response::utils::{CursorPaginatedResponse, ListResponse};
 use crate::state::common::CommonState;
 
+/// Get a wrapper transaction by hash
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/wrapper/{tx_id}",
+    tag = "transaction",
+    params(("tx_id" = String, Path, description = "Transaction hash")),
+    responses(
+        (
+            status = 200,
+            description = "The wrapper transaction, null when unknown.",
+            body = WrapperTransaction
+        ),
+        (
+            status = 400,
+            description = "Invalid transaction hash.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_wrapper_tx(
     _headers: HeaderMap,
@@ -37,6 +60,25 @@ pub async fn get_wrapper_tx(
     })))
 }
 
+/// Get an inner transaction by hash
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/inner/{tx_id}",
+    tag = "transaction",
+    params(("tx_id" = String, Path, description = "Transaction hash")),
+    responses(
+        (
+            status = 200,
+            description = "The inner transaction, null when unknown.",
+            body = InnerTransaction
+        ),
+        (
+            status = 400,
+            description = "Invalid transaction hash.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
 #[debug_handler]
 pub async fn get_inner_tx(
     _headers: HeaderMap,
@@ -57,6 +99,60 @@ pub async fn get_inner_tx(
     Ok(Json(inner_tx))
 }
 
+/// Get the inner transactions involving an address, newest first
+#[utoipa::path(
+    get,
+    path = "/api/v1/chain/history/{address}",
+    tag = "transaction",
+    params(
+        ("address" = String, Path, description = "The address"),
+        TransactionHistoryQueryParams,
+    ),
+    responses(
+        (
+            status = 200,
+            description = "A page of inner transactions.",
+            body = InnerTransactionList
+        ),
+        (
+            status = 400,
+            description = "Invalid cursor.",
+            body = ApiErrorResponse
+        ),
+    )
+)]
+#[debug_handler]
+pub async fn get_inner_txs_by_address(
+    _headers: HeaderMap,
+    Path(address): Path<String>,
+    Query(query): Query<TransactionHistoryQueryParams>,
+    State(state): State<CommonState>,
+) -> Result<Json<ListResponse<Vec<InnerTransaction>>>, ApiError> {
+    let per_page = per_page(query.per_page);
+    let cursor =
+        utils::composite_cursor(query.cursor.as_deref().unwrap_or_default())
+            .map_err(TransactionError::InvalidCursor)?;
+
+    let (inner_txs, next_cursor, total_items) = state
+        .transaction_service
+        .get_inner_txs_by_address(
+            address,
+            cursor,
+            per_page,
+            query.with_count.unwrap_or(false),
+        )
+        .await?;
+
+    let response = CursorPaginatedResponse::new(
+        inner_txs,
+        per_page,
+        next_cursor,
+        total_items,
+    );
+
+    Ok(Json(response.into()))
+}
+
 fn is_valid_hash(hash: &str) -> Result<(), TransactionError> {
     if hash.len().eq(&64) {
         Ok(())
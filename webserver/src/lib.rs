@@ -1,10 +1,16 @@
 pub mod app;
 pub mod appstate;
+pub mod cache;
 pub mod config;
 pub mod constant;
 pub mod dto;
 pub mod error;
+pub mod event_bus;
+pub mod freshness;
+pub mod graphql;
 pub mod handler;
+pub mod openapi;
+pub mod rate_limit;
 pub mod repository;
 pub mod response;
 pub mod service;
@@ -0,0 +1,207 @@
+use utoipa::OpenApi;
+
+use crate::dto::balance::BalancesDto;
+use crate::dto::crawler_state::CrawlerNameDto;
+use crate::dto::event::EventKindDto;
+use crate::dto::export::ExportFormatDto;
+use crate::dto::governance::{
+    ProposalKind, ProposalStatus as ProposalStatusDto,
+};
+use crate::dto::pos::{
+    MyValidatorKindDto, OrderByDto, ValidatorSortFieldDto, ValidatorStateDto,
+};
+use crate::handler::{
+    balance as balance_handlers, chain as chain_handlers,
+    crawler_state as crawler_state_handlers, event as event_handlers,
+    export as export_handlers, gas as gas_handlers, governance as gov_handlers,
+    pk as pk_handlers, pos as pos_handlers,
+    transaction as transaction_handlers,
+};
+use crate::response::api::ApiErrorResponse;
+use crate::response::balance::{AccountBalances, AddressBalance};
+use crate::response::chain::{
+    ChainStatus, EpochTokenSupply, IbcToken, LastProcessedBlock,
+    LastProcessedEpoch, NativeToken, Parameters, RpcUrl, Token,
+    TokenDistribution, TokenHolder, TokenMetadata, TokenSupply,
+};
+use crate::response::crawler_state::CrawlersTimestamps;
+use crate::response::export::{
+    BalanceChangeExport, BondExport, RewardClaimExport, TransactionExport,
+    UnbondExport,
+};
+use crate::response::gas::{Gas, GasPrice};
+use crate::response::governance::{
+    Proposal, ProposalStatus, ProposalType, ProposalVote, TallyType, VoteType,
+};
+use crate::response::pos::{
+    AccruedReward, Bond, BondStatus, ClaimedReward, Delegation,
+    EstimatedRewards, MergedBond, MyValidator, Redelegation, Reward,
+    RewardHistory, Slash, SlashType, TotalVotingPower, Unbond, Validator,
+    ValidatorBlock, ValidatorBlockKind, ValidatorDetail, ValidatorState,
+    ValidatorUptime, ValidatorWithId, Withdraw,
+};
+use crate::response::revealed_pk::RevealedPk;
+use crate::response::transaction::{
+    InnerTransaction, ShortInnerTransaction, TransactionKind,
+    TransactionResult, WrapperTransaction,
+};
+use crate::response::utils::{
+    BondPage, CursorPagination, DelegationPage, InnerTransactionList,
+    ListPagination, MergedBondPage, MyValidatorPage, Pagination, ProposalPage,
+    ProposalVoteList, RedelegationPage, TokenHolderPage, UnbondPage,
+    ValidatorBlockList, ValidatorPage, WithdrawPage,
+};
+
+/// OpenAPI document of the REST endpoints, derived from the handlers and
+/// the types they accept and return
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Namada Indexer REST Api"),
+    paths(
+        pos_handlers::get_validators,
+        pos_handlers::get_all_validators,
+        pos_handlers::get_validator,
+        pos_handlers::get_validator_delegations,
+        pos_handlers::get_estimated_rewards,
+        pos_handlers::get_validator_blocks,
+        pos_handlers::get_validator_slashes,
+        pos_handlers::get_my_validators,
+        pos_handlers::get_bonds,
+        pos_handlers::get_merged_bonds,
+        pos_handlers::get_unbonds,
+        pos_handlers::get_merged_unbonds,
+        pos_handlers::get_withdraws,
+        pos_handlers::get_redelegations,
+        pos_handlers::get_rewards,
+        pos_handlers::get_rewards_history,
+        pos_handlers::get_total_voting_power,
+        gov_handlers::get_governance_proposals,
+        gov_handlers::get_all_governance_proposals,
+        gov_handlers::get_governance_proposal_by_id,
+        gov_handlers::get_governance_proposal_votes,
+        gov_handlers::get_governance_proposal_votes_by_address,
+        gov_handlers::get_governance_proposal_votes_by_voter,
+        balance_handlers::get_addresses_balances,
+        balance_handlers::get_address_balance,
+        export_handlers::export_transactions,
+        export_handlers::export_balance_changes,
+        export_handlers::export_bonds,
+        export_handlers::export_unbonds,
+        export_handlers::export_reward_claims,
+        pk_handlers::get_revealed_pk,
+        gas_handlers::get_gas_by_token,
+        gas_handlers::get_gas_price_by_token,
+        transaction_handlers::get_wrapper_tx,
+        transaction_handlers::get_inner_tx,
+        transaction_handlers::get_inner_txs_by_address,
+        chain_handlers::get_parameters,
+        chain_handlers::get_rpc_url,
+        chain_handlers::get_tokens,
+        chain_handlers::get_token_supply,
+        chain_handlers::get_token_holders,
+        chain_handlers::get_token_distribution,
+        chain_handlers::get_last_processed_block,
+        chain_handlers::get_last_processed_epoch,
+        crawler_state_handlers::get_crawlers_timestamps,
+        chain_handlers::chain_status,
+        event_handlers::subscribe_events,
+    ),
+    components(schemas(
+        ApiErrorResponse,
+        Pagination,
+        CursorPagination,
+        ListPagination,
+        ValidatorPage,
+        DelegationPage,
+        MyValidatorPage,
+        BondPage,
+        MergedBondPage,
+        UnbondPage,
+        WithdrawPage,
+        RedelegationPage,
+        ProposalPage,
+        TokenHolderPage,
+        ValidatorBlockList,
+        ProposalVoteList,
+        InnerTransactionList,
+        ValidatorStateDto,
+        OrderByDto,
+        ValidatorSortFieldDto,
+        MyValidatorKindDto,
+        ProposalStatusDto,
+        ProposalKind,
+        CrawlerNameDto,
+        EventKindDto,
+        ExportFormatDto,
+        BalancesDto,
+        Validator,
+        ValidatorState,
+        ValidatorUptime,
+        ValidatorWithId,
+        ValidatorDetail,
+        ValidatorBlock,
+        ValidatorBlockKind,
+        EstimatedRewards,
+        Delegation,
+        Slash,
+        SlashType,
+        Bond,
+        BondStatus,
+        MergedBond,
+        MyValidator,
+        Unbond,
+        Withdraw,
+        Redelegation,
+        Reward,
+        AccruedReward,
+        ClaimedReward,
+        RewardHistory,
+        TotalVotingPower,
+        Proposal,
+        ProposalType,
+        ProposalStatus,
+        TallyType,
+        ProposalVote,
+        VoteType,
+        AddressBalance,
+        AccountBalances,
+        TransactionExport,
+        BalanceChangeExport,
+        BondExport,
+        UnbondExport,
+        RewardClaimExport,
+        RevealedPk,
+        Gas,
+        GasPrice,
+        WrapperTransaction,
+        ShortInnerTransaction,
+        InnerTransaction,
+        TransactionKind,
+        TransactionResult,
+        Parameters,
+        RpcUrl,
+        ChainStatus,
+        Token,
+        NativeToken,
+        IbcToken,
+        TokenMetadata,
+        TokenSupply,
+        EpochTokenSupply,
+        TokenHolder,
+        TokenDistribution,
+        LastProcessedBlock,
+        LastProcessedEpoch,
+        CrawlersTimestamps,
+    )),
+    tags(
+        (name = "pos", description = "Validators, bonds, unbonds and rewards"),
+        (name = "governance", description = "Proposals and votes"),
+        (name = "account", description = "Balances, public keys and exports"),
+        (name = "gas", description = "Gas limits and prices"),
+        (name = "transaction", description = "Wrapper and inner transactions"),
+        (name = "chain", description = "Chain parameters, tokens and status"),
+        (name = "crawler", description = "Crawlers activity"),
+        (name = "events", description = "Indexer events subscription"),
+    )
+)]
+pub struct ApiDoc;
@@ -0,0 +1,268 @@
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use axum::extract::{ConnectInfo, MatchedPath, Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use lru::LruCache;
+
+use crate::config::AppConfig;
+use crate::response::api::ApiErrorResponse;
+
+const API_KEY_HEADER: &str = "x-api-key";
+const RATE_LIMIT_LIMIT: &str = "ratelimit-limit";
+const RATE_LIMIT_REMAINING: &str = "ratelimit-remaining";
+const RATE_LIMIT_RESET: &str = "ratelimit-reset";
+const FORWARDED_FOR: &str = "x-forwarded-for";
+const REAL_IP: &str = "x-real-ip";
+/// Least recently seen clients are evicted past this number. Their bucket
+/// would have been refilled anyway unless they were seen very recently.
+const MAX_TRACKED_CLIENTS: usize = 100_000;
+
+/// Per client token bucket: each bucket holds up to one second worth of
+/// requests and refills continuously at the client rate.
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+struct Decision {
+    allowed: bool,
+    limit: u64,
+    remaining: u64,
+    reset: u64,
+    retry_after: u64,
+}
+
+#[derive(Clone)]
+pub struct RateLimiter {
+    rps: u64,
+    api_key_rps: u64,
+    api_keys: Arc<HashSet<String>>,
+    costs: Arc<HashMap<String, u64>>,
+    trusted_proxies: Arc<HashSet<IpAddr>>,
+    buckets: Arc<Mutex<LruCache<String, Bucket>>>,
+}
+
+impl RateLimiter {
+    /// Returns `None` when no `rps` is configured, in which case requests are
+    /// not rate limited.
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        let rps = config.rps?.max(1);
+
+        Some(Self {
+            rps,
+            api_key_rps: config
+                .api_key_rps
+                .unwrap_or(rps.saturating_mul(10))
+                .max(1),
+            api_keys: Arc::new(config.api_keys.iter().cloned().collect()),
+            costs: Arc::new(
+                config
+                    .rate_limit_costs
+                    .iter()
+                    .map(|route_cost| {
+                        (route_cost.route.clone(), route_cost.cost)
+                    })
+                    .collect(),
+            ),
+            trusted_proxies: Arc::new(
+                config.trusted_proxies.iter().copied().collect(),
+            ),
+            buckets: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(MAX_TRACKED_CLIENTS)
+                    .unwrap_or(NonZeroUsize::MIN),
+            ))),
+        })
+    }
+
+    fn check(&self, client: String, limit: u64, cost: u64) -> Decision {
+        let now = Instant::now();
+        let capacity = limit as f64;
+        let cost = cost.min(limit) as f64;
+
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.get_or_insert_mut(client, || Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * capacity).min(capacity);
+        bucket.updated_at = now;
+
+        let allowed = bucket.tokens >= cost;
+        if allowed {
+            bucket.tokens -= cost;
+        }
+
+        Decision {
+            allowed,
+            limit,
+            remaining: bucket.tokens.floor() as u64,
+            reset: ((capacity - bucket.tokens) / capacity).ceil() as u64,
+            retry_after: ((cost - bucket.tokens).max(0.0) / capacity).ceil()
+                as u64,
+        }
+    }
+
+    fn client(&self, request: &Request) -> (String, u64) {
+        let headers = request.headers();
+
+        if let Some(api_key) = header_value(headers, API_KEY_HEADER) {
+            if self.api_keys.contains(api_key) {
+                return (format!("key:{}", api_key), self.api_key_rps);
+            }
+        }
+
+        let peer = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let ip = peer
+            .map(|peer| self.client_ip(peer, headers).to_string())
+            .unwrap_or_default();
+
+        (format!("ip:{}", ip), self.rps)
+    }
+
+    /// Forwarding headers can be forged by any client, so they are only read
+    /// when the request comes from a trusted proxy. The client is then the
+    /// rightmost hop that is not a trusted proxy itself.
+    fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        if !self.trusted_proxies.contains(&peer) {
+            return peer;
+        }
+
+        let mut hops = header_value(headers, FORWARDED_FOR)
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|hop| hop.trim().parse::<IpAddr>().ok())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if hops.is_empty() {
+            hops.push(
+                header_value(headers, REAL_IP)
+                    .and_then(|ip| ip.trim().parse().ok()),
+            );
+        }
+
+        for hop in hops.into_iter().rev() {
+            match hop {
+                Some(ip) if self.trusted_proxies.contains(&ip) => continue,
+                Some(ip) => return ip,
+                // A malformed hop can't be attributed, the last trusted
+                // proxy is accountable for it
+                None => break,
+            }
+        }
+
+        peer
+    }
+
+    fn cost(&self, request: &Request) -> u64 {
+        request
+            .extensions()
+            .get::<MatchedPath>()
+            .and_then(|path| self.costs.get(path.as_str()))
+            .copied()
+            .unwrap_or(1)
+    }
+}
+
+fn header_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| !value.is_empty())
+}
+
+fn set_rate_limit_headers(headers: &mut HeaderMap, decision: &Decision) {
+    headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from(decision.limit));
+    headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from(decision.remaining));
+    headers.insert(RATE_LIMIT_RESET, HeaderValue::from(decision.reset));
+}
+
+pub async fn rate_limit(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (client, limit) = limiter.client(&request);
+    let cost = limiter.cost(&request);
+    let decision = limiter.check(client, limit, cost);
+
+    let mut response = if decision.allowed {
+        next.run(request).await
+    } else {
+        let mut response = ApiErrorResponse::send(
+            StatusCode::TOO_MANY_REQUESTS.as_u16(),
+            Some("Too many requests".to_string()),
+        );
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(decision.retry_after.max(1)),
+        );
+        response
+    };
+
+    set_rate_limit_headers(response.headers_mut(), &decision);
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(trusted_proxies: &[&str]) -> RateLimiter {
+        RateLimiter {
+            rps: 1,
+            api_key_rps: 1,
+            api_keys: Arc::default(),
+            costs: Arc::default(),
+            trusted_proxies: Arc::new(
+                trusted_proxies
+                    .iter()
+                    .map(|ip| ip.parse().unwrap())
+                    .collect(),
+            ),
+            buckets: Arc::new(Mutex::new(LruCache::new(NonZeroUsize::MIN))),
+        }
+    }
+
+    fn forwarded_for(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(FORWARDED_FOR, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    /// Test that forwarding headers sent directly by a client are ignored.
+    #[test]
+    fn test_client_ip_ignores_untrusted_forwarded_for() {
+        let limiter = limiter(&["10.0.0.1"]);
+        let peer = "1.2.3.4".parse().unwrap();
+
+        let ip = limiter.client_ip(peer, &forwarded_for("5.6.7.8"));
+
+        assert_eq!(ip, peer);
+    }
+
+    /// Test that the client behind trusted proxies is the rightmost untrusted
+    /// hop, so that hops prepended by the client are ignored.
+    #[test]
+    fn test_client_ip_takes_rightmost_untrusted_hop() {
+        let limiter = limiter(&["10.0.0.1", "10.0.0.2"]);
+        let peer = "10.0.0.1".parse().unwrap();
+
+        let ip = limiter
+            .client_ip(peer, &forwarded_for("5.6.7.8, 1.2.3.4, 10.0.0.2"));
+
+        assert_eq!(ip, "1.2.3.4".parse::<IpAddr>().unwrap());
+    }
+}
@@ -1,7 +1,10 @@
 use axum::async_trait;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
+use diesel::{
+    ExpressionMethods, JoinOnDsl, NullableExpressionMethods, QueryDsl,
+    RunQueryDsl, SelectableHelper,
+};
 use orm::balances::BalanceDb;
-use orm::schema::balances;
+use orm::schema::{balances, token_metadata};
 
 use crate::appstate::AppState;
 
@@ -17,7 +20,13 @@ pub trait BalanceRepoTrait {
     async fn get_address_balances(
         &self,
         address: String,
-    ) -> Result<Vec<BalanceDb>, String>;
+    ) -> Result<Vec<(BalanceDb, Option<i32>)>, String>;
+
+    async fn get_addresses_balances(
+        &self,
+        addresses: Vec<String>,
+        tokens: Option<Vec<String>>,
+    ) -> Result<Vec<(BalanceDb, Option<i32>)>, String>;
 }
 
 #[async_trait]
@@ -29,13 +38,52 @@ impl BalanceRepoTrait for BalanceRepo {
     async fn get_address_balances(
         &self,
         address: String,
-    ) -> Result<Vec<BalanceDb>, String> {
+    ) -> Result<Vec<(BalanceDb, Option<i32>)>, String> {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
             balances::table
+                .left_join(
+                    token_metadata::table
+                        .on(token_metadata::address.eq(balances::token)),
+                )
                 .filter(balances::dsl::owner.eq(address))
-                .select(BalanceDb::as_select())
+                .select((
+                    BalanceDb::as_select(),
+                    token_metadata::denomination.nullable(),
+                ))
+                .get_results(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn get_addresses_balances(
+        &self,
+        addresses: Vec<String>,
+        tokens: Option<Vec<String>>,
+    ) -> Result<Vec<(BalanceDb, Option<i32>)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = balances::table
+                .left_join(
+                    token_metadata::table
+                        .on(token_metadata::address.eq(balances::token)),
+                )
+                .filter(balances::dsl::owner.eq_any(addresses))
+                .into_boxed();
+
+            if let Some(tokens) = tokens {
+                query = query.filter(balances::dsl::token.eq_any(tokens));
+            }
+
+            query
+                .select((
+                    BalanceDb::as_select(),
+                    token_metadata::denomination.nullable(),
+                ))
                 .get_results(conn)
         })
         .await
@@ -1,12 +1,20 @@
 use axum::async_trait;
-use diesel::dsl::max;
+use diesel::dsl::{max, not};
 use diesel::{
-    ExpressionMethods, JoinOnDsl, QueryDsl, RunQueryDsl, SelectableHelper,
+    ExpressionMethods, JoinOnDsl, OptionalExtension, QueryDsl, RunQueryDsl,
+    SelectableHelper,
 };
+use orm::balances::BalanceDb;
 use orm::crawler_state::{ChainCrawlerStateDb, CrawlerNameDb};
 use orm::parameters::ParametersDb;
-use orm::schema::{chain_parameters, crawler_state, ibc_token, token};
-use orm::token::{IbcTokenDb, TokenDb};
+use orm::schema::{
+    chain_parameters, crawler_state, ibc_token, token, token_metadata,
+    token_supplies_per_epoch,
+};
+use orm::token::{
+    IbcTokenDb, TokenDb, TokenDistributionDb, TokenMetadataDb, TokenSupplyDb,
+};
+use orm::views::{token_distribution, token_holders};
 
 use crate::appstate::AppState;
 
@@ -21,6 +29,9 @@ pub trait ChainRepositoryTrait {
 
     async fn find_latest_height(&self) -> Result<i32, String>;
 
+    /// `None` until the chain crawler processed its first block
+    async fn find_last_processed_block(&self) -> Result<Option<i32>, String>;
+
     async fn find_latest_epoch(&self) -> Result<i32, String>;
 
     async fn find_chain_parameters(&self) -> Result<ParametersDb, String>;
@@ -29,7 +40,41 @@ pub trait ChainRepositoryTrait {
 
     async fn find_tokens(
         &self,
-    ) -> Result<Vec<(TokenDb, Option<IbcTokenDb>)>, String>;
+    ) -> Result<
+        Vec<(TokenDb, Option<IbcTokenDb>, Option<TokenMetadataDb>)>,
+        String,
+    >;
+
+    async fn find_token_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<(TokenDb, Option<TokenMetadataDb>)>, String>;
+
+    async fn find_token_supplies(
+        &self,
+        address: String,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<TokenSupplyDb>, String>;
+
+    async fn find_latest_token_supply(
+        &self,
+        address: String,
+    ) -> Result<Option<TokenSupplyDb>, String>;
+
+    async fn find_token_holders(
+        &self,
+        address: String,
+        exclude_internal: bool,
+        page: i64,
+        per_page: i64,
+    ) -> Result<Vec<BalanceDb>, String>;
+
+    async fn find_token_distribution(
+        &self,
+        address: String,
+        exclude_internal: bool,
+    ) -> Result<Option<TokenDistributionDb>, String>;
 }
 
 #[async_trait]
@@ -39,6 +84,12 @@ impl ChainRepositoryTrait for ChainRepository {
     }
 
     async fn find_latest_height(&self) -> Result<i32, String> {
+        self.find_last_processed_block()
+            .await
+            .and_then(|x| x.ok_or("No block processed".to_string()))
+    }
+
+    async fn find_last_processed_block(&self) -> Result<Option<i32>, String> {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
@@ -50,7 +101,6 @@ impl ChainRepositoryTrait for ChainRepository {
         .await
         .map_err(|e| e.to_string())?
         .map_err(|e| e.to_string())
-        .and_then(|x| x.ok_or("No block processed".to_string()))
     }
 
     async fn find_latest_epoch(&self) -> Result<i32, String> {
@@ -102,7 +152,10 @@ impl ChainRepositoryTrait for ChainRepository {
 
     async fn find_tokens(
         &self,
-    ) -> Result<Vec<(TokenDb, Option<IbcTokenDb>)>, String> {
+    ) -> Result<
+        Vec<(TokenDb, Option<IbcTokenDb>, Option<TokenMetadataDb>)>,
+        String,
+    > {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
@@ -110,11 +163,150 @@ impl ChainRepositoryTrait for ChainRepository {
                 .left_join(
                     ibc_token::table.on(token::address.eq(ibc_token::address)),
                 )
+                .left_join(token_metadata::table)
                 .select((
                     TokenDb::as_select(),
                     Option::<IbcTokenDb>::as_select(),
+                    Option::<TokenMetadataDb>::as_select(),
                 ))
-                .load::<(TokenDb, Option<IbcTokenDb>)>(conn)
+                .load::<(TokenDb, Option<IbcTokenDb>, Option<TokenMetadataDb>)>(
+                    conn,
+                )
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_token_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<(TokenDb, Option<TokenMetadataDb>)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            token::table
+                .left_join(token_metadata::table)
+                .filter(token::address.eq(address))
+                .select((
+                    TokenDb::as_select(),
+                    Option::<TokenMetadataDb>::as_select(),
+                ))
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_token_supplies(
+        &self,
+        address: String,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<TokenSupplyDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = token_supplies_per_epoch::table
+                .filter(token_supplies_per_epoch::address.eq(address))
+                .into_boxed();
+
+            if let Some(from_epoch) = from_epoch {
+                query = query
+                    .filter(token_supplies_per_epoch::epoch.ge(from_epoch));
+            }
+            if let Some(to_epoch) = to_epoch {
+                query =
+                    query.filter(token_supplies_per_epoch::epoch.le(to_epoch));
+            }
+
+            query
+                .order(token_supplies_per_epoch::epoch.asc())
+                .select(TokenSupplyDb::as_select())
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_latest_token_supply(
+        &self,
+        address: String,
+    ) -> Result<Option<TokenSupplyDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            token_supplies_per_epoch::table
+                .filter(token_supplies_per_epoch::address.eq(address))
+                .order(token_supplies_per_epoch::epoch.desc())
+                .select(TokenSupplyDb::as_select())
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    // The total count comes from the token_distribution view, so the
+    // holders are paged by hand from the token_holders view it is computed
+    // from, instead of counting them on every request
+    async fn find_token_holders(
+        &self,
+        address: String,
+        exclude_internal: bool,
+        page: i64,
+        per_page: i64,
+    ) -> Result<Vec<BalanceDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = token_holders::table
+                .filter(token_holders::token.eq(address))
+                .into_boxed();
+
+            if exclude_internal {
+                query = query.filter(not(token_holders::internal));
+            }
+
+            query
+                .order((
+                    token_holders::raw_amount.desc(),
+                    token_holders::owner.asc(),
+                ))
+                .limit(per_page)
+                .offset((page - 1) * per_page)
+                .select((
+                    token_holders::owner,
+                    token_holders::token,
+                    token_holders::raw_amount,
+                ))
+                .load::<BalanceDb>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_token_distribution(
+        &self,
+        address: String,
+        exclude_internal: bool,
+    ) -> Result<Option<TokenDistributionDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            token_distribution::table
+                .filter(token_distribution::token.eq(address))
+                .filter(
+                    token_distribution::exclude_internal.eq(exclude_internal),
+                )
+                .select(TokenDistributionDb::as_select())
+                .first(conn)
+                .optional()
         })
         .await
         .map_err(|e| e.to_string())?
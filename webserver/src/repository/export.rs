@@ -0,0 +1,460 @@
+use axum::async_trait;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::dsl::min;
+use diesel::{
+    ExpressionMethods, JoinOnDsl, NullableExpressionMethods, OptionalExtension,
+    QueryDsl, RunQueryDsl, SelectableHelper,
+};
+use orm::balances::BalanceChangeDb;
+use orm::bond::BondDb;
+use orm::pos_rewards::PoSRewardClaimDb;
+use orm::schema::{
+    balance_changes, blocks, bonds, inner_transaction_addresses,
+    inner_transactions, pos_rewards_claims, token_metadata, unbonds,
+    validators, wrapper_transactions,
+};
+use orm::transactions::{InnerTransactionDb, WrapperTransactionDb};
+use orm::unbond::UnbondDb;
+use orm::validators::ValidatorDb;
+
+use crate::appstate::AppState;
+
+/// Inclusive bounds on the block height of the exported rows
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HeightRange {
+    pub from: Option<i32>,
+    pub to: Option<i32>,
+}
+
+/// Epoch bounds on the exported bonds and unbonds
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EpochRange {
+    pub from: Option<i32>,
+    pub to: Option<i32>,
+}
+
+pub type TransactionExportDb = (
+    i32,
+    InnerTransactionDb,
+    WrapperTransactionDb,
+    Option<NaiveDateTime>,
+);
+pub type BalanceChangeExportDb =
+    (i32, BalanceChangeDb, Option<i32>, Option<NaiveDateTime>);
+pub type RewardClaimExportDb =
+    (i32, PoSRewardClaimDb, ValidatorDb, Option<NaiveDateTime>);
+
+#[derive(Clone)]
+pub struct ExportRepo {
+    pub(crate) app_state: AppState,
+}
+
+/// Every export is read in chunks of `limit` rows ordered by id, starting
+/// after the id of the last row of the previous chunk
+#[async_trait]
+pub trait ExportRepoTrait {
+    fn new(app_state: AppState) -> Self;
+
+    async fn find_first_block_at_or_after(
+        &self,
+        timestamp: NaiveDateTime,
+    ) -> Result<Option<i32>, String>;
+
+    async fn find_last_block_at_or_before(
+        &self,
+        timestamp: NaiveDateTime,
+    ) -> Result<Option<i32>, String>;
+
+    async fn find_transactions(
+        &self,
+        address: String,
+        range: HeightRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<TransactionExportDb>, String>;
+
+    async fn find_balance_changes(
+        &self,
+        address: String,
+        range: HeightRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<BalanceChangeExportDb>, String>;
+
+    async fn find_balance_before(
+        &self,
+        address: String,
+        token: String,
+        height: i32,
+    ) -> Result<Option<BigDecimal>, String>;
+
+    async fn find_bonds(
+        &self,
+        address: String,
+        range: EpochRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<(BondDb, ValidatorDb)>, String>;
+
+    async fn find_unbonds(
+        &self,
+        address: String,
+        range: EpochRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<(UnbondDb, ValidatorDb)>, String>;
+
+    async fn find_reward_claims(
+        &self,
+        address: String,
+        range: HeightRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<RewardClaimExportDb>, String>;
+
+    async fn find_transactions_history_start(
+        &self,
+    ) -> Result<Option<i32>, String>;
+
+    async fn find_balance_changes_history_start(
+        &self,
+    ) -> Result<Option<i32>, String>;
+
+    async fn find_reward_claims_history_start(
+        &self,
+    ) -> Result<Option<i32>, String>;
+}
+
+#[async_trait]
+impl ExportRepoTrait for ExportRepo {
+    fn new(app_state: AppState) -> Self {
+        Self { app_state }
+    }
+
+    async fn find_first_block_at_or_after(
+        &self,
+        timestamp: NaiveDateTime,
+    ) -> Result<Option<i32>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            blocks::table
+                .filter(blocks::dsl::timestamp.ge(timestamp))
+                .order(blocks::dsl::timestamp.asc())
+                .select(blocks::dsl::height)
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_last_block_at_or_before(
+        &self,
+        timestamp: NaiveDateTime,
+    ) -> Result<Option<i32>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            blocks::table
+                .filter(blocks::dsl::timestamp.le(timestamp))
+                .order(blocks::dsl::timestamp.desc())
+                .select(blocks::dsl::height)
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_transactions(
+        &self,
+        address: String,
+        range: HeightRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<TransactionExportDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = inner_transaction_addresses::table
+                .inner_join(
+                    inner_transactions::table
+                        .inner_join(wrapper_transactions::table),
+                )
+                .left_join(blocks::table.on(
+                    blocks::dsl::height.eq(wrapper_transactions::block_height),
+                ))
+                .filter(inner_transaction_addresses::dsl::address.eq(address))
+                .into_boxed();
+
+            if let Some(from) = range.from {
+                query = query
+                    .filter(wrapper_transactions::dsl::block_height.ge(from));
+            }
+            if let Some(to) = range.to {
+                query = query
+                    .filter(wrapper_transactions::dsl::block_height.le(to));
+            }
+            if let Some(after) = after {
+                query = query
+                    .filter(inner_transaction_addresses::dsl::id.gt(after));
+            }
+
+            query
+                .order(inner_transaction_addresses::dsl::id.asc())
+                .limit(limit)
+                .select((
+                    inner_transaction_addresses::dsl::id,
+                    InnerTransactionDb::as_select(),
+                    WrapperTransactionDb::as_select(),
+                    blocks::dsl::timestamp.nullable(),
+                ))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_balance_changes(
+        &self,
+        address: String,
+        range: HeightRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<BalanceChangeExportDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = balance_changes::table
+                .left_join(
+                    token_metadata::table
+                        .on(token_metadata::address.eq(balance_changes::token)),
+                )
+                .left_join(
+                    blocks::table
+                        .on(blocks::dsl::height.eq(balance_changes::height)),
+                )
+                .filter(balance_changes::dsl::owner.eq(address))
+                .into_boxed();
+
+            if let Some(from) = range.from {
+                query = query.filter(balance_changes::dsl::height.ge(from));
+            }
+            if let Some(to) = range.to {
+                query = query.filter(balance_changes::dsl::height.le(to));
+            }
+            if let Some(after) = after {
+                query = query.filter(balance_changes::dsl::id.gt(after));
+            }
+
+            query
+                .order(balance_changes::dsl::id.asc())
+                .limit(limit)
+                .select((
+                    balance_changes::dsl::id,
+                    BalanceChangeDb::as_select(),
+                    token_metadata::denomination.nullable(),
+                    blocks::dsl::timestamp.nullable(),
+                ))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_balance_before(
+        &self,
+        address: String,
+        token: String,
+        height: i32,
+    ) -> Result<Option<BigDecimal>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            balance_changes::table
+                .filter(balance_changes::dsl::owner.eq(address))
+                .filter(balance_changes::dsl::token.eq(token))
+                .filter(balance_changes::dsl::height.lt(height))
+                .order(balance_changes::dsl::height.desc())
+                .select(balance_changes::dsl::raw_amount)
+                .first(conn)
+                .optional()
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_bonds(
+        &self,
+        address: String,
+        range: EpochRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<(BondDb, ValidatorDb)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = bonds::table
+                .inner_join(validators::table)
+                .filter(bonds::dsl::address.eq(address))
+                .into_boxed();
+
+            if let Some(from) = range.from {
+                query = query.filter(bonds::dsl::start.ge(from));
+            }
+            if let Some(to) = range.to {
+                query = query.filter(bonds::dsl::start.le(to));
+            }
+            if let Some(after) = after {
+                query = query.filter(bonds::dsl::id.gt(after));
+            }
+
+            query
+                .order(bonds::dsl::id.asc())
+                .limit(limit)
+                .select((BondDb::as_select(), ValidatorDb::as_select()))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_unbonds(
+        &self,
+        address: String,
+        range: EpochRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<(UnbondDb, ValidatorDb)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = unbonds::table
+                .inner_join(validators::table)
+                .filter(unbonds::dsl::address.eq(address))
+                .into_boxed();
+
+            if let Some(from) = range.from {
+                query = query.filter(unbonds::dsl::withdraw_epoch.ge(from));
+            }
+            if let Some(to) = range.to {
+                query = query.filter(unbonds::dsl::withdraw_epoch.le(to));
+            }
+            if let Some(after) = after {
+                query = query.filter(unbonds::dsl::id.gt(after));
+            }
+
+            query
+                .order(unbonds::dsl::id.asc())
+                .limit(limit)
+                .select((UnbondDb::as_select(), ValidatorDb::as_select()))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_reward_claims(
+        &self,
+        address: String,
+        range: HeightRange,
+        after: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<RewardClaimExportDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = pos_rewards_claims::table
+                .inner_join(validators::table)
+                .left_join(blocks::table.on(
+                    blocks::dsl::height.eq(pos_rewards_claims::block_height),
+                ))
+                .filter(pos_rewards_claims::dsl::owner.eq(address))
+                .into_boxed();
+
+            if let Some(from) = range.from {
+                query = query
+                    .filter(pos_rewards_claims::dsl::block_height.ge(from));
+            }
+            if let Some(to) = range.to {
+                query =
+                    query.filter(pos_rewards_claims::dsl::block_height.le(to));
+            }
+            if let Some(after) = after {
+                query = query.filter(pos_rewards_claims::dsl::id.gt(after));
+            }
+
+            query
+                .order(pos_rewards_claims::dsl::id.asc())
+                .limit(limit)
+                .select((
+                    pos_rewards_claims::dsl::id,
+                    PoSRewardClaimDb::as_select(),
+                    ValidatorDb::as_select(),
+                    blocks::dsl::timestamp.nullable(),
+                ))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_transactions_history_start(
+        &self,
+    ) -> Result<Option<i32>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            inner_transaction_addresses::table
+                .inner_join(
+                    inner_transactions::table
+                        .inner_join(wrapper_transactions::table),
+                )
+                .select(min(wrapper_transactions::dsl::block_height))
+                .first(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_balance_changes_history_start(
+        &self,
+    ) -> Result<Option<i32>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            balance_changes::table
+                .select(min(balance_changes::dsl::height))
+                .first(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_reward_claims_history_start(
+        &self,
+    ) -> Result<Option<i32>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            pos_rewards_claims::table
+                .select(min(pos_rewards_claims::dsl::block_height))
+                .first(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+}
@@ -12,7 +12,9 @@ use orm::governance_votes::GovernanceProposalVoteDb;
 use orm::schema::{governance_proposals, governance_votes};
 
 use crate::appstate::AppState;
-use crate::repository::utils::{Paginate, PaginatedResponseDb};
+use crate::repository::utils::{
+    into_keyset_page, KeysetResponseDb, Paginate, PaginatedResponseDb,
+};
 
 #[derive(Clone)]
 pub struct GovernanceRepo {
@@ -29,6 +31,7 @@ pub trait GovernanceRepoTrait {
         kind: Option<GovernanceProposalKindDb>,
         pattern: Option<String>,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<GovernanceProposalDb>, String>;
 
     async fn find_all_governance_proposals(
@@ -47,8 +50,21 @@ pub trait GovernanceRepoTrait {
         &self,
         proposal_id: i32,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<GovernanceProposalVoteDb>, String>;
 
+    async fn find_governance_proposal_votes_after(
+        &self,
+        proposal_id: i32,
+        after: Option<i32>,
+        per_page: i64,
+    ) -> Result<KeysetResponseDb<GovernanceProposalVoteDb>, String>;
+
+    async fn count_governance_proposal_votes(
+        &self,
+        proposal_id: i32,
+    ) -> Result<i64, String>;
+
     async fn find_governance_proposal_votes_by_address(
         &self,
         proposal_id: i32,
@@ -73,6 +89,7 @@ impl GovernanceRepoTrait for GovernanceRepo {
         kind: Option<GovernanceProposalKindDb>,
         pattern: Option<String>,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<GovernanceProposalDb>, String> {
         let conn = self.app_state.get_db_connection().await;
         let query = self.governance_proposals(status, kind, pattern);
@@ -81,6 +98,7 @@ impl GovernanceRepoTrait for GovernanceRepo {
             query
                 .select(GovernanceProposalDb::as_select())
                 .paginate(page)
+                .per_page(per_page)
                 .load_and_count_pages(conn)
         })
         .await
@@ -126,14 +144,17 @@ impl GovernanceRepoTrait for GovernanceRepo {
         &self,
         proposal_id: i32,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<GovernanceProposalVoteDb>, String> {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
             governance_votes::table
-                .filter(governance_votes::dsl::id.eq(proposal_id))
+                .filter(governance_votes::dsl::proposal_id.eq(proposal_id))
+                .order(governance_votes::dsl::id.asc())
                 .select(GovernanceProposalVoteDb::as_select())
                 .paginate(page)
+                .per_page(per_page)
                 .load_and_count_pages(conn)
         })
         .await
@@ -141,6 +162,52 @@ impl GovernanceRepoTrait for GovernanceRepo {
         .map_err(|e| e.to_string())
     }
 
+    async fn find_governance_proposal_votes_after(
+        &self,
+        proposal_id: i32,
+        after: Option<i32>,
+        per_page: i64,
+    ) -> Result<KeysetResponseDb<GovernanceProposalVoteDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = governance_votes::table
+                .filter(governance_votes::dsl::proposal_id.eq(proposal_id))
+                .into_boxed();
+
+            if let Some(after) = after {
+                query = query.filter(governance_votes::dsl::id.gt(after));
+            }
+
+            query
+                .order(governance_votes::dsl::id.asc())
+                .limit(per_page + 1)
+                .select(GovernanceProposalVoteDb::as_select())
+                .load(conn)
+                .map(|votes| into_keyset_page(votes, per_page))
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn count_governance_proposal_votes(
+        &self,
+        proposal_id: i32,
+    ) -> Result<i64, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            governance_votes::table
+                .filter(governance_votes::dsl::proposal_id.eq(proposal_id))
+                .count()
+                .get_result(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
     async fn find_governance_proposal_votes_by_address(
         &self,
         proposal_id: i32,
@@ -150,7 +217,7 @@ impl GovernanceRepoTrait for GovernanceRepo {
 
         conn.interact(move |conn| {
             governance_votes::table
-                .filter(governance_votes::dsl::id.eq(proposal_id).and(
+                .filter(governance_votes::dsl::proposal_id.eq(proposal_id).and(
                     governance_votes::dsl::voter_address.eq(voter_address),
                 ))
                 .select(GovernanceProposalVoteDb::as_select())
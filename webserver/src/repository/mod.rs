@@ -1,5 +1,6 @@
 pub mod balance;
 pub mod chain;
+pub mod export;
 pub mod gas;
 pub mod governance;
 pub mod pos;
@@ -1,22 +1,30 @@
 use axum::async_trait;
 use bigdecimal::BigDecimal;
-use diesel::dsl::{sql, sum};
+use diesel::dsl::{count_distinct, count_star, min, sql, sum};
 use diesel::sql_types::Integer;
 use diesel::{
-    BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl,
-    SelectableHelper,
+    BoolExpressionMethods, ExpressionMethods, PgConnection, QueryDsl,
+    QueryResult, RunQueryDsl, SelectableHelper,
 };
 use orm::bond::BondDb;
 use orm::crawler_state::{CrawlerNameDb, EpochCrawlerStateDb};
 use orm::helpers::OrderByDb;
-use orm::pos_rewards::PoSRewardDb;
-use orm::schema::{bonds, crawler_state, pos_rewards, unbonds, validators};
+use orm::pos_rewards::{PoSRewardClaimDb, PoSRewardDb, PoSRewardHistoryDb};
+use orm::redelegations::RedelegationDb;
+use orm::schema::{
+    bonds, crawler_state, pos_rewards, pos_rewards_claims, pos_rewards_history,
+    redelegations, slashes, unbonds, validator_blocks, validators,
+};
+use orm::slashes::SlashDb;
 use orm::unbond::UnbondDb;
+use orm::validator_blocks::{ValidatorBlockDb, ValidatorBlockKindDb};
 use orm::validators::{
     validator_sort_by, ValidatorDb, ValidatorSortByDb, ValidatorStateDb,
 };
 
-use super::utils::{Paginate, PaginatedResponseDb};
+use super::utils::{
+    into_keyset_page, KeysetResponseDb, Paginate, PaginatedResponseDb,
+};
 use crate::appstate::AppState;
 
 #[derive(Clone)]
@@ -24,6 +32,11 @@ pub struct PosRepository {
     pub(crate) app_state: AppState,
 }
 
+/// Delegator, validator, amount, withdraw epoch and earliest start epoch of
+/// unbonds merged by withdraw epoch
+pub type MergedUnbondDb =
+    (String, ValidatorDb, Option<BigDecimal>, i32, Option<i32>);
+
 #[async_trait]
 pub trait PosRepositoryTrait {
     fn new(app_state: AppState) -> Self;
@@ -31,6 +44,7 @@ pub trait PosRepositoryTrait {
     async fn find_validators(
         &self,
         page: i64,
+        per_page: i64,
         states: Vec<ValidatorStateDb>,
         sort_by: Option<(ValidatorSortByDb, OrderByDb)>,
     ) -> Result<PaginatedResponseDb<ValidatorDb>, String>;
@@ -47,10 +61,70 @@ pub trait PosRepositoryTrait {
         id: i32,
     ) -> Result<Option<ValidatorDb>, String>;
 
+    async fn find_validator_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<ValidatorDb>, String>;
+
+    async fn find_validators_blocks_count(
+        &self,
+        validator_ids: Vec<i32>,
+        after_height: i32,
+    ) -> Result<Vec<(i32, ValidatorBlockKindDb, i64)>, String>;
+
+    async fn find_validator_blocks(
+        &self,
+        validator_id: i32,
+        kinds: Vec<ValidatorBlockKindDb>,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<ValidatorBlockDb>, String>;
+
+    async fn find_validator_blocks_before(
+        &self,
+        validator_id: i32,
+        kinds: Vec<ValidatorBlockKindDb>,
+        before: Option<(i32, i32)>,
+        per_page: i64,
+    ) -> Result<KeysetResponseDb<(i32, ValidatorBlockDb)>, String>;
+
+    async fn count_validator_blocks(
+        &self,
+        validator_id: i32,
+        kinds: Vec<ValidatorBlockKindDb>,
+    ) -> Result<i64, String>;
+
+    async fn find_slashes_by_validator_ids(
+        &self,
+        validator_ids: Vec<i32>,
+    ) -> Result<Vec<SlashDb>, String>;
+
+    async fn find_validator_self_bond(
+        &self,
+        validator_id: i32,
+        validator_address: String,
+    ) -> Result<Option<BigDecimal>, String>;
+
+    async fn find_validator_delegations_summary(
+        &self,
+        validator_id: i32,
+        validator_address: String,
+    ) -> Result<(Option<BigDecimal>, i64), String>;
+
+    async fn find_validator_delegations(
+        &self,
+        validator_id: i32,
+        validator_address: String,
+        order_by: OrderByDb,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<(String, Option<BigDecimal>)>, String>;
+
     async fn find_merged_bonds_by_address(
         &self,
         address: String,
         page: i64,
+        per_page: i64,
     ) -> Result<
         PaginatedResponseDb<(String, ValidatorDb, Option<BigDecimal>)>,
         String,
@@ -60,12 +134,28 @@ pub trait PosRepositoryTrait {
         &self,
         address: String,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<(ValidatorDb, BondDb)>, String>;
 
+    async fn find_validators_with_bonds_by_addresses(
+        &self,
+        addresses: Vec<String>,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<(ValidatorDb, Option<BigDecimal>)>, String>;
+
+    async fn find_validators_with_unbonds_by_addresses(
+        &self,
+        addresses: Vec<String>,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<(ValidatorDb, Option<BigDecimal>)>, String>;
+
     async fn find_unbonds_by_address(
         &self,
         address: String,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<(ValidatorDb, UnbondDb)>, String>;
 
     async fn find_merged_unbonds_by_address(
@@ -73,16 +163,15 @@ pub trait PosRepositoryTrait {
         address: String,
         current_epoch: i32,
         page: i64,
-    ) -> Result<
-        PaginatedResponseDb<(String, ValidatorDb, Option<BigDecimal>, i32)>,
-        String,
-    >;
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<MergedUnbondDb>, String>;
 
     async fn find_withdraws_by_address(
         &self,
         address: String,
         current_epoch: i32,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<(ValidatorDb, UnbondDb)>, String>;
 
     async fn find_rewards_by_address(
@@ -90,11 +179,76 @@ pub trait PosRepositoryTrait {
         address: String,
     ) -> Result<Vec<PoSRewardDb>, String>;
 
+    async fn find_rewards_history_by_address(
+        &self,
+        address: String,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<(ValidatorDb, PoSRewardHistoryDb)>, String>;
+
+    async fn find_reward_claims_by_address(
+        &self,
+        address: String,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<(ValidatorDb, PoSRewardClaimDb)>, String>;
+
+    async fn find_redelegations_by_address(
+        &self,
+        address: String,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<RedelegationDb>, String>;
+
+    async fn find_validators_by_ids(
+        &self,
+        ids: Vec<i32>,
+    ) -> Result<Vec<ValidatorDb>, String>;
+
     async fn get_total_voting_power(&self) -> Result<Option<i64>, String>;
 
     async fn get_state(&self) -> Result<EpochCrawlerStateDb, String>;
 }
 
+/// Blocks of the validator before the `(height, id)` cursor, newest first.
+/// A validator can both propose and sign a block, so the height alone isn't
+/// unique.
+fn validator_blocks_before(
+    conn: &mut PgConnection,
+    validator_id: i32,
+    kinds: Vec<ValidatorBlockKindDb>,
+    before: Option<(i32, i32)>,
+    per_page: i64,
+) -> QueryResult<KeysetResponseDb<(i32, ValidatorBlockDb)>> {
+    let mut query = validator_blocks::table
+        .filter(
+            validator_blocks::dsl::validator_id
+                .eq(validator_id)
+                .and(validator_blocks::dsl::kind.eq_any(kinds)),
+        )
+        .into_boxed();
+
+    if let Some((height, id)) = before {
+        query = query.filter(
+            validator_blocks::dsl::height.lt(height).or(
+                validator_blocks::dsl::height
+                    .eq(height)
+                    .and(validator_blocks::dsl::id.lt(id)),
+            ),
+        );
+    }
+
+    query
+        .order((
+            validator_blocks::dsl::height.desc(),
+            validator_blocks::dsl::id.desc(),
+        ))
+        .limit(per_page + 1)
+        .select((validator_blocks::dsl::id, ValidatorBlockDb::as_select()))
+        .load(conn)
+        .map(|blocks| into_keyset_page(blocks, per_page))
+}
+
 #[async_trait]
 impl PosRepositoryTrait for PosRepository {
     fn new(app_state: AppState) -> Self {
@@ -104,6 +258,7 @@ impl PosRepositoryTrait for PosRepository {
     async fn find_validators(
         &self,
         page: i64,
+        per_page: i64,
         states: Vec<ValidatorStateDb>,
         sort_by: Option<(ValidatorSortByDb, OrderByDb)>,
     ) -> Result<PaginatedResponseDb<ValidatorDb>, String> {
@@ -121,6 +276,7 @@ impl PosRepositoryTrait for PosRepository {
             boxed
                 .select(ValidatorDb::as_select())
                 .paginate(page)
+                .per_page(per_page)
                 .load_and_count_pages(conn)
         })
         .await
@@ -179,10 +335,237 @@ impl PosRepositoryTrait for PosRepository {
         .map_err(|e| e.to_string())
     }
 
+    async fn find_validator_by_address(
+        &self,
+        address: String,
+    ) -> Result<Option<ValidatorDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validators::table
+                .filter(validators::dsl::namada_address.eq(address))
+                .select(ValidatorDb::as_select())
+                .first(conn)
+                .ok()
+        })
+        .await
+        .map_err(|e| e.to_string())
+    }
+
+    /// Returns the number of blocks of each kind for each validator after the
+    /// given height, counted from the validator_id and height index alone
+    async fn find_validators_blocks_count(
+        &self,
+        validator_ids: Vec<i32>,
+        after_height: i32,
+    ) -> Result<Vec<(i32, ValidatorBlockKindDb, i64)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validator_blocks::table
+                .filter(
+                    validator_blocks::dsl::validator_id
+                        .eq_any(validator_ids)
+                        .and(validator_blocks::dsl::height.gt(after_height)),
+                )
+                .group_by((
+                    validator_blocks::dsl::validator_id,
+                    validator_blocks::dsl::kind,
+                ))
+                .select((
+                    validator_blocks::dsl::validator_id,
+                    validator_blocks::dsl::kind,
+                    count_star(),
+                ))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_validator_blocks(
+        &self,
+        validator_id: i32,
+        kinds: Vec<ValidatorBlockKindDb>,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<ValidatorBlockDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validator_blocks::table
+                .filter(
+                    validator_blocks::dsl::validator_id
+                        .eq(validator_id)
+                        .and(validator_blocks::dsl::kind.eq_any(kinds)),
+                )
+                .order(validator_blocks::dsl::height.desc())
+                .select(ValidatorBlockDb::as_select())
+                .paginate(page)
+                .per_page(per_page)
+                .load_and_count_pages(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_validator_blocks_before(
+        &self,
+        validator_id: i32,
+        kinds: Vec<ValidatorBlockKindDb>,
+        before: Option<(i32, i32)>,
+        per_page: i64,
+    ) -> Result<KeysetResponseDb<(i32, ValidatorBlockDb)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validator_blocks_before(conn, validator_id, kinds, before, per_page)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn count_validator_blocks(
+        &self,
+        validator_id: i32,
+        kinds: Vec<ValidatorBlockKindDb>,
+    ) -> Result<i64, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validator_blocks::table
+                .filter(
+                    validator_blocks::dsl::validator_id
+                        .eq(validator_id)
+                        .and(validator_blocks::dsl::kind.eq_any(kinds)),
+                )
+                .count()
+                .get_result(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_slashes_by_validator_ids(
+        &self,
+        validator_ids: Vec<i32>,
+    ) -> Result<Vec<SlashDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            slashes::table
+                .filter(slashes::dsl::validator_id.eq_any(validator_ids))
+                .order(slashes::dsl::block_height.desc())
+                .select(SlashDb::as_select())
+                .get_results(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_validator_self_bond(
+        &self,
+        validator_id: i32,
+        validator_address: String,
+    ) -> Result<Option<BigDecimal>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            bonds::table
+                .filter(
+                    bonds::dsl::validator_id
+                        .eq(validator_id)
+                        .and(bonds::dsl::address.eq(validator_address)),
+                )
+                .select(sum(bonds::dsl::raw_amount))
+                .first(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    /// Returns the total amount delegated to a validator and the number of
+    /// delegators, not counting the validator self-bond
+    async fn find_validator_delegations_summary(
+        &self,
+        validator_id: i32,
+        validator_address: String,
+    ) -> Result<(Option<BigDecimal>, i64), String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            bonds::table
+                .filter(
+                    bonds::dsl::validator_id
+                        .eq(validator_id)
+                        .and(bonds::dsl::address.ne(validator_address)),
+                )
+                .select((
+                    sum(bonds::dsl::raw_amount),
+                    count_distinct(bonds::dsl::address),
+                ))
+                .first(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_validator_delegations(
+        &self,
+        validator_id: i32,
+        validator_address: String,
+        order_by: OrderByDb,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<(String, Option<BigDecimal>)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let query = bonds::table
+                .filter(
+                    bonds::dsl::validator_id
+                        .eq(validator_id)
+                        .and(bonds::dsl::address.ne(validator_address)),
+                )
+                .group_by(bonds::dsl::address)
+                .select((bonds::dsl::address, sum(bonds::dsl::raw_amount)));
+
+            match order_by {
+                OrderByDb::Asc => query
+                    .order((
+                        sum(bonds::dsl::raw_amount).asc(),
+                        bonds::dsl::address.asc(),
+                    ))
+                    .paginate(page)
+                    .per_page(per_page)
+                    .load_and_count_pages::<(String, Option<BigDecimal>)>(conn),
+                OrderByDb::Desc => query
+                    .order((
+                        sum(bonds::dsl::raw_amount).desc(),
+                        bonds::dsl::address.asc(),
+                    ))
+                    .paginate(page)
+                    .per_page(per_page)
+                    .load_and_count_pages::<(String, Option<BigDecimal>)>(conn),
+            }
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
     async fn find_bonds_by_address(
         &self,
         address: String,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<(ValidatorDb, BondDb)>, String> {
         let conn = self.app_state.get_db_connection().await;
 
@@ -192,6 +575,7 @@ impl PosRepositoryTrait for PosRepository {
                 .filter(bonds::dsl::address.eq(address))
                 .select((validators::all_columns, bonds::all_columns))
                 .paginate(page)
+                .per_page(per_page)
                 .load_and_count_pages::<(ValidatorDb, BondDb)>(conn)
         })
         .await
@@ -199,10 +583,65 @@ impl PosRepositoryTrait for PosRepository {
         .map_err(|e| e.to_string())
     }
 
+    /// Returns the validators the given addresses bonded to, along with the
+    /// total amount they bonded to each of them
+    async fn find_validators_with_bonds_by_addresses(
+        &self,
+        addresses: Vec<String>,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<(ValidatorDb, Option<BigDecimal>)>, String>
+    {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validators::table
+                .inner_join(bonds::table)
+                .filter(bonds::dsl::address.eq_any(addresses))
+                .group_by(validators::id)
+                .select((validators::all_columns, sum(bonds::raw_amount)))
+                .order((sum(bonds::raw_amount).desc(), validators::id.asc()))
+                .paginate(page)
+                .per_page(per_page)
+                .load_and_count_pages::<(ValidatorDb, Option<BigDecimal>)>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    /// Returns the validators the given addresses unbonded from, along with
+    /// the total amount they unbonded from each of them
+    async fn find_validators_with_unbonds_by_addresses(
+        &self,
+        addresses: Vec<String>,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<(ValidatorDb, Option<BigDecimal>)>, String>
+    {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validators::table
+                .inner_join(unbonds::table)
+                .filter(unbonds::dsl::address.eq_any(addresses))
+                .group_by(validators::id)
+                .select((validators::all_columns, sum(unbonds::raw_amount)))
+                .order((sum(unbonds::raw_amount).desc(), validators::id.asc()))
+                .paginate(page)
+                .per_page(per_page)
+                .load_and_count_pages::<(ValidatorDb, Option<BigDecimal>)>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
     async fn find_merged_bonds_by_address(
         &self,
         address: String,
         page: i64,
+        per_page: i64,
     ) -> Result<
         PaginatedResponseDb<(String, ValidatorDb, Option<BigDecimal>)>,
         String,
@@ -220,6 +659,7 @@ impl PosRepositoryTrait for PosRepository {
                     sum(bonds::raw_amount),
                 ))
                 .paginate(page)
+                .per_page(per_page)
                 // TODO: this is ok for now, create mixed aggragate later
                 .load_and_count_pages::<(String, ValidatorDb, Option<BigDecimal>)>(
                     conn,
@@ -235,6 +675,7 @@ impl PosRepositoryTrait for PosRepository {
         &self,
         address: String,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<(ValidatorDb, UnbondDb)>, String> {
         let conn = self.app_state.get_db_connection().await;
 
@@ -244,6 +685,7 @@ impl PosRepositoryTrait for PosRepository {
                 .filter(unbonds::dsl::address.eq(address))
                 .select((validators::all_columns, unbonds::all_columns))
                 .paginate(page)
+                .per_page(per_page)
                 .load_and_count_pages::<(ValidatorDb, UnbondDb)>(conn)
         })
         .await
@@ -256,25 +698,37 @@ impl PosRepositoryTrait for PosRepository {
         address: String,
         current_epoch: i32,
         page: i64,
-    ) -> Result<
-        PaginatedResponseDb<(String, ValidatorDb, Option<BigDecimal>, i32)>,
-        String,
-    > {
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<MergedUnbondDb>, String> {
         let conn = self.app_state.get_db_connection().await;
 
         conn.interact(move |conn| {
-                validators::table
+            validators::table
                 .inner_join(unbonds::table)
                 .filter(unbonds::dsl::address.eq(address))
-                .group_by((unbonds::address, validators::id, sql::<Integer>(&format!("CASE WHEN withdraw_epoch <= {} THEN 0 ELSE withdraw_epoch END", current_epoch))))
+                .group_by((
+                    unbonds::address,
+                    validators::id,
+                    sql::<Integer>(&format!(
+                        "CASE WHEN withdraw_epoch <= {} THEN 0 ELSE \
+                         withdraw_epoch END",
+                        current_epoch
+                    )),
+                ))
                 .select((
-                        unbonds::address,
-                        validators::all_columns,
-                        sum(unbonds::raw_amount),
-                        sql::<Integer>(&format!("CASE WHEN MIN(withdraw_epoch) <= {} THEN 0 ELSE MAX(withdraw_epoch) END AS withdraw_epoch", current_epoch))))
+                    unbonds::address,
+                    validators::all_columns,
+                    sum(unbonds::raw_amount),
+                    sql::<Integer>(&format!(
+                        "CASE WHEN MIN(withdraw_epoch) <= {} THEN 0 ELSE \
+                         MAX(withdraw_epoch) END AS withdraw_epoch",
+                        current_epoch
+                    )),
+                    min(unbonds::start_epoch),
+                ))
                 .paginate(page)
-                .load_and_count_pages::<(String, ValidatorDb, Option<BigDecimal>, i32)>(conn)
-
+                .per_page(per_page)
+                .load_and_count_pages::<MergedUnbondDb>(conn)
         })
         .await
         .map_err(|e| e.to_string())?
@@ -286,6 +740,7 @@ impl PosRepositoryTrait for PosRepository {
         address: String,
         current_epoch: i32,
         page: i64,
+        per_page: i64,
     ) -> Result<PaginatedResponseDb<(ValidatorDb, UnbondDb)>, String> {
         let conn = self.app_state.get_db_connection().await;
 
@@ -299,6 +754,7 @@ impl PosRepositoryTrait for PosRepository {
                 )
                 .select((validators::all_columns, unbonds::all_columns))
                 .paginate(page)
+                .per_page(per_page)
                 .load_and_count_pages::<(ValidatorDb, UnbondDb)>(conn)
         })
         .await
@@ -323,6 +779,123 @@ impl PosRepositoryTrait for PosRepository {
         .map_err(|e| e.to_string())
     }
 
+    async fn find_rewards_history_by_address(
+        &self,
+        address: String,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<(ValidatorDb, PoSRewardHistoryDb)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = validators::table
+                .inner_join(pos_rewards_history::table)
+                .filter(pos_rewards_history::dsl::owner.eq(address))
+                .into_boxed();
+
+            if let Some(from_epoch) = from_epoch {
+                query = query
+                    .filter(pos_rewards_history::dsl::epoch.ge(from_epoch));
+            }
+            if let Some(to_epoch) = to_epoch {
+                query =
+                    query.filter(pos_rewards_history::dsl::epoch.le(to_epoch));
+            }
+
+            query
+                .order((
+                    pos_rewards_history::dsl::epoch.asc(),
+                    validators::dsl::id.asc(),
+                ))
+                .select((
+                    ValidatorDb::as_select(),
+                    PoSRewardHistoryDb::as_select(),
+                ))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_reward_claims_by_address(
+        &self,
+        address: String,
+        from_epoch: Option<i32>,
+        to_epoch: Option<i32>,
+    ) -> Result<Vec<(ValidatorDb, PoSRewardClaimDb)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let mut query = validators::table
+                .inner_join(pos_rewards_claims::table)
+                .filter(pos_rewards_claims::dsl::owner.eq(address))
+                .into_boxed();
+
+            if let Some(from_epoch) = from_epoch {
+                query =
+                    query.filter(pos_rewards_claims::dsl::epoch.ge(from_epoch));
+            }
+            if let Some(to_epoch) = to_epoch {
+                query =
+                    query.filter(pos_rewards_claims::dsl::epoch.le(to_epoch));
+            }
+
+            query
+                .order(pos_rewards_claims::dsl::block_height.asc())
+                .select((
+                    ValidatorDb::as_select(),
+                    PoSRewardClaimDb::as_select(),
+                ))
+                .load(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_redelegations_by_address(
+        &self,
+        address: String,
+        page: i64,
+        per_page: i64,
+    ) -> Result<PaginatedResponseDb<RedelegationDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            redelegations::table
+                .filter(redelegations::dsl::delegator.eq(address))
+                .order((
+                    redelegations::dsl::epoch.desc(),
+                    redelegations::dsl::id.desc(),
+                ))
+                .select(RedelegationDb::as_select())
+                .paginate(page)
+                .per_page(per_page)
+                .load_and_count_pages::<RedelegationDb>(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_validators_by_ids(
+        &self,
+        ids: Vec<i32>,
+    ) -> Result<Vec<ValidatorDb>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            validators::table
+                .filter(validators::dsl::id.eq_any(ids))
+                .select(ValidatorDb::as_select())
+                .get_results(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
     async fn get_total_voting_power(&self) -> Result<Option<i64>, String> {
         let conn = self.app_state.get_db_connection().await;
 
@@ -353,3 +926,252 @@ impl PosRepositoryTrait for PosRepository {
         .map_err(|e| e.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use orm::bond::BondInsertDb;
+    use orm::validator_blocks::ValidatorBlockInsertDb;
+    use orm::validators::ValidatorInsertDb;
+    use shared::validator::Validator;
+    use test_helpers::db::TestDb;
+
+    use super::*;
+
+    fn insert_validator(
+        conn: &mut PgConnection,
+        address: &str,
+    ) -> anyhow::Result<ValidatorDb> {
+        Ok(diesel::insert_into(validators::table)
+            .values(ValidatorInsertDb {
+                namada_address: address.to_string(),
+                ..ValidatorInsertDb::from_validator(Validator::fake())
+            })
+            .get_result(conn)?)
+    }
+
+    /// Inserts bonds of `(address, validator id, amount)` starting at
+    /// distinct epochs
+    fn insert_bonds(
+        conn: &mut PgConnection,
+        bonds: &[(&str, i32, i32)],
+    ) -> anyhow::Result<()> {
+        diesel::insert_into(bonds::table)
+            .values(
+                bonds
+                    .iter()
+                    .enumerate()
+                    .map(|(start, (address, validator_id, amount))| {
+                        BondInsertDb {
+                            address: address.to_string(),
+                            validator_id: *validator_id,
+                            raw_amount: BigDecimal::from(*amount),
+                            start: start as i32,
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    fn pos_repository(db: &TestDb) -> PosRepository {
+        PosRepository::new(AppState::with_pool(
+            db.pool(),
+            "redis://127.0.0.1:1".to_string(),
+        ))
+    }
+
+    async fn find_validator(
+        repository: &PosRepository,
+        address: &str,
+    ) -> ValidatorDb {
+        repository
+            .find_validator_by_address(address.to_string())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    /// Test that the delegations of a validator leave out its self-bond and
+    /// sum the bonds of each delegator.
+    #[tokio::test]
+    async fn test_find_validator_delegations() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let validator = insert_validator(conn, "tnam1validator")?;
+            let other = insert_validator(conn, "tnam1other")?;
+            insert_bonds(
+                conn,
+                &[
+                    ("tnam1validator", validator.id, 1000),
+                    ("tnam1alice", validator.id, 10),
+                    ("tnam1alice", validator.id, 30),
+                    ("tnam1bob", validator.id, 40),
+                    ("tnam1carol", validator.id, 5),
+                    ("tnam1carol", other.id, 500),
+                ],
+            )?;
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+
+        let repository = pos_repository(&db);
+        let validator = find_validator(&repository, "tnam1validator").await;
+        let other = find_validator(&repository, "tnam1other").await;
+
+        let (total, delegators) = repository
+            .find_validator_delegations_summary(
+                validator.id,
+                validator.namada_address.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(total, Some(BigDecimal::from(85)));
+        assert_eq!(delegators, 3);
+
+        let (delegations, total_pages, total_items) = repository
+            .find_validator_delegations(
+                validator.id,
+                validator.namada_address.clone(),
+                OrderByDb::Desc,
+                1,
+                2,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            delegations,
+            vec![
+                ("tnam1alice".to_string(), Some(BigDecimal::from(40))),
+                ("tnam1bob".to_string(), Some(BigDecimal::from(40))),
+            ]
+        );
+        assert_eq!((total_pages, total_items), (2, 3));
+
+        let (delegations, _, _) = repository
+            .find_validator_delegations(
+                other.id,
+                other.namada_address,
+                OrderByDb::Asc,
+                1,
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            delegations,
+            vec![("tnam1carol".to_string(), Some(BigDecimal::from(500)))]
+        );
+    }
+
+    /// Test that the validators of several addresses are merged, summing
+    /// what every address bonded to each of them.
+    #[tokio::test]
+    async fn test_find_validators_with_bonds_by_addresses() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let first = insert_validator(conn, "tnam1first")?;
+            let second = insert_validator(conn, "tnam1second")?;
+            insert_validator(conn, "tnam1third")?;
+            insert_bonds(
+                conn,
+                &[
+                    ("tnam1alice", first.id, 10),
+                    ("tnam1alice", second.id, 20),
+                    ("tnam1bob", first.id, 15),
+                    ("tnam1carol", first.id, 1000),
+                ],
+            )?;
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+
+        let (validators, total_pages, total_items) = pos_repository(&db)
+            .find_validators_with_bonds_by_addresses(
+                vec!["tnam1alice".to_string(), "tnam1bob".to_string()],
+                1,
+                10,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            validators
+                .into_iter()
+                .map(|(validator, amount)| (validator.namada_address, amount))
+                .collect::<Vec<_>>(),
+            vec![
+                ("tnam1first".to_string(), Some(BigDecimal::from(25))),
+                ("tnam1second".to_string(), Some(BigDecimal::from(20))),
+            ]
+        );
+        assert_eq!((total_pages, total_items), (1, 2));
+    }
+
+    /// Test that a page ending between two blocks of the same height doesn't
+    /// skip the second one.
+    #[tokio::test]
+    async fn test_validator_blocks_before_with_same_height() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            let validator: ValidatorDb = diesel::insert_into(validators::table)
+                .values(ValidatorInsertDb::from_validator(Validator::fake()))
+                .get_result(conn)?;
+
+            let blocks = [
+                (1, ValidatorBlockKindDb::Signed),
+                (2, ValidatorBlockKindDb::Proposed),
+                (2, ValidatorBlockKindDb::Signed),
+                (3, ValidatorBlockKindDb::Missed),
+            ];
+            diesel::insert_into(validator_blocks::table)
+                .values(
+                    blocks
+                        .iter()
+                        .map(|(height, kind)| ValidatorBlockInsertDb {
+                            validator_id: validator.id,
+                            height: *height,
+                            kind: kind.clone(),
+                        })
+                        .collect::<Vec<_>>(),
+                )
+                .execute(conn)?;
+
+            let kinds = vec![
+                ValidatorBlockKindDb::Proposed,
+                ValidatorBlockKindDb::Signed,
+                ValidatorBlockKindDb::Missed,
+            ];
+            let mut heights = vec![];
+            let mut cursor = None;
+            loop {
+                let (page, has_next_page) = validator_blocks_before(
+                    conn,
+                    validator.id,
+                    kinds.clone(),
+                    cursor,
+                    2,
+                )?;
+                heights.extend(page.iter().map(|(_, block)| block.height));
+                cursor = page.last().map(|(id, block)| (block.height, *id));
+                if !has_next_page {
+                    break;
+                }
+            }
+
+            assert_eq!(heights, vec![3, 2, 2, 1]);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+}
@@ -1,9 +1,16 @@
 use axum::async_trait;
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
-use orm::schema::{inner_transactions, wrapper_transactions};
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, OptionalExtension, PgConnection,
+    QueryDsl, QueryResult, RunQueryDsl, SelectableHelper,
+};
+use orm::schema::{
+    inner_transaction_addresses, inner_transactions, token_metadata,
+    wrapper_transactions,
+};
 use orm::transactions::{InnerTransactionDb, WrapperTransactionDb};
 
 use crate::appstate::AppState;
+use crate::repository::utils::{into_keyset_page, KeysetResponseDb};
 
 #[derive(Clone)]
 pub struct TransactionRepository {
@@ -26,6 +33,63 @@ pub trait TransactionRepositoryTrait {
         &self,
         id: String,
     ) -> Result<Option<InnerTransactionDb>, String>;
+    async fn find_tokens_denomination(
+        &self,
+        tokens: Vec<String>,
+    ) -> Result<Vec<(String, i32)>, String>;
+    async fn find_inner_tx_with_context(
+        &self,
+        id: String,
+    ) -> Result<Option<(InnerTransactionDb, i32, Vec<String>)>, String>;
+    async fn find_inner_txs_by_address_before(
+        &self,
+        address: String,
+        before: Option<(i32, i32)>,
+        per_page: i64,
+    ) -> Result<KeysetResponseDb<(i32, i32, InnerTransactionDb)>, String>;
+    async fn count_inner_txs_by_address(
+        &self,
+        address: String,
+    ) -> Result<i64, String>;
+}
+
+/// Inner transactions of `address` before the `(block_height, id)` cursor,
+/// newest block first. Ranges crawled later are inserted with higher ids, so
+/// the id alone doesn't follow the chain.
+fn inner_txs_by_address_before(
+    conn: &mut PgConnection,
+    address: String,
+    before: Option<(i32, i32)>,
+    per_page: i64,
+) -> QueryResult<KeysetResponseDb<(i32, i32, InnerTransactionDb)>> {
+    let mut query = inner_transaction_addresses::table
+        .inner_join(inner_transactions::table)
+        .filter(inner_transaction_addresses::dsl::address.eq(address))
+        .into_boxed();
+
+    if let Some((block_height, id)) = before {
+        query = query.filter(
+            inner_transaction_addresses::dsl::block_height
+                .lt(block_height)
+                .or(inner_transaction_addresses::dsl::block_height
+                    .eq(block_height)
+                    .and(inner_transaction_addresses::dsl::id.lt(id))),
+        );
+    }
+
+    query
+        .order((
+            inner_transaction_addresses::dsl::block_height.desc(),
+            inner_transaction_addresses::dsl::id.desc(),
+        ))
+        .limit(per_page + 1)
+        .select((
+            inner_transaction_addresses::dsl::block_height,
+            inner_transaction_addresses::dsl::id,
+            InnerTransactionDb::as_select(),
+        ))
+        .load(conn)
+        .map(|inner_txs| into_keyset_page(inner_txs, per_page))
 }
 
 #[async_trait]
@@ -84,4 +148,181 @@ impl TransactionRepositoryTrait for TransactionRepository {
         .await
         .map_err(|e| e.to_string())
     }
+
+    async fn find_tokens_denomination(
+        &self,
+        tokens: Vec<String>,
+    ) -> Result<Vec<(String, i32)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            token_metadata::table
+                .filter(token_metadata::address.eq_any(tokens))
+                .select((token_metadata::address, token_metadata::denomination))
+                .get_results(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn find_inner_tx_with_context(
+        &self,
+        id: String,
+    ) -> Result<Option<(InnerTransactionDb, i32, Vec<String>)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            let inner_tx = inner_transactions::table
+                .inner_join(wrapper_transactions::table)
+                .filter(inner_transactions::dsl::id.eq(&id))
+                .select((
+                    InnerTransactionDb::as_select(),
+                    wrapper_transactions::dsl::block_height,
+                ))
+                .first(conn)
+                .optional()?;
+
+            let Some((inner_tx, block_height)) = inner_tx else {
+                return Ok(None);
+            };
+
+            let addresses = inner_transaction_addresses::table
+                .filter(inner_transaction_addresses::dsl::inner_tx_id.eq(&id))
+                .select(inner_transaction_addresses::dsl::address)
+                .order(inner_transaction_addresses::dsl::address)
+                .get_results(conn)?;
+
+            Ok(Some((inner_tx, block_height, addresses)))
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e: diesel::result::Error| e.to_string())
+    }
+
+    async fn find_inner_txs_by_address_before(
+        &self,
+        address: String,
+        before: Option<(i32, i32)>,
+        per_page: i64,
+    ) -> Result<KeysetResponseDb<(i32, i32, InnerTransactionDb)>, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            inner_txs_by_address_before(conn, address, before, per_page)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+
+    async fn count_inner_txs_by_address(
+        &self,
+        address: String,
+    ) -> Result<i64, String> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            inner_transaction_addresses::table
+                .filter(inner_transaction_addresses::dsl::address.eq(address))
+                .count()
+                .get_result(conn)
+        })
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use orm::transactions::{
+        InnerTransactionAddressInsertDb, InnerTransactionInsertDb,
+        TransactionKindDb, TransactionResultDb, WrapperTransactionInsertDb,
+    };
+    use test_helpers::db::TestDb;
+
+    use super::*;
+
+    /// Test that the history of an address follows the block height when a
+    /// range of older blocks is crawled after newer ones.
+    #[tokio::test]
+    async fn test_inner_txs_by_address_before_with_blocks_out_of_order() {
+        let db = TestDb::new();
+
+        db.run_test(|conn| {
+            for block_height in [3, 4, 1, 2] {
+                insert_block_inner_txs(conn, block_height)?;
+            }
+
+            let mut block_heights = vec![];
+            let mut cursor = None;
+            loop {
+                let (page, has_next_page) = inner_txs_by_address_before(
+                    conn,
+                    "tnam1source".to_string(),
+                    cursor,
+                    3,
+                )?;
+                block_heights.extend(
+                    page.iter().map(|(block_height, _, _)| *block_height),
+                );
+                cursor = page
+                    .last()
+                    .map(|(block_height, id, _)| (*block_height, *id));
+                if !has_next_page {
+                    break;
+                }
+            }
+
+            assert_eq!(block_heights, vec![4, 4, 3, 3, 2, 2, 1, 1]);
+
+            anyhow::Ok(())
+        })
+        .await
+        .expect("Failed to run test");
+    }
+
+    fn insert_block_inner_txs(
+        conn: &mut PgConnection,
+        block_height: i32,
+    ) -> anyhow::Result<()> {
+        let wrapper_id = format!("wrapper{}", block_height);
+
+        diesel::insert_into(wrapper_transactions::table)
+            .values(WrapperTransactionInsertDb {
+                id: wrapper_id.clone(),
+                fee_payer: "tnam1payer".to_string(),
+                fee_token: "tnam1token".to_string(),
+                gas_limit: "10000".to_string(),
+                block_height,
+                exit_code: TransactionResultDb::Applied,
+                atomic: true,
+            })
+            .execute(conn)?;
+
+        for index in 0..2 {
+            let inner_tx_id = format!("inner{}-{}", block_height, index);
+
+            diesel::insert_into(inner_transactions::table)
+                .values(InnerTransactionInsertDb {
+                    id: inner_tx_id.clone(),
+                    wrapper_id: wrapper_id.clone(),
+                    kind: TransactionKindDb::Unknown,
+                    data: None,
+                    memo: None,
+                    exit_code: TransactionResultDb::Applied,
+                })
+                .execute(conn)?;
+            diesel::insert_into(inner_transaction_addresses::table)
+                .values(InnerTransactionAddressInsertDb {
+                    inner_tx_id,
+                    address: "tnam1source".to_string(),
+                    block_height,
+                })
+                .execute(conn)?;
+        }
+
+        anyhow::Ok(())
+    }
 }
@@ -35,6 +35,22 @@ pub type TotalItems = i64;
 
 pub type PaginatedResponseDb<T> = (Vec<T>, TotalPages, TotalItems);
 
+pub type HasNextPage = bool;
+
+pub type KeysetResponseDb<T> = (Vec<T>, HasNextPage);
+
+/// Keyset queries fetch one row past the page size, its presence tells us
+/// whether a next page exists without counting the whole table
+pub fn into_keyset_page<T>(
+    mut results: Vec<T>,
+    per_page: i64,
+) -> KeysetResponseDb<T> {
+    let has_next_page = results.len() as i64 > per_page;
+    results.truncate(per_page as usize);
+
+    (results, has_next_page)
+}
+
 impl<T> Paginated<T> {
     pub fn per_page(self, per_page: i64) -> Self {
         Paginated {
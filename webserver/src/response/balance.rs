@@ -1,11 +1,23 @@
+use async_graphql::SimpleObject;
 use orm::balances::BalanceDb;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AddressBalance {
     pub token_address: String,
+    /// Raw amount, in the smallest unit of the token
     pub balance: String,
+    /// Amount in the token denomination, if the token metadata is indexed
+    pub denominated_balance: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountBalances {
+    pub address: String,
+    pub balances: Vec<AddressBalance>,
 }
 
 impl From<BalanceDb> for AddressBalance {
@@ -13,6 +25,7 @@ impl From<BalanceDb> for AddressBalance {
         Self {
             token_address: value.token,
             balance: value.raw_amount.to_string(),
+            denominated_balance: None,
         }
     }
 }
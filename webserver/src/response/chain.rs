@@ -1,9 +1,14 @@
+use async_graphql::{SimpleObject, Union};
+use bigdecimal::{BigDecimal, Zero};
+use orm::balances::BalanceDb;
 use orm::parameters::ParametersDb;
+use orm::token::{TokenDistributionDb, TokenMetadataDb, TokenSupplyDb};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as SerdeJSONValue;
 use shared::token::Token as SharedToken;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Parameters {
     pub unbonding_length: String,
@@ -16,8 +21,11 @@ pub struct Parameters {
     pub min_duration: String,
     pub min_num_of_blocks: String,
     pub max_block_time: String,
+    #[graphql(skip)]
+    #[schema(value_type = Object)]
     pub checksums: SerdeJSONValue,
     pub epoch_switch_blocks_delay: String,
+    pub cubic_slashing_window_length: String,
 }
 
 impl From<ParametersDb> for Parameters {
@@ -37,42 +45,80 @@ impl From<ParametersDb> for Parameters {
             epoch_switch_blocks_delay: parameters
                 .epoch_switch_blocks_delay
                 .to_string(),
+            cubic_slashing_window_length: parameters
+                .cubic_slashing_window_length
+                .to_string(),
         }
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcUrl {
     pub url: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainStatus {
+    pub height: i32,
+    pub epoch: i32,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LastProcessedBlock {
     pub block: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LastProcessedEpoch {
     pub epoch: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMetadata {
+    pub denomination: String,
+    pub symbol: Option<String>,
+    pub ibc_port: Option<String>,
+    pub ibc_channel: Option<String>,
+    pub ibc_base_denom: Option<String>,
+    pub origin_chain: Option<String>,
+    pub first_seen_height: String,
+}
+
+impl From<TokenMetadataDb> for TokenMetadata {
+    fn from(metadata: TokenMetadataDb) -> Self {
+        Self {
+            denomination: metadata.denomination.to_string(),
+            symbol: metadata.symbol,
+            ibc_port: metadata.ibc_port,
+            ibc_channel: metadata.ibc_channel,
+            ibc_base_denom: metadata.ibc_base_denom,
+            origin_chain: metadata.origin_chain,
+            first_seen_height: metadata.first_seen_height.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct NativeToken {
     pub address: String,
+    pub metadata: Option<TokenMetadata>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct IbcToken {
     pub address: String,
     pub trace: String,
+    pub metadata: Option<TokenMetadata>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, Union, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
 pub enum Token {
@@ -80,16 +126,115 @@ pub enum Token {
     Ibc(IbcToken),
 }
 
-impl From<SharedToken> for Token {
-    fn from(value: SharedToken) -> Self {
+impl From<(SharedToken, Option<TokenMetadataDb>)> for Token {
+    fn from((value, metadata): (SharedToken, Option<TokenMetadataDb>)) -> Self {
+        let metadata = metadata.map(TokenMetadata::from);
+
         match value {
             SharedToken::Native(token) => Token::Native(NativeToken {
                 address: token.to_string(),
+                metadata,
             }),
             SharedToken::Ibc(token) => Token::Ibc(IbcToken {
                 address: token.address.to_string(),
                 trace: token.trace.to_string(),
+                metadata,
             }),
         }
     }
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochTokenSupply {
+    pub epoch: String,
+    pub total: String,
+    pub effective: String,
+    pub bonded: String,
+    pub pgf: String,
+    pub masp: String,
+}
+
+impl From<TokenSupplyDb> for EpochTokenSupply {
+    fn from(supply: TokenSupplyDb) -> Self {
+        Self {
+            epoch: supply.epoch.to_string(),
+            total: supply.total.to_string(),
+            effective: supply.effective.to_string(),
+            bonded: supply.bonded.to_string(),
+            pgf: supply.pgf.to_string(),
+            masp: supply.masp.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSupply {
+    pub address: String,
+    /// Denomination of the raw amounts, if the token metadata is indexed
+    pub denomination: Option<String>,
+    pub current: Option<EpochTokenSupply>,
+    pub history: Vec<EpochTokenSupply>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenHolder {
+    pub address: String,
+    pub amount: String,
+    /// Amount in the token denomination, if the token metadata is indexed
+    pub denominated_amount: Option<String>,
+    pub share: String,
+}
+
+impl TokenHolder {
+    pub fn from(balance: BalanceDb, total: &BigDecimal) -> Self {
+        Self {
+            address: balance.owner,
+            share: share_of(&balance.raw_amount, total),
+            amount: balance.raw_amount.to_string(),
+            denominated_amount: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDistribution {
+    pub address: String,
+    /// Denomination of the raw amounts, if the token metadata is indexed
+    pub denomination: Option<String>,
+    pub holders_count: String,
+    pub total: String,
+    pub top_10_amount: String,
+    pub top_10_share: String,
+    pub top_100_amount: String,
+    pub top_100_share: String,
+    pub gini: String,
+}
+
+impl From<TokenDistributionDb> for TokenDistribution {
+    fn from(distribution: TokenDistributionDb) -> Self {
+        let total = &distribution.total;
+
+        Self {
+            address: distribution.token.clone(),
+            denomination: None,
+            holders_count: distribution.holders_count.to_string(),
+            top_10_share: share_of(&distribution.top_10_amount, total),
+            top_10_amount: distribution.top_10_amount.to_string(),
+            top_100_share: share_of(&distribution.top_100_amount, total),
+            top_100_amount: distribution.top_100_amount.to_string(),
+            gini: distribution.gini.with_scale(6).to_string(),
+            total: total.to_string(),
+        }
+    }
+}
+
+fn share_of(amount: &BigDecimal, total: &BigDecimal) -> String {
+    if total.is_zero() {
+        return BigDecimal::zero().with_scale(6).to_string();
+    }
+    (amount / total).with_scale(6).to_string()
+}
@@ -0,0 +1,60 @@
+use orm::governance_proposal::GovernanceProposalResultDb;
+use orm::notifications;
+use serde::Serialize;
+
+use crate::response::transaction::{TransactionKind, TransactionResult};
+
+/// Events streamed to the subscribers. Transaction events are published by
+/// the crawlers with their id only and completed from the db.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum IndexerEvent {
+    #[serde(rename_all = "camelCase")]
+    Block { height: i32, epoch: i32 },
+    #[serde(rename_all = "camelCase")]
+    Transaction {
+        id: String,
+        wrapper_id: String,
+        height: i32,
+        kind: TransactionKind,
+        exit_code: TransactionResult,
+        addresses: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    ProposalStatus {
+        id: i32,
+        result: GovernanceProposalResultDb,
+    },
+    #[serde(rename_all = "camelCase")]
+    ValidatorSet { epoch: i32, validators: usize },
+}
+
+impl IndexerEvent {
+    /// Converts the events that need no lookup, `None` for transactions
+    pub fn from_notification(
+        event: notifications::IndexerEvent,
+    ) -> Option<Self> {
+        match event {
+            notifications::IndexerEvent::Block { height, epoch } => {
+                Some(Self::Block { height, epoch })
+            }
+            notifications::IndexerEvent::ProposalStatus { id, result } => {
+                Some(Self::ProposalStatus { id, result })
+            }
+            notifications::IndexerEvent::ValidatorSet { epoch, validators } => {
+                Some(Self::ValidatorSet { epoch, validators })
+            }
+            notifications::IndexerEvent::Transaction { .. } => None,
+        }
+    }
+
+    /// Addresses involved in the event, empty for chain wide events
+    pub fn addresses(&self) -> &[String] {
+        match self {
+            Self::Transaction { addresses, .. } => addresses,
+            Self::Block { .. }
+            | Self::ProposalStatus { .. }
+            | Self::ValidatorSet { .. } => &[],
+        }
+    }
+}
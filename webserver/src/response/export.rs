@@ -0,0 +1,149 @@
+use chrono::NaiveDateTime;
+use orm::balances::BalanceChangeDb;
+use orm::bond::BondDb;
+use orm::pos_rewards::PoSRewardClaimDb;
+use orm::transactions::{InnerTransactionDb, WrapperTransactionDb};
+use orm::unbond::UnbondDb;
+use orm::validators::ValidatorDb;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::transaction::{TransactionKind, TransactionResult};
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionExport {
+    pub block_height: String,
+    pub timestamp: Option<String>,
+    pub wrapper_id: String,
+    pub tx_id: String,
+    pub kind: TransactionKind,
+    pub exit_code: TransactionResult,
+    pub fee_payer: String,
+    pub fee_token: String,
+    pub gas_limit: String,
+    pub memo: Option<String>,
+    pub data: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChangeExport {
+    pub block_height: String,
+    pub timestamp: Option<String>,
+    pub token_address: String,
+    /// Raw amounts, in the smallest unit of the token
+    pub change: String,
+    pub balance: String,
+    /// Amounts in the token denomination, if the token metadata is indexed
+    pub denominated_change: Option<String>,
+    pub denominated_balance: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BondExport {
+    pub validator_address: String,
+    pub amount: String,
+    pub start_epoch: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UnbondExport {
+    pub validator_address: String,
+    pub amount: String,
+    pub withdraw_epoch: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardClaimExport {
+    pub block_height: String,
+    pub timestamp: Option<String>,
+    pub epoch: String,
+    pub validator_address: String,
+    pub amount: String,
+}
+
+/// Block timestamps are exported as RFC 3339 strings, and left empty for
+/// blocks the transactions crawler has not processed yet
+fn to_rfc3339(timestamp: Option<NaiveDateTime>) -> Option<String> {
+    timestamp.map(|timestamp| timestamp.and_utc().to_rfc3339())
+}
+
+impl TransactionExport {
+    pub fn from(
+        inner_tx: InnerTransactionDb,
+        wrapper_tx: WrapperTransactionDb,
+        timestamp: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            block_height: wrapper_tx.block_height.to_string(),
+            timestamp: to_rfc3339(timestamp),
+            wrapper_id: wrapper_tx.id,
+            tx_id: inner_tx.id,
+            kind: TransactionKind::from(inner_tx.kind),
+            exit_code: TransactionResult::from(inner_tx.exit_code),
+            fee_payer: wrapper_tx.fee_payer,
+            fee_token: wrapper_tx.fee_token,
+            gas_limit: wrapper_tx.gas_limit,
+            memo: inner_tx.memo,
+            data: inner_tx.data,
+        }
+    }
+}
+
+impl BalanceChangeExport {
+    pub fn from(
+        db_change: BalanceChangeDb,
+        change: String,
+        timestamp: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            block_height: db_change.height.to_string(),
+            timestamp: to_rfc3339(timestamp),
+            token_address: db_change.token,
+            change,
+            balance: db_change.raw_amount.to_string(),
+            denominated_change: None,
+            denominated_balance: None,
+        }
+    }
+}
+
+impl BondExport {
+    pub fn from(db_bond: BondDb, db_validator: ValidatorDb) -> Self {
+        Self {
+            validator_address: db_validator.namada_address,
+            amount: db_bond.raw_amount.to_string(),
+            start_epoch: db_bond.start.to_string(),
+        }
+    }
+}
+
+impl UnbondExport {
+    pub fn from(db_unbond: UnbondDb, db_validator: ValidatorDb) -> Self {
+        Self {
+            validator_address: db_validator.namada_address,
+            amount: db_unbond.raw_amount.to_string(),
+            withdraw_epoch: db_unbond.withdraw_epoch.to_string(),
+        }
+    }
+}
+
+impl RewardClaimExport {
+    pub fn from(
+        db_claim: PoSRewardClaimDb,
+        db_validator: ValidatorDb,
+        timestamp: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            block_height: db_claim.block_height.to_string(),
+            timestamp: to_rfc3339(timestamp),
+            epoch: db_claim.epoch.to_string(),
+            validator_address: db_validator.namada_address,
+            amount: db_claim.raw_amount.to_string(),
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use orm::gas::{GasDb, GasPriceDb};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::transaction::TransactionKind;
 use crate::service::utils::raw_amount_to_nam;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Gas {
     pub gas_limit: u64,
@@ -20,7 +21,7 @@ impl From<GasDb> for Gas {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GasPrice {
     pub token: String,
@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use async_graphql::{Enum, SimpleObject};
 use orm::crawler_state::ChainCrawlerStateDb;
 use orm::governance_proposal::{
     GovernanceProposalDb, GovernanceProposalKindDb, GovernanceProposalResultDb,
@@ -7,10 +8,13 @@ use orm::governance_proposal::{
 };
 use orm::governance_votes::{GovernanceProposalVoteDb, GovernanceVoteKindDb};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::utils::{epoch_progress, time_between_epochs};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum ProposalType {
     Default,
@@ -30,7 +34,9 @@ impl Display for ProposalType {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum TallyType {
     TwoThirds,
@@ -52,7 +58,9 @@ impl Display for TallyType {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum VoteType {
     Yay,
@@ -60,7 +68,9 @@ pub enum VoteType {
     Abstain,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum ProposalStatus {
     Pending,
@@ -82,11 +92,13 @@ impl Display for ProposalStatus {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
+#[graphql(complex)]
 pub struct Proposal {
     pub id: String,
     pub content: String,
+    #[graphql(name = "type")]
     pub r#type: ProposalType,
     pub tally_type: TallyType,
     pub data: Option<String>,
@@ -104,7 +116,7 @@ pub struct Proposal {
     pub abstain_votes: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProposalVote {
     pub proposal_id: u64,
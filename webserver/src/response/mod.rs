@@ -2,6 +2,8 @@ pub mod api;
 pub mod balance;
 pub mod chain;
 pub mod crawler_state;
+pub mod event;
+pub mod export;
 pub mod gas;
 pub mod governance;
 pub mod pos;
@@ -1,14 +1,22 @@
+use async_graphql::{Enum, SimpleObject};
 use bigdecimal::BigDecimal;
 use orm::bond::BondDb;
 use orm::crawler_state::{ChainCrawlerStateDb, EpochCrawlerStateDb};
-use orm::pos_rewards::PoSRewardDb;
+use orm::parameters::ParametersDb;
+use orm::pos_rewards::{PoSRewardClaimDb, PoSRewardDb, PoSRewardHistoryDb};
+use orm::redelegations::RedelegationDb;
+use orm::slashes::{SlashDb, SlashTypeDb};
 use orm::unbond::UnbondDb;
+use orm::validator_blocks::{ValidatorBlockDb, ValidatorBlockKindDb};
 use orm::validators::{ValidatorDb, ValidatorStateDb};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::utils::{epoch_progress, time_between_epochs};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum ValidatorState {
     Consensus,
@@ -32,13 +40,14 @@ impl From<ValidatorStateDb> for ValidatorState {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Validator {
     pub address: String,
     pub voting_power: String,
     pub max_commission: String,
     pub commission: String,
+    pub pending_commission: Option<String>,
     pub state: ValidatorState,
     pub name: Option<String>,
     pub email: Option<String>,
@@ -48,30 +57,149 @@ pub struct Validator {
     pub avatar: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorDetail {
+    #[serde(flatten)]
+    #[graphql(flatten)]
+    pub validator: ValidatorWithId,
+    pub self_bond: String,
+    pub total_delegated: String,
+    pub delegators_count: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimatedRewards {
+    pub amount: String,
+    pub epochs: String,
+    pub earning_epochs: String,
+    pub apr: String,
+    pub commission: String,
+    pub uptime: Option<String>,
+    pub gross_rewards: String,
+    pub net_rewards: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[graphql(complex)]
+pub struct Delegation {
+    pub address: String,
+    pub amount: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorUptime {
+    pub blocks: String,
+    pub uptime: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ValidatorBlockKind {
+    Proposed,
+    Signed,
+    Missed,
+}
+
+impl From<ValidatorBlockKindDb> for ValidatorBlockKind {
+    fn from(value: ValidatorBlockKindDb) -> Self {
+        match value {
+            ValidatorBlockKindDb::Proposed => Self::Proposed,
+            ValidatorBlockKindDb::Signed => Self::Signed,
+            ValidatorBlockKindDb::Missed => Self::Missed,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorBlock {
+    pub height: String,
+    pub kind: ValidatorBlockKind,
+}
+
+impl From<ValidatorBlockDb> for ValidatorBlock {
+    fn from(value: ValidatorBlockDb) -> Self {
+        Self {
+            height: value.height.to_string(),
+            kind: value.kind.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum SlashType {
+    DuplicateVote,
+    LightClientAttack,
+}
+
+impl From<SlashTypeDb> for SlashType {
+    fn from(value: SlashTypeDb) -> Self {
+        match value {
+            SlashTypeDb::DuplicateVote => Self::DuplicateVote,
+            SlashTypeDb::LightClientAttack => Self::LightClientAttack,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Slash {
+    pub slash_type: SlashType,
+    pub block_height: String,
+    pub epoch: String,
+    pub rate: String,
+}
+
+impl From<SlashDb> for Slash {
+    fn from(value: SlashDb) -> Self {
+        Self {
+            slash_type: value.slash_type.into(),
+            block_height: value.block_height.to_string(),
+            epoch: value.epoch.to_string(),
+            rate: value.rate,
+        }
+    }
+}
+
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum BondStatus {
     Active,
     Inactive,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Bond {
     pub amount: String,
     pub validator: ValidatorWithId,
     pub status: BondStatus,
     pub start_epoch: String,
+    pub slashed: bool,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MergedBond {
     pub amount: String,
     pub validator: ValidatorWithId,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MyValidator {
+    pub validator: ValidatorWithId,
+    pub amount: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Unbond {
     pub amount: String,
@@ -79,9 +207,10 @@ pub struct Unbond {
     pub withdraw_epoch: String,
     pub withdraw_time: String,
     pub can_withdraw: bool,
+    pub slashed: bool,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Withdraw {
     pub amount: String,
@@ -89,14 +218,50 @@ pub struct Withdraw {
     pub withdraw_epoch: String,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Reward {
     pub amount: String,
     pub validator: ValidatorWithId,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccruedReward {
+    pub amount: String,
+    pub epoch: String,
+    pub validator: ValidatorWithId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaimedReward {
+    pub amount: String,
+    pub epoch: String,
+    pub block_height: String,
+    pub validator: ValidatorWithId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardHistory {
+    pub accrued: Vec<AccruedReward>,
+    pub claimed: Vec<ClaimedReward>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Redelegation {
+    pub amount: String,
+    pub src_validator: ValidatorWithId,
+    pub dest_validator: ValidatorWithId,
+    pub epoch: String,
+    pub end_epoch: String,
+    pub slashable_until_epoch: String,
+    pub can_redelegate: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct TotalVotingPower {
     pub total_voting_power: String,
@@ -109,6 +274,7 @@ impl From<ValidatorDb> for Validator {
             voting_power: value.voting_power.to_string(),
             max_commission: value.max_commission,
             commission: value.commission,
+            pending_commission: value.pending_commission,
             state: value.state.into(),
             name: value.name,
             email: value.email,
@@ -120,13 +286,16 @@ impl From<ValidatorDb> for Validator {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
+#[graphql(complex)]
 pub struct ValidatorWithId {
     #[serde(flatten)]
+    #[graphql(flatten)]
     pub validator: Validator,
     pub validator_id: String,
     pub rank: Option<i32>,
+    pub uptime: Vec<ValidatorUptime>,
 }
 
 impl ValidatorWithId {
@@ -135,8 +304,13 @@ impl ValidatorWithId {
             validator_id: db_validator.id.to_string(),
             validator: Validator::from(db_validator),
             rank,
+            uptime: vec![],
         }
     }
+
+    pub fn with_uptime(self, uptime: Vec<ValidatorUptime>) -> Self {
+        Self { uptime, ..self }
+    }
 }
 
 impl From<(&BondDb, &EpochCrawlerStateDb)> for BondStatus {
@@ -160,6 +334,7 @@ impl Bond {
             validator: ValidatorWithId::from(db_validator, None),
             status,
             start_epoch: db_bond.start.to_string(),
+            slashed: false,
         }
     }
 }
@@ -207,6 +382,7 @@ impl Unbond {
             withdraw_epoch: withdraw_epoch.to_string(),
             withdraw_time: withdraw_time.to_string(),
             can_withdraw: chain_state.last_processed_epoch >= withdraw_epoch,
+            slashed: false,
         }
     }
 }
@@ -221,6 +397,60 @@ impl Withdraw {
     }
 }
 
+impl AccruedReward {
+    pub fn from(
+        db_reward: PoSRewardHistoryDb,
+        db_validator: ValidatorDb,
+    ) -> Self {
+        Self {
+            amount: db_reward.raw_amount.to_string(),
+            epoch: db_reward.epoch.to_string(),
+            validator: ValidatorWithId::from(db_validator, None),
+        }
+    }
+}
+
+impl ClaimedReward {
+    pub fn from(db_claim: PoSRewardClaimDb, db_validator: ValidatorDb) -> Self {
+        Self {
+            amount: db_claim.raw_amount.to_string(),
+            epoch: db_claim.epoch.to_string(),
+            block_height: db_claim.block_height.to_string(),
+            validator: ValidatorWithId::from(db_validator, None),
+        }
+    }
+}
+
+impl Redelegation {
+    /// Redelegated stake becomes active at the pipeline epoch and stays
+    /// slashable for the source validator until every infraction committed
+    /// before that epoch has been processed. Until then, the destination
+    /// bond can't be redelegated again.
+    pub fn from(
+        db_redelegation: RedelegationDb,
+        db_src_validator: ValidatorDb,
+        db_dest_validator: ValidatorDb,
+        parameters: &ParametersDb,
+        current_epoch: i32,
+    ) -> Self {
+        let end_epoch = db_redelegation.epoch + parameters.pipeline_length;
+        let slashable_until_epoch = end_epoch
+            + parameters.unbonding_length
+            + 1
+            + parameters.cubic_slashing_window_length;
+
+        Self {
+            amount: db_redelegation.raw_amount.to_string(),
+            src_validator: ValidatorWithId::from(db_src_validator, None),
+            dest_validator: ValidatorWithId::from(db_dest_validator, None),
+            epoch: db_redelegation.epoch.to_string(),
+            end_epoch: end_epoch.to_string(),
+            slashable_until_epoch: slashable_until_epoch.to_string(),
+            can_redelegate: current_epoch >= slashable_until_epoch,
+        }
+    }
+}
+
 impl Reward {
     pub fn from(db_reward: PoSRewardDb, db_validator: ValidatorDb) -> Self {
         Self {
@@ -229,3 +459,76 @@ impl Reward {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(id: i32) -> ValidatorDb {
+        ValidatorDb {
+            id,
+            namada_address: format!("tnam1validator{}", id),
+            voting_power: 1,
+            max_commission: "1".to_string(),
+            commission: "0.05".to_string(),
+            name: None,
+            email: None,
+            website: None,
+            description: None,
+            discord_handle: None,
+            avatar: None,
+            state: ValidatorStateDb::Consensus,
+            consensus_address: None,
+            pending_commission: None,
+        }
+    }
+
+    fn parameters() -> ParametersDb {
+        ParametersDb {
+            id: 1,
+            unbonding_length: 21,
+            pipeline_length: 2,
+            epochs_per_year: 365,
+            min_num_of_blocks: 4,
+            min_duration: 60,
+            max_block_time: 30,
+            apr: "0.1".to_string(),
+            native_token_address: "tnam1native".to_string(),
+            chain_id: "namada-test".to_string(),
+            genesis_time: 0,
+            checksums: serde_json::Value::Null,
+            epoch_switch_blocks_delay: 2,
+            cubic_slashing_window_length: 1,
+        }
+    }
+
+    /// Test that redelegated stake stays slashable for the source validator
+    /// past the pipeline, the unbonding length and the cubic slashing window,
+    /// and can only be redelegated again afterwards.
+    #[test]
+    fn test_redelegation_slashing_window() {
+        let redelegation = |current_epoch| {
+            Redelegation::from(
+                RedelegationDb {
+                    tx_id: "tx".to_string(),
+                    delegator: "tnam1delegator".to_string(),
+                    src_validator_id: 1,
+                    dest_validator_id: 2,
+                    raw_amount: BigDecimal::from(100),
+                    epoch: 10,
+                },
+                validator(1),
+                validator(2),
+                &parameters(),
+                current_epoch,
+            )
+        };
+
+        let slashable = redelegation(34);
+        assert_eq!(slashable.end_epoch, "12");
+        assert_eq!(slashable.slashable_until_epoch, "35");
+        assert!(!slashable.can_redelegate);
+
+        assert!(redelegation(35).can_redelegate);
+    }
+}
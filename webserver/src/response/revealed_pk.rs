@@ -1,7 +1,8 @@
 use orm::revealed_pk::RevealedPkDb;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RevealedPk {
     pub public_key: Option<String>,
@@ -1,16 +1,22 @@
+use async_graphql::{Enum, SimpleObject};
 use orm::transactions::{
     InnerTransactionDb, TransactionKindDb, TransactionResultDb,
     WrapperTransactionDb,
 };
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, Enum, ToSchema,
+)]
 pub enum TransactionResult {
     Applied,
     Rejected,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, Enum, ToSchema,
+)]
 #[serde(rename_all = "camelCase")]
 pub enum TransactionKind {
     TransparentTransfer,
@@ -31,7 +37,7 @@ pub enum TransactionKind {
     Unknown,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct WrapperTransaction {
     pub tx_id: String,
@@ -44,7 +50,7 @@ pub struct WrapperTransaction {
     pub atomic: bool,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ShortInnerTransaction {
     pub tx_id: String,
@@ -54,7 +60,7 @@ pub struct ShortInnerTransaction {
     pub exit_code: TransactionResult,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct InnerTransaction {
     pub tx_id: String,
@@ -69,10 +75,10 @@ impl InnerTransaction {
     pub fn to_short(&self) -> ShortInnerTransaction {
         ShortInnerTransaction {
             tx_id: self.tx_id.clone(),
-            kind: self.kind.clone(),
+            kind: self.kind,
             data: self.data.clone(),
             memo: self.memo.clone(),
-            exit_code: self.exit_code.clone(),
+            exit_code: self.exit_code,
         }
     }
 }
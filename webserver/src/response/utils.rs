@@ -1,15 +1,35 @@
+use async_graphql::SimpleObject;
 use namada_sdk::state::EPOCH_SWITCH_BLOCKS_DELAY;
 use serde::Serialize;
+use utoipa::ToSchema;
 
-use crate::constant::ITEM_PER_PAGE;
+use super::chain::TokenHolder;
+use super::governance::{Proposal, ProposalVote};
+use super::pos::{
+    Bond, Delegation, MergedBond, MyValidator, Redelegation, Unbond,
+    ValidatorBlock, ValidatorWithId, Withdraw,
+};
+use super::transaction::InnerTransaction;
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, ToSchema)]
+#[aliases(
+    ValidatorPage = PaginatedResponse<Vec<ValidatorWithId>>,
+    DelegationPage = PaginatedResponse<Vec<Delegation>>,
+    MyValidatorPage = PaginatedResponse<Vec<MyValidator>>,
+    BondPage = PaginatedResponse<Vec<Bond>>,
+    MergedBondPage = PaginatedResponse<Vec<MergedBond>>,
+    UnbondPage = PaginatedResponse<Vec<Unbond>>,
+    WithdrawPage = PaginatedResponse<Vec<Withdraw>>,
+    RedelegationPage = PaginatedResponse<Vec<Redelegation>>,
+    ProposalPage = PaginatedResponse<Vec<Proposal>>,
+    TokenHolderPage = PaginatedResponse<Vec<TokenHolder>>
+)]
 pub struct PaginatedResponse<T: Serialize> {
     pub results: T,
     pub pagination: Pagination,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, SimpleObject, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Pagination {
     pub page: u64,
@@ -18,6 +38,64 @@ pub struct Pagination {
     pub total_items: u64,
 }
 
+#[derive(Clone, Debug, Serialize)]
+pub struct CursorPaginatedResponse<T: Serialize> {
+    pub results: T,
+    pub pagination: CursorPagination,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPagination {
+    pub per_page: u64,
+    pub next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_items: Option<u64>,
+}
+
+/// List endpoints answer with page numbers unless the client sent a cursor
+#[derive(Clone, Debug, Serialize, ToSchema)]
+#[aliases(
+    ValidatorBlockList = ListResponse<Vec<ValidatorBlock>>,
+    ProposalVoteList = ListResponse<Vec<ProposalVote>>,
+    InnerTransactionList = ListResponse<Vec<InnerTransaction>>
+)]
+pub struct ListResponse<T: Serialize> {
+    pub results: T,
+    pub pagination: ListPagination,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum ListPagination {
+    Page(Pagination),
+    Cursor(CursorPagination),
+}
+
+impl<T> From<PaginatedResponse<T>> for ListResponse<T>
+where
+    T: Serialize,
+{
+    fn from(value: PaginatedResponse<T>) -> Self {
+        Self {
+            results: value.results,
+            pagination: ListPagination::Page(value.pagination),
+        }
+    }
+}
+
+impl<T> From<CursorPaginatedResponse<T>> for ListResponse<T>
+where
+    T: Serialize,
+{
+    fn from(value: CursorPaginatedResponse<T>) -> Self {
+        Self {
+            results: value.results,
+            pagination: ListPagination::Cursor(value.pagination),
+        }
+    }
+}
+
 impl<T> PaginatedResponse<T>
 where
     T: Serialize,
@@ -25,6 +103,7 @@ where
     pub fn new(
         results: T,
         page: u64,
+        per_page: u64,
         total_pages: u64,
         total_items: u64,
     ) -> Self {
@@ -32,7 +111,7 @@ where
             results,
             pagination: Pagination {
                 page,
-                per_page: ITEM_PER_PAGE,
+                per_page,
                 total_pages,
                 total_items,
             },
@@ -40,6 +119,27 @@ where
     }
 }
 
+impl<T> CursorPaginatedResponse<T>
+where
+    T: Serialize,
+{
+    pub fn new(
+        results: T,
+        per_page: u64,
+        next_cursor: Option<String>,
+        total_items: Option<u64>,
+    ) -> Self {
+        Self {
+            results,
+            pagination: CursorPagination {
+                per_page,
+                next_cursor,
+                total_items,
+            },
+        }
+    }
+}
+
 pub fn epoch_progress(
     current_block: i32,
     first_block_in_epoch: i32,
@@ -1,9 +1,12 @@
-use shared::balance::Amount;
+use std::collections::{HashMap, HashSet};
 
+use orm::balances::BalanceDb;
+
+use super::utils::raw_amount_to_denominated;
 use crate::appstate::AppState;
 use crate::error::balance::BalanceError;
 use crate::repository::balance::{BalanceRepo, BalanceRepoTrait};
-use crate::response::balance::AddressBalance;
+use crate::response::balance::{AccountBalances, AddressBalance};
 
 #[derive(Clone)]
 pub struct BalanceService {
@@ -27,16 +30,63 @@ impl BalanceService {
             .await
             .map_err(BalanceError::Database)?;
 
-        // TODO: temporary solution as we only store NAM balances
         let denominated_balances: Vec<AddressBalance> = balances
-            .iter()
-            .cloned()
-            .map(|balance| AddressBalance {
-                token_address: balance.token,
-                balance: Amount::from(balance.raw_amount).to_string(),
+            .into_iter()
+            .map(|(balance, denomination)| {
+                Self::denominate(balance, denomination)
             })
             .collect();
 
         Ok(denominated_balances)
     }
+
+    pub async fn get_addresses_balances(
+        &self,
+        addresses: Vec<String>,
+        tokens: Option<Vec<String>>,
+    ) -> Result<Vec<AccountBalances>, BalanceError> {
+        let balances = self
+            .balance_repo
+            .get_addresses_balances(addresses.clone(), tokens)
+            .await
+            .map_err(BalanceError::Database)?;
+
+        let mut balances_by_owner: HashMap<String, Vec<AddressBalance>> =
+            HashMap::new();
+        for (balance, denomination) in balances {
+            balances_by_owner
+                .entry(balance.owner.clone())
+                .or_default()
+                .push(Self::denominate(balance, denomination));
+        }
+
+        // Addresses are returned in the requested order, even when they
+        // don't hold any token
+        let mut seen = HashSet::new();
+        let accounts = addresses
+            .into_iter()
+            .filter(|address| seen.insert(address.clone()))
+            .map(|address| AccountBalances {
+                balances: balances_by_owner
+                    .remove(&address)
+                    .unwrap_or_default(),
+                address,
+            })
+            .collect();
+
+        Ok(accounts)
+    }
+
+    fn denominate(
+        balance: BalanceDb,
+        denomination: Option<i32>,
+    ) -> AddressBalance {
+        let balance = AddressBalance::from(balance);
+        AddressBalance {
+            denominated_balance: denomination.map(|denomination| {
+                raw_amount_to_denominated(&balance.balance, denomination as u8)
+            }),
+            ..balance
+        }
+    }
 }
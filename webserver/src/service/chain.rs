@@ -1,10 +1,14 @@
+use orm::token::{TokenDistributionDb, TokenMetadataDb};
 use shared::id::Id;
 use shared::token::{IbcToken, Token};
 
+use super::utils::raw_amount_to_denominated;
 use crate::appstate::AppState;
 use crate::error::chain::ChainError;
 use crate::repository::chain::{ChainRepository, ChainRepositoryTrait};
-use crate::response::chain::Parameters;
+use crate::response::chain::{
+    EpochTokenSupply, Parameters, TokenDistribution, TokenHolder, TokenSupply,
+};
 
 #[derive(Clone)]
 pub struct ChainService {
@@ -45,7 +49,9 @@ impl ChainService {
             .map_err(ChainError::Database)
     }
 
-    pub async fn find_tokens(&self) -> Result<Vec<Token>, ChainError> {
+    pub async fn find_tokens(
+        &self,
+    ) -> Result<Vec<(Token, Option<TokenMetadataDb>)>, ChainError> {
         let tokens_db = self
             .chain_repo
             .find_tokens()
@@ -54,15 +60,162 @@ impl ChainService {
 
         let tokens = tokens_db
             .into_iter()
-            .map(|(token, ibc_token)| match ibc_token {
-                Some(ibc_token) => Token::Ibc(IbcToken {
-                    address: Id::Account(ibc_token.address),
-                    trace: Id::IbcTrace(ibc_token.ibc_trace),
-                }),
-                None => Token::Native(Id::Account(token.address)),
+            .map(|(token, ibc_token, metadata)| {
+                let token = match ibc_token {
+                    Some(ibc_token) => Token::Ibc(IbcToken {
+                        address: Id::Account(ibc_token.address),
+                        trace: Id::IbcTrace(ibc_token.ibc_trace),
+                    }),
+                    None => Token::Native(Id::Account(token.address)),
+                };
+                (token, metadata)
             })
-            .collect::<Vec<Token>>();
+            .collect::<Vec<_>>();
 
         Ok(tokens)
     }
+
+    pub async fn find_token_supply(
+        &self,
+        address: String,
+        from_epoch: Option<u64>,
+        to_epoch: Option<u64>,
+    ) -> Result<TokenSupply, ChainError> {
+        let denomination =
+            self.find_token_denomination(address.clone()).await?;
+
+        let current = self
+            .chain_repo
+            .find_latest_token_supply(address.clone())
+            .await
+            .map_err(ChainError::Database)?
+            .map(EpochTokenSupply::from);
+
+        let history = self
+            .chain_repo
+            .find_token_supplies(
+                address.clone(),
+                from_epoch.map(|epoch| epoch as i32),
+                to_epoch.map(|epoch| epoch as i32),
+            )
+            .await
+            .map_err(ChainError::Database)?
+            .into_iter()
+            .map(EpochTokenSupply::from)
+            .collect();
+
+        Ok(TokenSupply {
+            address,
+            denomination: denomination
+                .map(|denomination| denomination.to_string()),
+            current,
+            history,
+        })
+    }
+
+    pub async fn find_token_holders(
+        &self,
+        address: String,
+        exclude_internal: bool,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<TokenHolder>, u64, u64), ChainError> {
+        let (distribution, denomination) = self
+            .find_distribution(address.clone(), exclude_internal)
+            .await?;
+
+        let Some(distribution) = distribution else {
+            return Ok((vec![], 0, 0));
+        };
+
+        let holders = self
+            .chain_repo
+            .find_token_holders(
+                address,
+                exclude_internal,
+                page as i64,
+                per_page as i64,
+            )
+            .await
+            .map_err(ChainError::Database)?
+            .into_iter()
+            .map(|balance| {
+                let holder = TokenHolder::from(balance, &distribution.total);
+                TokenHolder {
+                    denominated_amount: denomination.map(|denomination| {
+                        raw_amount_to_denominated(&holder.amount, denomination)
+                    }),
+                    ..holder
+                }
+            })
+            .collect();
+
+        let total_items = distribution.holders_count as u64;
+        let total_pages = total_items.div_ceil(per_page);
+
+        Ok((holders, total_pages, total_items))
+    }
+
+    pub async fn find_token_distribution(
+        &self,
+        address: String,
+        exclude_internal: bool,
+    ) -> Result<TokenDistribution, ChainError> {
+        let (distribution, denomination) = self
+            .find_distribution(address.clone(), exclude_internal)
+            .await?;
+
+        let distribution = distribution
+            .map(TokenDistribution::from)
+            .unwrap_or_else(|| TokenDistribution {
+                address,
+                denomination: None,
+                holders_count: "0".to_string(),
+                total: "0".to_string(),
+                top_10_amount: "0".to_string(),
+                top_10_share: "0".to_string(),
+                top_100_amount: "0".to_string(),
+                top_100_share: "0".to_string(),
+                gini: "0".to_string(),
+            });
+
+        Ok(TokenDistribution {
+            denomination: denomination
+                .map(|denomination| denomination.to_string()),
+            ..distribution
+        })
+    }
+
+    /// Tokens without any holder have no row in the distribution view
+    async fn find_distribution(
+        &self,
+        address: String,
+        exclude_internal: bool,
+    ) -> Result<(Option<TokenDistributionDb>, Option<u8>), ChainError> {
+        let denomination =
+            self.find_token_denomination(address.clone()).await?;
+
+        let distribution = self
+            .chain_repo
+            .find_token_distribution(address, exclude_internal)
+            .await
+            .map_err(ChainError::Database)?;
+
+        Ok((distribution, denomination))
+    }
+
+    /// Returns the denomination of the token, if its metadata is indexed
+    pub async fn find_token_denomination(
+        &self,
+        address: String,
+    ) -> Result<Option<u8>, ChainError> {
+        let (_, metadata) = self
+            .chain_repo
+            .find_token_by_address(address.clone())
+            .await
+            .map_err(ChainError::Database)?
+            .ok_or(ChainError::TokenNotFound(address))?;
+
+        Ok(metadata.map(|metadata| metadata.denomination as u8))
+    }
 }
@@ -1,4 +1,4 @@
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl, SelectableHelper};
 use orm::crawler_state::{CrawlerNameDb, CrawlerStateDb};
 use orm::schema::crawler_state;
 
@@ -36,7 +36,7 @@ impl CrawlerStateService {
                 }
 
                 query
-                    .select(crawler_state::all_columns)
+                    .select(CrawlerStateDb::as_select())
                     .get_results(conn)
                     .map_err(|e| CrawlerStateError::Database(e.to_string()))
             })
@@ -54,6 +54,23 @@ impl CrawlerStateService {
         })
     }
 
+    pub async fn get_states(
+        &self,
+        names: Vec<CrawlerNameDb>,
+    ) -> Result<Vec<CrawlerStateDb>, CrawlerStateError> {
+        let conn = self.app_state.get_db_connection().await;
+
+        conn.interact(move |conn| {
+            crawler_state::table
+                .filter(crawler_state::name.eq_any(names))
+                .select(CrawlerStateDb::as_select())
+                .get_results(conn)
+                .map_err(|e| CrawlerStateError::Database(e.to_string()))
+        })
+        .await
+        .map_err(|e| CrawlerStateError::Database(e.to_string()))?
+    }
+
     fn to_crawler_name_db(value: &CrawlerNameDto) -> CrawlerNameDb {
         match value {
             CrawlerNameDto::Chain => CrawlerNameDb::Chain,
@@ -0,0 +1,419 @@
+use std::collections::HashMap;
+use std::future::Future;
+
+use bigdecimal::BigDecimal;
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+
+use super::utils::{raw_amount_to_denominated, raw_amount_to_nam};
+use crate::appstate::AppState;
+use crate::constant::EXPORT_CHUNK_SIZE;
+use crate::error::export::ExportError;
+use crate::repository::export::{
+    EpochRange, ExportRepo, ExportRepoTrait, HeightRange,
+};
+use crate::response::export::{
+    BalanceChangeExport, BondExport, RewardClaimExport, TransactionExport,
+    UnbondExport,
+};
+
+/// Exported rows, one database chunk at a time
+pub type ExportStream<T> = BoxStream<'static, Result<Vec<T>, ExportError>>;
+
+#[derive(Clone)]
+pub struct ExportService {
+    pub export_repo: ExportRepo,
+}
+
+impl ExportService {
+    pub fn new(app_state: AppState) -> Self {
+        Self {
+            export_repo: ExportRepo::new(app_state),
+        }
+    }
+
+    /// Timestamps are resolved to the heights of the blocks they enclose and
+    /// narrow the height bounds when both are given
+    pub async fn resolve_height_range(
+        &self,
+        from_height: Option<u64>,
+        to_height: Option<u64>,
+        from_timestamp: Option<i64>,
+        to_timestamp: Option<i64>,
+    ) -> Result<HeightRange, ExportError> {
+        let mut range = HeightRange {
+            from: from_height.map(|height| height as i32),
+            to: to_height.map(|height| height as i32),
+        };
+
+        if let Some(from_timestamp) = from_timestamp {
+            let from = self
+                .export_repo
+                .find_first_block_at_or_after(Self::to_datetime(
+                    from_timestamp,
+                )?)
+                .await
+                .map_err(ExportError::Database)?
+                // No block was produced after the timestamp yet, nothing
+                // can match
+                .unwrap_or(i32::MAX);
+            range.from =
+                Some(range.from.map_or(from, |height| height.max(from)));
+        }
+        if let Some(to_timestamp) = to_timestamp {
+            let to = self
+                .export_repo
+                .find_last_block_at_or_before(Self::to_datetime(to_timestamp)?)
+                .await
+                .map_err(ExportError::Database)?
+                // Heights start at 1, so nothing can match
+                .unwrap_or(0);
+            range.to = Some(range.to.map_or(to, |height| height.min(to)));
+        }
+
+        Ok(range)
+    }
+
+    /// Heights of the first indexed transaction, balance change and reward
+    /// claim. The crawlers only record this history from the moment they
+    /// were upgraded, exports cannot go further back.
+    pub async fn transactions_history_start(
+        &self,
+    ) -> Result<Option<i32>, ExportError> {
+        self.export_repo
+            .find_transactions_history_start()
+            .await
+            .map_err(ExportError::Database)
+    }
+
+    pub async fn balance_changes_history_start(
+        &self,
+    ) -> Result<Option<i32>, ExportError> {
+        self.export_repo
+            .find_balance_changes_history_start()
+            .await
+            .map_err(ExportError::Database)
+    }
+
+    pub async fn reward_claims_history_start(
+        &self,
+    ) -> Result<Option<i32>, ExportError> {
+        self.export_repo
+            .find_reward_claims_history_start()
+            .await
+            .map_err(ExportError::Database)
+    }
+
+    pub fn export_transactions(
+        &self,
+        address: String,
+        range: HeightRange,
+    ) -> ExportStream<TransactionExport> {
+        let export_repo = self.export_repo.clone();
+
+        Self::in_chunks(move |after| {
+            let export_repo = export_repo.clone();
+            let address = address.clone();
+
+            async move {
+                let transactions = export_repo
+                    .find_transactions(address, range, after, EXPORT_CHUNK_SIZE)
+                    .await?;
+
+                Ok(transactions
+                    .into_iter()
+                    .map(|(id, inner_tx, wrapper_tx, timestamp)| {
+                        (
+                            id,
+                            TransactionExport::from(
+                                inner_tx, wrapper_tx, timestamp,
+                            ),
+                        )
+                    })
+                    .collect())
+            }
+        })
+    }
+
+    /// Changes are computed against the previous balance of the same token,
+    /// which may precede the exported range
+    pub fn export_balance_changes(
+        &self,
+        address: String,
+        range: HeightRange,
+    ) -> ExportStream<BalanceChangeExport> {
+        let export_repo = self.export_repo.clone();
+
+        let changes = {
+            let export_repo = export_repo.clone();
+            let address = address.clone();
+
+            Self::in_chunks(move |after| {
+                let export_repo = export_repo.clone();
+                let address = address.clone();
+
+                async move {
+                    let changes = export_repo
+                        .find_balance_changes(
+                            address,
+                            range,
+                            after,
+                            EXPORT_CHUNK_SIZE,
+                        )
+                        .await?;
+
+                    Ok(changes
+                        .into_iter()
+                        .map(|(id, change, denomination, timestamp)| {
+                            (id, (change, denomination, timestamp))
+                        })
+                        .collect())
+                }
+            })
+        };
+
+        futures::stream::try_unfold(
+            (changes, HashMap::<String, BigDecimal>::new()),
+            move |(mut changes, mut previous_balances)| {
+                let export_repo = export_repo.clone();
+                let address = address.clone();
+
+                async move {
+                    let Some(chunk) = changes.try_next().await? else {
+                        return Ok(None);
+                    };
+
+                    let mut rows = Vec::with_capacity(chunk.len());
+                    for (change, denomination, timestamp) in chunk {
+                        let previous_balance =
+                            match previous_balances.remove(&change.token) {
+                                Some(balance) => balance,
+                                None => export_repo
+                                    .find_balance_before(
+                                        address.clone(),
+                                        change.token.clone(),
+                                        change.height,
+                                    )
+                                    .await
+                                    .map_err(ExportError::Database)?
+                                    .unwrap_or_default(),
+                            };
+                        previous_balances.insert(
+                            change.token.clone(),
+                            change.raw_amount.clone(),
+                        );
+
+                        let difference = &change.raw_amount - &previous_balance;
+                        let (denominated_change, denominated_balance) =
+                            match denomination {
+                                Some(denomination) => {
+                                    let denomination = denomination as u8;
+                                    (
+                                        Some(Self::denominate_signed(
+                                            &difference,
+                                            denomination,
+                                        )),
+                                        Some(raw_amount_to_denominated(
+                                            &change.raw_amount.to_string(),
+                                            denomination,
+                                        )),
+                                    )
+                                }
+                                None => (None, None),
+                            };
+
+                        rows.push(BalanceChangeExport {
+                            denominated_change,
+                            denominated_balance,
+                            ..BalanceChangeExport::from(
+                                change,
+                                difference.to_string(),
+                                timestamp,
+                            )
+                        });
+                    }
+
+                    Ok(Some((rows, (changes, previous_balances))))
+                }
+            },
+        )
+        .boxed()
+    }
+
+    pub fn export_bonds(
+        &self,
+        address: String,
+        range: EpochRange,
+    ) -> ExportStream<BondExport> {
+        let export_repo = self.export_repo.clone();
+
+        Self::in_chunks(move |after| {
+            let export_repo = export_repo.clone();
+            let address = address.clone();
+
+            async move {
+                let bonds = export_repo
+                    .find_bonds(address, range, after, EXPORT_CHUNK_SIZE)
+                    .await?;
+
+                Ok(bonds
+                    .into_iter()
+                    .map(|(bond, validator)| {
+                        let id = bond.id;
+                        let bond = BondExport::from(bond, validator);
+
+                        (
+                            id,
+                            BondExport {
+                                amount: raw_amount_to_nam(bond.amount),
+                                ..bond
+                            },
+                        )
+                    })
+                    .collect())
+            }
+        })
+    }
+
+    pub fn export_unbonds(
+        &self,
+        address: String,
+        range: EpochRange,
+    ) -> ExportStream<UnbondExport> {
+        let export_repo = self.export_repo.clone();
+
+        Self::in_chunks(move |after| {
+            let export_repo = export_repo.clone();
+            let address = address.clone();
+
+            async move {
+                let unbonds = export_repo
+                    .find_unbonds(address, range, after, EXPORT_CHUNK_SIZE)
+                    .await?;
+
+                Ok(unbonds
+                    .into_iter()
+                    .map(|(unbond, validator)| {
+                        let id = unbond.id;
+                        let unbond = UnbondExport::from(unbond, validator);
+
+                        (
+                            id,
+                            UnbondExport {
+                                amount: raw_amount_to_nam(unbond.amount),
+                                ..unbond
+                            },
+                        )
+                    })
+                    .collect())
+            }
+        })
+    }
+
+    pub fn export_reward_claims(
+        &self,
+        address: String,
+        range: HeightRange,
+    ) -> ExportStream<RewardClaimExport> {
+        let export_repo = self.export_repo.clone();
+
+        Self::in_chunks(move |after| {
+            let export_repo = export_repo.clone();
+            let address = address.clone();
+
+            async move {
+                let claims = export_repo
+                    .find_reward_claims(
+                        address,
+                        range,
+                        after,
+                        EXPORT_CHUNK_SIZE,
+                    )
+                    .await?;
+
+                Ok(claims
+                    .into_iter()
+                    .map(|(id, claim, validator, timestamp)| {
+                        let claim = RewardClaimExport::from(
+                            claim, validator, timestamp,
+                        );
+
+                        (
+                            id,
+                            RewardClaimExport {
+                                amount: raw_amount_to_nam(claim.amount),
+                                ..claim
+                            },
+                        )
+                    })
+                    .collect())
+            }
+        })
+    }
+
+    /// Pages through `fetch_chunk` by the id of the last row of each chunk
+    /// until a chunk comes back smaller than `EXPORT_CHUNK_SIZE`
+    fn in_chunks<T, F, Fut>(fetch_chunk: F) -> ExportStream<T>
+    where
+        T: Send + 'static,
+        F: Fn(Option<i32>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Vec<(i32, T)>, String>> + Send + 'static,
+    {
+        // `None` once the last chunk has been fetched
+        let first_chunk: Option<Option<i32>> = Some(None);
+
+        futures::stream::try_unfold(first_chunk, move |next_chunk| {
+            let chunk = next_chunk.map(&fetch_chunk);
+
+            async move {
+                let Some(chunk) = chunk else {
+                    return Ok(None);
+                };
+                let rows = chunk.await.map_err(ExportError::Database)?;
+                if rows.is_empty() {
+                    return Ok(None);
+                }
+
+                let next_chunk = match rows.last() {
+                    Some((id, _)) if rows.len() as i64 == EXPORT_CHUNK_SIZE => {
+                        Some(Some(*id))
+                    }
+                    _ => None,
+                };
+
+                Ok(Some((
+                    rows.into_iter().map(|(_, row)| row).collect(),
+                    next_chunk,
+                )))
+            }
+        })
+        .boxed()
+    }
+
+    fn to_datetime(
+        timestamp: i64,
+    ) -> Result<chrono::NaiveDateTime, ExportError> {
+        chrono::DateTime::from_timestamp(timestamp, 0)
+            .map(|datetime| datetime.naive_utc())
+            .ok_or_else(|| {
+                ExportError::InvalidRequest(format!(
+                    "Invalid timestamp {}",
+                    timestamp
+                ))
+            })
+    }
+
+    fn denominate_signed(raw_amount: &BigDecimal, denomination: u8) -> String {
+        if *raw_amount < BigDecimal::default() {
+            format!(
+                "-{}",
+                raw_amount_to_denominated(
+                    &raw_amount.abs().to_string(),
+                    denomination
+                )
+            )
+        } else {
+            raw_amount_to_denominated(&raw_amount.to_string(), denomination)
+        }
+    }
+}
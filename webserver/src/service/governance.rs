@@ -29,13 +29,20 @@ impl GovernanceService {
         kind: Option<ProposalKind>,
         pattern: Option<String>,
         page: u64,
+        per_page: u64,
     ) -> Result<(Vec<Proposal>, u64, u64), GovernanceError> {
         let kind = self.map_kind(kind);
         let status = self.map_status(status);
 
         let (db_proposals, total_pages, total_items) = self
             .governance_repo
-            .find_governance_proposals(status, kind, pattern, page as i64)
+            .find_governance_proposals(
+                status,
+                kind,
+                pattern,
+                page as i64,
+                per_page as i64,
+            )
             .await
             .map_err(GovernanceError::Database)?;
 
@@ -144,6 +151,7 @@ impl GovernanceService {
         &self,
         proposal_id: u64,
         page: u64,
+        per_page: u64,
     ) -> Result<(Vec<ProposalVote>, u64, u64), GovernanceError> {
         let db_proposal = self
             .governance_repo
@@ -157,7 +165,11 @@ impl GovernanceService {
 
         let (db_proposal_votes, total_pages, total_items) = self
             .governance_repo
-            .find_governance_proposal_votes(proposal_id as i32, page as i64)
+            .find_governance_proposal_votes(
+                proposal_id as i32,
+                page as i64,
+                per_page as i64,
+            )
             .await
             .map_err(GovernanceError::Database)?;
 
@@ -171,6 +183,61 @@ impl GovernanceService {
         ))
     }
 
+    pub async fn find_governance_proposal_votes_after(
+        &self,
+        proposal_id: u64,
+        cursor: Option<i32>,
+        per_page: u64,
+        with_count: bool,
+    ) -> Result<(Vec<ProposalVote>, Option<String>, Option<u64>), GovernanceError>
+    {
+        let db_proposal = self
+            .governance_repo
+            .find_governance_proposals_by_id(proposal_id as i32)
+            .await
+            .map_err(GovernanceError::Database)?;
+
+        if db_proposal.is_none() {
+            return Err(GovernanceError::NotFound(proposal_id));
+        }
+
+        let (db_proposal_votes, has_next_page) = self
+            .governance_repo
+            .find_governance_proposal_votes_after(
+                proposal_id as i32,
+                cursor,
+                per_page as i64,
+            )
+            .await
+            .map_err(GovernanceError::Database)?;
+
+        let next_cursor = db_proposal_votes
+            .last()
+            .filter(|_| has_next_page)
+            .map(|vote| vote.id.to_string());
+
+        let total_items = if with_count {
+            let total_items = self
+                .governance_repo
+                .count_governance_proposal_votes(proposal_id as i32)
+                .await
+                .map_err(GovernanceError::Database)?;
+
+            Some(total_items as u64)
+        } else {
+            None
+        };
+
+        Ok((
+            db_proposal_votes
+                .into_iter()
+                .map(ProposalVote::from)
+                .collect(),
+            next_cursor,
+            total_items,
+        ))
+    }
+
     pub async fn find_governance_proposal_votes_by_address(
         &self,
         proposal_id: u64,
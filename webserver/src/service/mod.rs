@@ -1,6 +1,7 @@
 pub mod balance;
 pub mod chain;
 pub mod crawler_state;
+pub mod export;
 pub mod gas;
 pub mod governance;
 pub mod pos;
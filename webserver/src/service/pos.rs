@@ -1,15 +1,24 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
 use bigdecimal::{BigDecimal, Zero};
 use orm::helpers::OrderByDb;
+use orm::validator_blocks::ValidatorBlockKindDb;
 use orm::validators::{ValidatorSortByDb, ValidatorStateDb};
 
 use super::utils::raw_amount_to_nam;
 use crate::appstate::AppState;
-use crate::dto::pos::{OrderByDto, ValidatorSortFieldDto, ValidatorStateDto};
+use crate::dto::pos::{
+    MyValidatorKindDto, OrderByDto, ValidatorSortFieldDto, ValidatorStateDto,
+};
 use crate::error::pos::PoSError;
 use crate::repository::chain::{ChainRepository, ChainRepositoryTrait};
 use crate::repository::pos::{PosRepository, PosRepositoryTrait};
 use crate::response::pos::{
-    Bond, BondStatus, MergedBond, Reward, Unbond, ValidatorWithId, Withdraw,
+    AccruedReward, Bond, BondStatus, ClaimedReward, Delegation,
+    EstimatedRewards, MergedBond, MyValidator, Redelegation, Reward,
+    RewardHistory, Slash, Unbond, ValidatorBlock, ValidatorDetail,
+    ValidatorUptime, ValidatorWithId, Withdraw,
 };
 
 #[derive(Clone)]
@@ -29,9 +38,11 @@ impl PosService {
     pub async fn get_validators(
         &self,
         page: u64,
+        per_page: u64,
         states: Vec<ValidatorStateDto>,
         sort_field: Option<ValidatorSortFieldDto>,
         sort_order: Option<OrderByDto>,
+        uptime_windows: &[u64],
     ) -> Result<(Vec<ValidatorWithId>, u64, u64), PoSError> {
         let validator_states = states
             .into_iter()
@@ -43,7 +54,12 @@ impl PosService {
         });
         let (db_validators, total_pages, total_items) = self
             .pos_repo
-            .find_validators(page as i64, validator_states, validator_sort_by)
+            .find_validators(
+                page as i64,
+                per_page as i64,
+                validator_states,
+                validator_sort_by,
+            )
             .await
             .map_err(PoSError::Database)?;
 
@@ -53,6 +69,13 @@ impl PosService {
             .await
             .map_err(PoSError::Database)?;
 
+        let mut validators_uptime = self
+            .get_validators_uptime(
+                db_validators.iter().map(|v| v.id).collect(),
+                uptime_windows,
+            )
+            .await?;
+
         let validators = db_validators
             .into_iter()
             .map(|v| {
@@ -60,7 +83,9 @@ impl PosService {
                     .iter()
                     .position(|v_id| v_id == &v.id)
                     .map(|r| (r + 1) as i32);
-                ValidatorWithId::from(v, rank)
+                let uptime =
+                    validators_uptime.remove(&v.id).unwrap_or_default();
+                ValidatorWithId::from(v, rank).with_uptime(uptime)
             })
             .collect();
 
@@ -70,6 +95,7 @@ impl PosService {
     pub async fn get_all_validators(
         &self,
         states: Vec<ValidatorStateDto>,
+        uptime_windows: &[u64],
     ) -> Result<Vec<ValidatorWithId>, PoSError> {
         let validator_states = states
             .into_iter()
@@ -85,6 +111,12 @@ impl PosService {
             .get_validators_rank()
             .await
             .map_err(PoSError::Database)?;
+        let mut validators_uptime = self
+            .get_validators_uptime(
+                db_validators.iter().map(|v| v.id).collect(),
+                uptime_windows,
+            )
+            .await?;
         let validators = db_validators
             .into_iter()
             .map(|v| {
@@ -92,17 +124,398 @@ impl PosService {
                     .iter()
                     .position(|v_id| v_id == &v.id)
                     .map(|r| (r + 1) as i32);
-                ValidatorWithId::from(v, rank)
+                let uptime =
+                    validators_uptime.remove(&v.id).unwrap_or_default();
+                ValidatorWithId::from(v, rank).with_uptime(uptime)
             })
             .collect();
 
         Ok(validators)
     }
 
+    pub async fn get_validator(
+        &self,
+        address: String,
+        uptime_windows: &[u64],
+    ) -> Result<ValidatorDetail, PoSError> {
+        let validator = self
+            .pos_repo
+            .find_validator_by_address(address.clone())
+            .await
+            .map_err(PoSError::Database)?
+            .ok_or(PoSError::ValidatorNotFound(address.clone()))?;
+
+        let validators_rank = self
+            .pos_repo
+            .get_validators_rank()
+            .await
+            .map_err(PoSError::Database)?;
+
+        let self_bond = self
+            .pos_repo
+            .find_validator_self_bond(validator.id, address.clone())
+            .await
+            .map_err(PoSError::Database)?;
+
+        let (total_delegated, delegators_count) = self
+            .pos_repo
+            .find_validator_delegations_summary(validator.id, address)
+            .await
+            .map_err(PoSError::Database)?;
+
+        let uptime = self
+            .get_validators_uptime(vec![validator.id], uptime_windows)
+            .await?
+            .remove(&validator.id)
+            .unwrap_or_default();
+
+        let rank = validators_rank
+            .iter()
+            .position(|v_id| v_id == &validator.id)
+            .map(|r| (r + 1) as i32);
+
+        Ok(ValidatorDetail {
+            validator: ValidatorWithId::from(validator, rank)
+                .with_uptime(uptime),
+            self_bond: raw_amount_to_nam(
+                self_bond.unwrap_or(BigDecimal::zero()).to_string(),
+            ),
+            total_delegated: raw_amount_to_nam(
+                total_delegated.unwrap_or(BigDecimal::zero()).to_string(),
+            ),
+            delegators_count: delegators_count.to_string(),
+        })
+    }
+
+    pub async fn get_validator_delegations(
+        &self,
+        address: String,
+        sort_order: Option<OrderByDto>,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<Delegation>, u64, u64), PoSError> {
+        let validator = self
+            .pos_repo
+            .find_validator_by_address(address.clone())
+            .await
+            .map_err(PoSError::Database)?
+            .ok_or(PoSError::ValidatorNotFound(address.clone()))?;
+
+        let order_by = match sort_order.unwrap_or(OrderByDto::Desc) {
+            OrderByDto::Asc => OrderByDb::Asc,
+            OrderByDto::Desc => OrderByDb::Desc,
+        };
+
+        let (db_delegations, total_pages, total_items) = self
+            .pos_repo
+            .find_validator_delegations(
+                validator.id,
+                address,
+                order_by,
+                page as i64,
+                per_page as i64,
+            )
+            .await
+            .map_err(PoSError::Database)?;
+
+        let delegations = db_delegations
+            .into_iter()
+            .map(|(address, amount)| Delegation {
+                address,
+                amount: raw_amount_to_nam(
+                    amount.unwrap_or(BigDecimal::zero()).to_string(),
+                ),
+            })
+            .collect();
+
+        Ok((delegations, total_pages as u64, total_items as u64))
+    }
+
+    /// Projects the rewards a new delegation of `amount` to the validator
+    /// would earn over `epochs` epochs. The delegation only starts earning
+    /// once the pipeline is over, by which time any pending commission change
+    /// is in effect too. Rewards are scaled down by the validator uptime over
+    /// the largest window it has blocks for.
+    pub async fn get_estimated_rewards(
+        &self,
+        address: String,
+        amount: String,
+        epochs: Option<u64>,
+        uptime_windows: &[u64],
+    ) -> Result<EstimatedRewards, PoSError> {
+        let amount_dec = BigDecimal::from_str(&amount)
+            .ok()
+            .filter(|amount| amount >= &BigDecimal::zero())
+            .ok_or(PoSError::InvalidAmount(amount.clone()))?;
+
+        let validator = self
+            .pos_repo
+            .find_validator_by_address(address.clone())
+            .await
+            .map_err(PoSError::Database)?
+            .ok_or(PoSError::ValidatorNotFound(address))?;
+
+        let parameters = self
+            .chain_repo
+            .find_chain_parameters()
+            .await
+            .map_err(PoSError::Database)?;
+
+        let epochs = epochs.unwrap_or(parameters.epochs_per_year as u64);
+        let earning_epochs =
+            epochs.saturating_sub(parameters.pipeline_length as u64);
+
+        let commission = validator
+            .pending_commission
+            .clone()
+            .unwrap_or(validator.commission.clone());
+
+        let uptime = self
+            .get_validators_uptime(vec![validator.id], uptime_windows)
+            .await?
+            .remove(&validator.id)
+            .unwrap_or_default()
+            .into_iter()
+            .max_by_key(|uptime| uptime.blocks.parse::<u64>().unwrap_or(0))
+            .map(|uptime| uptime.uptime);
+
+        let to_decimal = |value: &str| {
+            BigDecimal::from_str(value).map_err(|e| {
+                PoSError::Unknown(format!("Invalid decimal {value}: {e}"))
+            })
+        };
+        let apr = to_decimal(&parameters.apr)?;
+        let commission_dec = to_decimal(&commission)?;
+        let uptime_dec = match &uptime {
+            Some(uptime) => to_decimal(uptime)?,
+            None => BigDecimal::from(1),
+        };
+
+        let (gross_rewards, net_rewards) = Self::estimate_rewards(
+            &amount_dec,
+            &apr,
+            &commission_dec,
+            &uptime_dec,
+            earning_epochs,
+            parameters.epochs_per_year,
+        );
+
+        Ok(EstimatedRewards {
+            amount,
+            epochs: epochs.to_string(),
+            earning_epochs: earning_epochs.to_string(),
+            apr: parameters.apr,
+            commission,
+            uptime,
+            gross_rewards: gross_rewards.with_scale(6).to_string(),
+            net_rewards: net_rewards.with_scale(6).to_string(),
+        })
+    }
+
+    pub async fn get_validator_blocks(
+        &self,
+        address: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<ValidatorBlock>, u64, u64), PoSError> {
+        let validator = self
+            .pos_repo
+            .find_validator_by_address(address.clone())
+            .await
+            .map_err(PoSError::Database)?
+            .ok_or(PoSError::ValidatorNotFound(address))?;
+
+        let (db_validator_blocks, total_pages, total_items) = self
+            .pos_repo
+            .find_validator_blocks(
+                validator.id,
+                vec![
+                    ValidatorBlockKindDb::Proposed,
+                    ValidatorBlockKindDb::Missed,
+                ],
+                page as i64,
+                per_page as i64,
+            )
+            .await
+            .map_err(PoSError::Database)?;
+
+        let validator_blocks = db_validator_blocks
+            .into_iter()
+            .map(ValidatorBlock::from)
+            .collect();
+
+        Ok((validator_blocks, total_pages as u64, total_items as u64))
+    }
+
+    pub async fn get_validator_blocks_before(
+        &self,
+        address: String,
+        cursor: Option<(i32, i32)>,
+        per_page: u64,
+        with_count: bool,
+    ) -> Result<(Vec<ValidatorBlock>, Option<String>, Option<u64>), PoSError>
+    {
+        let validator = self
+            .pos_repo
+            .find_validator_by_address(address.clone())
+            .await
+            .map_err(PoSError::Database)?
+            .ok_or(PoSError::ValidatorNotFound(address))?;
+
+        let kinds =
+            vec![ValidatorBlockKindDb::Proposed, ValidatorBlockKindDb::Missed];
+
+        let (db_validator_blocks, has_next_page) = self
+            .pos_repo
+            .find_validator_blocks_before(
+                validator.id,
+                kinds.clone(),
+                cursor,
+                per_page as i64,
+            )
+            .await
+            .map_err(PoSError::Database)?;
+
+        // Both the height and the row id, the height alone isn't unique
+        let next_cursor = db_validator_blocks
+            .last()
+            .filter(|_| has_next_page)
+            .map(|(id, block)| format!("{}_{}", block.height, id));
+
+        let total_items = if with_count {
+            let total_items = self
+                .pos_repo
+                .count_validator_blocks(validator.id, kinds)
+                .await
+                .map_err(PoSError::Database)?;
+
+            Some(total_items as u64)
+        } else {
+            None
+        };
+
+        let validator_blocks = db_validator_blocks
+            .into_iter()
+            .map(|(_, block)| ValidatorBlock::from(block))
+            .collect();
+
+        Ok((validator_blocks, next_cursor, total_items))
+    }
+
+    pub async fn get_validator_slashes(
+        &self,
+        address: String,
+    ) -> Result<Vec<Slash>, PoSError> {
+        let validator = self
+            .pos_repo
+            .find_validator_by_address(address.clone())
+            .await
+            .map_err(PoSError::Database)?
+            .ok_or(PoSError::ValidatorNotFound(address))?;
+
+        let slashes = self
+            .pos_repo
+            .find_slashes_by_validator_ids(vec![validator.id])
+            .await
+            .map_err(PoSError::Database)?
+            .into_iter()
+            .map(Slash::from)
+            .collect();
+
+        Ok(slashes)
+    }
+
+    /// Returns the epochs at which each of the given validators committed a
+    /// slashable infraction
+    async fn get_slashed_epochs(
+        &self,
+        validator_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, Vec<i32>>, PoSError> {
+        let slashes = self
+            .pos_repo
+            .find_slashes_by_validator_ids(validator_ids)
+            .await
+            .map_err(PoSError::Database)?;
+
+        let mut slashed_epochs: HashMap<i32, Vec<i32>> = HashMap::new();
+        for slash in slashes {
+            slashed_epochs
+                .entry(slash.validator_id)
+                .or_default()
+                .push(slash.epoch);
+        }
+
+        Ok(slashed_epochs)
+    }
+
+    /// Returns the share of signed blocks of each validator over the last
+    /// `uptime_windows` blocks. Validators without any signature recorded
+    /// in a window are left out for that window.
+    async fn get_validators_uptime(
+        &self,
+        validator_ids: Vec<i32>,
+        uptime_windows: &[u64],
+    ) -> Result<HashMap<i32, Vec<ValidatorUptime>>, PoSError> {
+        let mut validators_uptime: HashMap<i32, Vec<ValidatorUptime>> =
+            HashMap::new();
+
+        let latest_height = self
+            .chain_repo
+            .find_last_processed_block()
+            .await
+            .map_err(PoSError::Database)?;
+        // The chain crawler might not have processed any block yet
+        let Some(latest_height) = latest_height else {
+            return Ok(validators_uptime);
+        };
+
+        for window in uptime_windows {
+            // The window is made of the `window` blocks up to the latest one
+            let after_height = latest_height
+                .saturating_sub((*window).min(i32::MAX as u64) as i32);
+
+            let blocks_count = self
+                .pos_repo
+                .find_validators_blocks_count(
+                    validator_ids.clone(),
+                    after_height,
+                )
+                .await
+                .map_err(PoSError::Database)?;
+
+            let mut signatures: HashMap<i32, (i64, i64)> = HashMap::new();
+            for (validator_id, kind, count) in blocks_count {
+                let (signed, missed) =
+                    signatures.entry(validator_id).or_default();
+                match kind {
+                    ValidatorBlockKindDb::Signed => *signed += count,
+                    ValidatorBlockKindDb::Missed => *missed += count,
+                    ValidatorBlockKindDb::Proposed => {}
+                }
+            }
+
+            for (validator_id, (signed, missed)) in signatures {
+                if signed + missed == 0 {
+                    continue;
+                }
+                validators_uptime.entry(validator_id).or_default().push(
+                    ValidatorUptime {
+                        blocks: window.to_string(),
+                        uptime: (signed as f64 / (signed + missed) as f64)
+                            .to_string(),
+                    },
+                );
+            }
+        }
+
+        Ok(validators_uptime)
+    }
+
     pub async fn get_bonds_by_address(
         &self,
         address: String,
         page: u64,
+        per_page: u64,
     ) -> Result<(Vec<Bond>, u64, u64), PoSError> {
         let pos_state = self
             .pos_repo
@@ -112,18 +525,31 @@ impl PosService {
 
         let (db_bonds, total_pages, total_items) = self
             .pos_repo
-            .find_bonds_by_address(address, page as i64)
+            .find_bonds_by_address(address, page as i64, per_page as i64)
             .await
             .map_err(PoSError::Database)?;
 
+        let slashed_epochs = self
+            .get_slashed_epochs(
+                db_bonds.iter().map(|(validator, _)| validator.id).collect(),
+            )
+            .await?;
+
         let bonds: Vec<Bond> = db_bonds
             .into_iter()
             .map(|(validator, bond)| {
+                // A bond is affected by any infraction committed while it was
+                // contributing to the validator stake
+                let slashed =
+                    slashed_epochs.get(&validator.id).is_some_and(|epochs| {
+                        epochs.iter().any(|epoch| *epoch >= bond.start)
+                    });
                 let bond_status = BondStatus::from((&bond, &pos_state));
                 let bond = Bond::from(bond, bond_status, validator);
 
                 Bond {
                     amount: raw_amount_to_nam(bond.amount),
+                    slashed,
                     ..bond
                 }
             })
@@ -132,14 +558,69 @@ impl PosService {
         Ok((bonds, total_pages as u64, total_items as u64))
     }
 
+    pub async fn get_my_validators(
+        &self,
+        addresses: Vec<String>,
+        kind: MyValidatorKindDto,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<MyValidator>, u64, u64), PoSError> {
+        let (db_validators, total_pages, total_items) = match kind {
+            MyValidatorKindDto::WithBonds => self
+                .pos_repo
+                .find_validators_with_bonds_by_addresses(
+                    addresses,
+                    page as i64,
+                    per_page as i64,
+                )
+                .await
+                .map_err(PoSError::Database)?,
+            MyValidatorKindDto::WithUnbonds => self
+                .pos_repo
+                .find_validators_with_unbonds_by_addresses(
+                    addresses,
+                    page as i64,
+                    per_page as i64,
+                )
+                .await
+                .map_err(PoSError::Database)?,
+        };
+
+        let validators_rank = self
+            .pos_repo
+            .get_validators_rank()
+            .await
+            .map_err(PoSError::Database)?;
+
+        let validators = db_validators
+            .into_iter()
+            .map(|(validator, amount)| {
+                let rank = validators_rank
+                    .iter()
+                    .position(|v_id| v_id == &validator.id)
+                    .map(|r| (r + 1) as i32);
+
+                MyValidator {
+                    validator: ValidatorWithId::from(validator, rank),
+                    amount: raw_amount_to_nam(
+                        amount.unwrap_or(BigDecimal::zero()).to_string(),
+                    ),
+                }
+            })
+            .collect();
+
+        Ok((validators, total_pages as u64, total_items as u64))
+    }
+
     pub async fn get_merged_bonds_by_address(
         &self,
         address: String,
         page: u64,
+        per_page: u64,
     ) -> Result<(Vec<MergedBond>, u64, u64), PoSError> {
         let (db_bonds, total_pages, total_items) = self
             .pos_repo
-            .find_merged_bonds_by_address(address, page as i64)
+            .find_merged_bonds_by_address(address, page as i64, per_page as i64)
             .await
             .map_err(PoSError::Database)?;
 
@@ -165,10 +646,11 @@ impl PosService {
         &self,
         address: String,
         page: u64,
+        per_page: u64,
     ) -> Result<(Vec<Unbond>, u64, u64), PoSError> {
         let (db_unbonds, total_pages, total_items) = self
             .pos_repo
-            .find_unbonds_by_address(address, page as i64)
+            .find_unbonds_by_address(address, page as i64, per_page as i64)
             .await
             .map_err(PoSError::Database)?;
 
@@ -184,9 +666,25 @@ impl PosService {
             .await
             .map_err(PoSError::Database)?;
 
+        let slashed_epochs = self
+            .get_slashed_epochs(
+                db_unbonds
+                    .iter()
+                    .map(|(validator, _)| validator.id)
+                    .collect(),
+            )
+            .await?;
+
         let unbonds: Vec<Unbond> = db_unbonds
             .into_iter()
             .map(|(validator, unbond)| {
+                let slashed = Self::is_unbond_slashed(
+                    &slashed_epochs,
+                    validator.id,
+                    unbond.start_epoch,
+                    unbond.withdraw_epoch,
+                    parameters.unbonding_length + parameters.pipeline_length,
+                );
                 let bond = Unbond::from(
                     unbond.raw_amount,
                     unbond.withdraw_epoch,
@@ -197,6 +695,7 @@ impl PosService {
                 );
                 Unbond {
                     amount: raw_amount_to_nam(bond.amount),
+                    slashed,
                     ..bond
                 }
             })
@@ -209,6 +708,7 @@ impl PosService {
         &self,
         address: String,
         page: u64,
+        per_page: u64,
     ) -> Result<(Vec<Unbond>, u64, u64), PoSError> {
         let chain_state = self
             .chain_repo
@@ -228,6 +728,7 @@ impl PosService {
                 address,
                 pos_state.last_processed_epoch,
                 page as i64,
+                per_page as i64,
             )
             .await
             .map_err(PoSError::Database)?;
@@ -238,9 +739,25 @@ impl PosService {
             .await
             .map_err(PoSError::Database)?;
 
+        let slashed_epochs = self
+            .get_slashed_epochs(
+                db_merged_unbonds
+                    .iter()
+                    .map(|(_, validator, _, _, _)| validator.id)
+                    .collect(),
+            )
+            .await?;
+
         let unbonds: Vec<Unbond> = db_merged_unbonds
             .into_iter()
-            .map(|(_, validator, raw_amount, withdraw_epoch)| {
+            .map(|(_, validator, raw_amount, withdraw_epoch, start_epoch)| {
+                let slashed = Self::is_unbond_slashed(
+                    &slashed_epochs,
+                    validator.id,
+                    start_epoch,
+                    withdraw_epoch,
+                    parameters.unbonding_length + parameters.pipeline_length,
+                );
                 let bond = Unbond::from(
                     raw_amount.unwrap_or(BigDecimal::zero()),
                     withdraw_epoch,
@@ -251,6 +768,7 @@ impl PosService {
                 );
                 Unbond {
                     amount: raw_amount_to_nam(bond.amount),
+                    slashed,
                     ..bond
                 }
             })
@@ -264,6 +782,7 @@ impl PosService {
         address: String,
         epoch: Option<u64>,
         page: u64,
+        per_page: u64,
     ) -> Result<(Vec<Withdraw>, u64, u64), PoSError> {
         let epoch = if let Some(epoch) = epoch {
             epoch as i32
@@ -277,7 +796,12 @@ impl PosService {
 
         let (db_withdraws, total_pages, total_items) = self
             .pos_repo
-            .find_withdraws_by_address(address, epoch, page as i64)
+            .find_withdraws_by_address(
+                address,
+                epoch,
+                page as i64,
+                per_page as i64,
+            )
             .await
             .map_err(PoSError::Database)?;
 
@@ -295,6 +819,79 @@ impl PosService {
         Ok((withdraws, total_pages as u64, total_items as u64))
     }
 
+    pub async fn get_redelegations_by_address(
+        &self,
+        address: String,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<Redelegation>, u64, u64), PoSError> {
+        let (db_redelegations, total_pages, total_items) = self
+            .pos_repo
+            .find_redelegations_by_address(
+                address,
+                page as i64,
+                per_page as i64,
+            )
+            .await
+            .map_err(PoSError::Database)?;
+
+        let chain_state = self
+            .chain_repo
+            .get_state()
+            .await
+            .map_err(PoSError::Database)?;
+
+        let parameters = self
+            .chain_repo
+            .find_chain_parameters()
+            .await
+            .map_err(PoSError::Database)?;
+
+        let validator_ids = db_redelegations
+            .iter()
+            .flat_map(|redelegation| {
+                [
+                    redelegation.src_validator_id,
+                    redelegation.dest_validator_id,
+                ]
+            })
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+
+        let validators: HashMap<i32, _> = self
+            .pos_repo
+            .find_validators_by_ids(validator_ids)
+            .await
+            .map_err(PoSError::Database)?
+            .into_iter()
+            .map(|validator| (validator.id, validator))
+            .collect();
+
+        let redelegations = db_redelegations
+            .into_iter()
+            .filter_map(|redelegation| {
+                let src_validator =
+                    validators.get(&redelegation.src_validator_id)?.clone();
+                let dest_validator =
+                    validators.get(&redelegation.dest_validator_id)?.clone();
+                let redelegation = Redelegation::from(
+                    redelegation,
+                    src_validator,
+                    dest_validator,
+                    &parameters,
+                    chain_state.last_processed_epoch,
+                );
+                Some(Redelegation {
+                    amount: raw_amount_to_nam(redelegation.amount),
+                    ..redelegation
+                })
+            })
+            .collect();
+
+        Ok((redelegations, total_pages as u64, total_items as u64))
+    }
+
     pub async fn get_rewards_by_address(
         &self,
         address: String,
@@ -332,6 +929,56 @@ impl PosService {
         Ok(denominated_rewards)
     }
 
+    pub async fn get_rewards_history_by_address(
+        &self,
+        address: String,
+        from_epoch: Option<u64>,
+        to_epoch: Option<u64>,
+    ) -> Result<RewardHistory, PoSError> {
+        let from_epoch = from_epoch.map(|epoch| epoch as i32);
+        let to_epoch = to_epoch.map(|epoch| epoch as i32);
+
+        let db_accrued = self
+            .pos_repo
+            .find_rewards_history_by_address(
+                address.clone(),
+                from_epoch,
+                to_epoch,
+            )
+            .await
+            .map_err(PoSError::Database)?;
+
+        let db_claimed = self
+            .pos_repo
+            .find_reward_claims_by_address(address, from_epoch, to_epoch)
+            .await
+            .map_err(PoSError::Database)?;
+
+        let accrued = db_accrued
+            .into_iter()
+            .map(|(validator, reward)| {
+                let reward = AccruedReward::from(reward, validator);
+                AccruedReward {
+                    amount: raw_amount_to_nam(reward.amount),
+                    ..reward
+                }
+            })
+            .collect();
+
+        let claimed = db_claimed
+            .into_iter()
+            .map(|(validator, claim)| {
+                let claim = ClaimedReward::from(claim, validator);
+                ClaimedReward {
+                    amount: raw_amount_to_nam(claim.amount),
+                    ..claim
+                }
+            })
+            .collect();
+
+        Ok(RewardHistory { accrued, claimed })
+    }
+
     // TODO: maybe return object(struct) instead
     pub async fn get_total_voting_power(&self) -> Result<u64, PoSError> {
         let total_voting_power_db = self
@@ -343,6 +990,43 @@ impl PosService {
         Ok(total_voting_power_db.unwrap_or_default() as u64)
     }
 
+    /// Rewards of `amount` over `earning_epochs` at the yearly `apr`, before
+    /// and after the commission and the uptime of the validator
+    fn estimate_rewards(
+        amount: &BigDecimal,
+        apr: &BigDecimal,
+        commission: &BigDecimal,
+        uptime: &BigDecimal,
+        earning_epochs: u64,
+        epochs_per_year: i32,
+    ) -> (BigDecimal, BigDecimal) {
+        let gross_rewards = amount * apr * BigDecimal::from(earning_epochs)
+            / BigDecimal::from(epochs_per_year.max(1));
+        let net_rewards =
+            &gross_rewards * (BigDecimal::from(1) - commission) * uptime;
+
+        (gross_rewards, net_rewards)
+    }
+
+    /// Unbonded tokens are slashed for the infractions committed while they
+    /// were bonded, even when discovered after the unbond. `unbond_window` is
+    /// the unbonding and pipeline lengths, an unknown start epoch counts every
+    /// earlier infraction.
+    fn is_unbond_slashed(
+        slashed_epochs: &HashMap<i32, Vec<i32>>,
+        validator_id: i32,
+        start_epoch: Option<i32>,
+        withdraw_epoch: i32,
+        unbond_window: i32,
+    ) -> bool {
+        let bonded_epochs =
+            start_epoch.unwrap_or(i32::MIN)..=withdraw_epoch - unbond_window;
+
+        slashed_epochs.get(&validator_id).is_some_and(|epochs| {
+            epochs.iter().any(|epoch| bonded_epochs.contains(epoch))
+        })
+    }
+
     fn to_validator_state_db(value: ValidatorStateDto) -> ValidatorStateDb {
         match value {
             ValidatorStateDto::Consensus => ValidatorStateDb::Consensus,
@@ -377,3 +1061,92 @@ impl PosService {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that only the infractions committed between the bond start and
+    /// the unbond epoch slash the unbond.
+    #[test]
+    fn test_is_unbond_slashed_ignores_slashes_outside_the_bond() {
+        // Bonded at epoch 5, unbonded at epoch 10, withdrawable at epoch 15
+        let is_slashed = |epoch| {
+            PosService::is_unbond_slashed(
+                &HashMap::from([(1, vec![epoch])]),
+                1,
+                Some(5),
+                15,
+                5,
+            )
+        };
+
+        assert!(!is_slashed(3));
+        assert!(is_slashed(5));
+        assert!(is_slashed(10));
+        assert!(!is_slashed(12));
+        assert!(!is_slashed(15));
+    }
+
+    /// Test that an infraction committed before the unbond slashes it even
+    /// when discovered after the unbond, and that an unknown start epoch
+    /// counts every earlier infraction.
+    #[test]
+    fn test_is_unbond_slashed_with_infraction_discovered_after_the_unbond() {
+        // The slash of validator 2 is only known once the unbond happened,
+        // it is recorded at its infraction epoch
+        let slashed_epochs = HashMap::from([(1, vec![12]), (2, vec![8])]);
+
+        assert!(!PosService::is_unbond_slashed(
+            &slashed_epochs,
+            1,
+            Some(5),
+            15,
+            5
+        ));
+        assert!(PosService::is_unbond_slashed(
+            &slashed_epochs,
+            2,
+            Some(5),
+            15,
+            5
+        ));
+        assert!(PosService::is_unbond_slashed(
+            &HashMap::from([(1, vec![1])]),
+            1,
+            None,
+            15,
+            5
+        ));
+    }
+
+    /// Test that the estimate is prorated over the earning epochs of a year,
+    /// then reduced by the commission and the uptime.
+    #[test]
+    fn test_estimate_rewards() {
+        let decimal = |value: &str| BigDecimal::from_str(value).unwrap();
+
+        let (gross, net) = PosService::estimate_rewards(
+            &decimal("1000"),
+            &decimal("0.1"),
+            &decimal("0.05"),
+            &decimal("0.9"),
+            365,
+            730,
+        );
+        assert_eq!(gross, decimal("50"));
+        assert_eq!(net, decimal("42.75"));
+
+        // Nothing is earned before the delegation is bonded
+        let (gross, net) = PosService::estimate_rewards(
+            &decimal("1000"),
+            &decimal("0.1"),
+            &decimal("0.05"),
+            &decimal("1"),
+            0,
+            730,
+        );
+        assert!(gross.is_zero());
+        assert!(net.is_zero());
+    }
+}
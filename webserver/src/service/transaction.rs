@@ -1,9 +1,18 @@
+use std::collections::{HashMap, HashSet};
+
+use namada_core::token::Denomination;
+use orm::transactions::{InnerTransactionDb, TransactionKindDb};
+use shared::ser::TransparentTransfer;
+
 use crate::appstate::AppState;
 use crate::error::transaction::TransactionError;
 use crate::repository::tranasaction::{
     TransactionRepository, TransactionRepositoryTrait,
 };
-use crate::response::transaction::{InnerTransaction, WrapperTransaction};
+use crate::response::event::IndexerEvent;
+use crate::response::transaction::{
+    InnerTransaction, TransactionKind, TransactionResult, WrapperTransaction,
+};
 
 #[derive(Clone)]
 pub struct TransactionService {
@@ -40,7 +49,11 @@ impl TransactionService {
             .await
             .map_err(TransactionError::Database)?;
 
-        Ok(inner_tx.map(InnerTransaction::from))
+        let inner_txs = self
+            .denominate_transfers(inner_tx.into_iter().collect())
+            .await?;
+
+        Ok(inner_txs.into_iter().next())
     }
 
     pub async fn get_inner_tx_by_wrapper_id(
@@ -53,6 +66,153 @@ impl TransactionService {
             .await
             .map_err(TransactionError::Database)?;
 
-        Ok(inner_txs.into_iter().map(InnerTransaction::from).collect())
+        self.denominate_transfers(inner_txs).await
+    }
+
+    /// Inner transactions involving `address`, newest first. The cursor is
+    /// the block height and position of the last transaction of the previous
+    /// page.
+    pub async fn get_inner_txs_by_address(
+        &self,
+        address: String,
+        cursor: Option<(i32, i32)>,
+        per_page: u64,
+        with_count: bool,
+    ) -> Result<
+        (Vec<InnerTransaction>, Option<String>, Option<u64>),
+        TransactionError,
+    > {
+        let (inner_txs, has_next_page) = self
+            .transaction_repo
+            .find_inner_txs_by_address_before(
+                address.clone(),
+                cursor,
+                per_page as i64,
+            )
+            .await
+            .map_err(TransactionError::Database)?;
+
+        let next_cursor = inner_txs.last().filter(|_| has_next_page).map(
+            |(block_height, position, _)| {
+                format!("{}_{}", block_height, position)
+            },
+        );
+
+        let total_items = if with_count {
+            let total_items = self
+                .transaction_repo
+                .count_inner_txs_by_address(address)
+                .await
+                .map_err(TransactionError::Database)?;
+
+            Some(total_items as u64)
+        } else {
+            None
+        };
+
+        let inner_txs = self
+            .denominate_transfers(
+                inner_txs
+                    .into_iter()
+                    .map(|(_, _, inner_tx)| inner_tx)
+                    .collect(),
+            )
+            .await?;
+
+        Ok((inner_txs, next_cursor, total_items))
+    }
+
+    /// Builds the transaction event streamed to the subscribers from the id
+    /// published by the transactions crawler
+    pub async fn get_transaction_event(
+        &self,
+        id: String,
+    ) -> Result<Option<IndexerEvent>, TransactionError> {
+        let inner_tx = self
+            .transaction_repo
+            .find_inner_tx_with_context(id)
+            .await
+            .map_err(TransactionError::Database)?;
+
+        Ok(inner_tx.map(|(inner_tx, height, addresses)| {
+            IndexerEvent::Transaction {
+                id: inner_tx.id,
+                wrapper_id: inner_tx.wrapper_id,
+                height,
+                kind: TransactionKind::from(inner_tx.kind),
+                exit_code: TransactionResult::from(inner_tx.exit_code),
+                addresses,
+            }
+        }))
+    }
+
+    /// Transfer amounts are expressed in the denomination picked by the
+    /// transaction, they are converted to the denomination of their token
+    async fn denominate_transfers(
+        &self,
+        inner_txs: Vec<InnerTransactionDb>,
+    ) -> Result<Vec<InnerTransaction>, TransactionError> {
+        let mut inner_txs = inner_txs
+            .into_iter()
+            .map(|inner_tx| {
+                let transfer = match (&inner_tx.kind, &inner_tx.data) {
+                    (TransactionKindDb::TransparentTransfer, Some(data)) => {
+                        serde_json::from_str::<TransparentTransfer>(data).ok()
+                    }
+                    _ => None,
+                };
+                (InnerTransaction::from(inner_tx), transfer)
+            })
+            .collect::<Vec<_>>();
+
+        let tokens = inner_txs
+            .iter()
+            .filter_map(|(_, transfer)| transfer.as_ref())
+            .flat_map(|transfer| {
+                transfer.sources.0.keys().chain(transfer.targets.0.keys())
+            })
+            .map(|account| account.token.to_string())
+            .collect::<HashSet<_>>();
+
+        if tokens.is_empty() {
+            return Ok(inner_txs.into_iter().map(|(tx, _)| tx).collect());
+        }
+
+        let denominations = self
+            .transaction_repo
+            .find_tokens_denomination(tokens.into_iter().collect())
+            .await
+            .map_err(TransactionError::Database)?
+            .into_iter()
+            .map(|(token, denomination)| (token, denomination as u8))
+            .collect::<HashMap<_, _>>();
+
+        for (inner_tx, transfer) in inner_txs.iter_mut() {
+            let Some(transfer) = transfer else {
+                continue;
+            };
+
+            for (account, amount) in transfer
+                .sources
+                .0
+                .iter_mut()
+                .chain(transfer.targets.0.iter_mut())
+            {
+                let Some(denomination) =
+                    denominations.get(&account.token.to_string())
+                else {
+                    continue;
+                };
+                if let Ok(denominated) =
+                    amount.increase_precision(Denomination(*denomination))
+                {
+                    *amount = denominated;
+                }
+            }
+
+            inner_tx.data = serde_json::to_string(transfer).ok();
+        }
+
+        Ok(inner_txs.into_iter().map(|(tx, _)| tx).collect())
     }
 }
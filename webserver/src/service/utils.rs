@@ -1,7 +1,14 @@
-use namada_core::token::Amount;
+use namada_core::token::{Amount, DenominatedAmount, Denomination};
 
 pub fn raw_amount_to_nam(raw_amount: String) -> String {
     Amount::from_str(raw_amount, 0)
         .expect("raw_amount is not a valid string")
         .to_string_native()
 }
+
+pub fn raw_amount_to_denominated(raw_amount: &str, denomination: u8) -> String {
+    let amount = Amount::from_str(raw_amount, 0)
+        .expect("raw_amount is not a valid string");
+    DenominatedAmount::new(amount, Denomination(denomination))
+        .to_string_precise()
+}
@@ -2,9 +2,11 @@ use namada_sdk::tendermint_rpc::HttpClient;
 
 use crate::appstate::AppState;
 use crate::config::AppConfig;
+use crate::event_bus::EventBus;
 use crate::service::balance::BalanceService;
 use crate::service::chain::ChainService;
 use crate::service::crawler_state::CrawlerStateService;
+use crate::service::export::ExportService;
 use crate::service::gas::GasService;
 use crate::service::governance::GovernanceService;
 use crate::service::pos::PosService;
@@ -21,12 +23,19 @@ pub struct CommonState {
     pub gas_service: GasService,
     pub transaction_service: TransactionService,
     pub crawler_state_service: CrawlerStateService,
+    pub export_service: ExportService,
+    pub event_bus: EventBus,
     pub client: HttpClient,
     pub config: AppConfig,
 }
 
 impl CommonState {
-    pub fn new(client: HttpClient, config: AppConfig, data: AppState) -> Self {
+    pub fn new(
+        client: HttpClient,
+        config: AppConfig,
+        data: AppState,
+        event_bus: EventBus,
+    ) -> Self {
         Self {
             pos_service: PosService::new(data.clone()),
             gov_service: GovernanceService::new(data.clone()),
@@ -36,6 +45,8 @@ impl CommonState {
             gas_service: GasService::new(data.clone()),
             transaction_service: TransactionService::new(data.clone()),
             crawler_state_service: CrawlerStateService::new(data.clone()),
+            export_service: ExportService::new(data.clone()),
+            event_bus,
             client,
             config,
         }